@@ -1,3 +1,4 @@
+use advent_of_code_rust::utils::{params, Day};
 use clap::{self, Parser};
 
 mod router;
@@ -5,21 +6,48 @@ mod router;
 /// Runs the specfied Advent of Code solution
 #[derive(Parser, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Year of the solution [possible values: 2015-2023 inclusive]
     #[arg(required = true)]
     #[arg(value_parser = clap::value_parser!(u32).range(2015..=2025))]
-    year: u32,
+    year: Option<u32>,
 
     /// Day of the solution  [possible values: 1-25 inclusive]
     #[arg(required = true)]
     #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
-    day: u8,
+    day: Option<u8>,
 
     /// Part of the solution
     #[clap(value_enum)]
     #[arg(short, long, default_value_t = Part::Both)]
     part: Part,
+
+    /// Animates the day's simulation instead of running the solution: in the terminal, or
+    /// written to FILE (a GIF if it ends in `.gif`, plain text otherwise)
+    #[arg(long, value_name = "FILE", num_args = 0..=1)]
+    animate: Option<Option<String>>,
+
+    /// When animating, only draws every Nth step
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    #[arg(value_parser = clap::value_parser!(u64).range(1..))]
+    every: u64,
+
+    /// When animating, how long to show each frame (in milliseconds)
+    #[arg(long, value_name = "MS", default_value_t = 100)]
+    delay: u64,
+
+    /// Overrides one of the solution's parameters (e.g., a grid size or number of steps); may
+    /// be repeated. An unknown NAME lists the parameters the solution accepts
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+
+    /// Prints the hit and miss statistics of the solution's memoized computations
+    #[arg(long)]
+    memo_stats: bool,
 }
 
 /// Represents a part of the day's solution to run (or both parts)
@@ -30,7 +58,45 @@ enum Part {
     Both,
 }
 
+/// Other things to do with a day besides running its solution
+#[derive(clap::Subcommand, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Command {
+    /// Prints the day's parsed input as a Graphviz DOT graph
+    Graph {
+        /// Year of the puzzle
+        #[arg(value_parser = clap::value_parser!(u32).range(2015..=2025))]
+        year: u32,
+
+        /// Day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Prints one of the day's debugging reports on its input
+    Report {
+        /// Year of the puzzle
+        #[arg(value_parser = clap::value_parser!(u32).range(2015..=2025))]
+        year: u32,
+
+        /// Day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Which report to print. An unknown NAME lists the reports there are
+        name: String,
+
+        /// The report's argument, if it takes one (e.g., a number of button pushes)
+        arg: Option<String>,
+    },
+}
+
 fn main() {
     let args = Args::parse();
-    router::run_solution(&args);
+    match args.command {
+        Some(Command::Graph { year, day }) => router::print_graph(&Day { year, day }),
+        Some(Command::Report { year, day, name, arg }) => {
+            router::print_report(&Day { year, day }, &name, arg.as_deref())
+        },
+        None if args.animate.is_some() => router::animate(&args),
+        None => router::run_solution(&args),
+    }
 }
\ No newline at end of file
@@ -1,38 +1,226 @@
 //! Utilities to take command line arguments and run the specified solution. 
-use std::collections::{HashMap, HashSet};
+use std::{collections::{HashMap, HashSet}, fmt, str::FromStr, time::Duration};
+use itertools::Itertools;
 use crate::{Args, Part};
-use advent_of_code_rust::utils::{solution::Solution, io_utils::{self, InputFileType}, Day};
+use advent_of_code_rust::utils::{dot::Graph, memo, params::{self, Param, ParamError}, solution::Solution, io_utils::{self, InputFileType}, visualize::{Animation, Target, Visualize}, Day};
 use advent_of_code_rust::{year_2015, year_2016, year_2017, year_2018, year_2023, year_2024, year_2025};
 
 /// Runs the solution(s) specified by the command line arguments.
 pub fn run_solution(args: &Args) {
 
-    let day = Day { year: args.year, day: args.day };
-
+    let day = day_from_args(args);
     let solns = get_solns(&day);
     let input_filename = io_utils::input_filename(&day, InputFileType::Input);
+    memo::report_stats(args.memo_stats);
+    let part_one = solns.part_one.filter(|_| !matches!(args.part, Part::Two));
+    let part_two = solns.part_two.filter(|_| !matches!(args.part, Part::One));
+
+    // When both parts run, each part only gets the parameters it declares, so
+    // only a name that neither part declares is an error.
+    let declared: Vec<Param> = part_one.iter()
+        .chain(part_two.iter())
+        .flat_map(|soln| soln.params())
+        .unique_by(|param| String::from(param.name()))
+        .collect();
+    if let Some((name, _value)) = args.params.iter().find(|(name, _value)| !is_declared(&declared, name)) {
+        println!("{}", ParamError::unknown(name, &declared));
+        return;
+    }
 
     if !matches!(args.part, Part::Two) {
-        match solns.part_one {
+        match part_one {
             Some(mut p_one) => {
                 println!("Part one:");
-                println!("{}", p_one.solve(&input_filename));            
+                let assignments = declared_params(p_one.as_ref(), &args.params);
+                match params::apply(p_one.as_mut(), &assignments) {
+                    Ok(()) => println!("{}", p_one.solve(&input_filename)),
+                    Err(err) => println!("{err}"),
+                }
             },
             None => println!("No solution found for part one of this day."),
         }
     }
 
     if !matches!(args.part, Part::One) {
-        match solns.part_two {
+        match part_two {
             Some(mut p_two) => {
                 println!("Part two:");
-                println!("{}", p_two.solve(&input_filename));            
+                let assignments = declared_params(p_two.as_ref(), &args.params);
+                match params::apply(p_two.as_mut(), &assignments) {
+                    Ok(()) => println!("{}", p_two.solve(&input_filename)),
+                    Err(err) => println!("{err}"),
+                }
             },
             None => println!("No solution found for part two of this day."),
         }
     }
 }
 
+fn is_declared(params: &[Param], name: &str) -> bool {
+    params.iter().any(|param| param.name() == name)
+}
+
+/// The parameter assignments for parameters the solution declares.
+fn declared_params(soln: &dyn Solution, assignments: &[(String, String)]) -> Vec<(String, String)> {
+    let params = soln.params();
+    assignments.iter()
+        .filter(|(name, _value)| is_declared(&params, name))
+        .cloned()
+        .collect()
+}
+
+/// A simulation to animate, built from an input file and parameter
+/// assignments.
+type Simulation = fn(&str, &[(String, String)]) -> Result<Box<dyn Visualize>, ParamError>;
+
+/// Checks that there are no parameter assignments, for simulations without
+/// parameters.
+fn no_params(assignments: &[(String, String)]) -> Result<(), ParamError> {
+    match assignments.first() {
+        Some((name, _value)) => Err(ParamError::unknown(name, &[])),
+        None => Ok(()),
+    }
+}
+
+/// Animates the simulation of the day specified by the command line arguments.
+pub fn animate(args: &Args) {
+    let day = day_from_args(args);
+    let simulations: HashMap<Day, Simulation> = HashMap::from([
+        (
+            Day { year: 2017, day: 22 },
+            (|filename, assignments| no_params(assignments).map(|()| year_2017::day_22::simulation(filename))) as Simulation,
+        ),
+        (
+            Day { year: 2018, day: 13 },
+            |filename, assignments| no_params(assignments).map(|()| year_2018::day_13::simulation(filename)),
+        ),
+        (
+            Day { year: 2018, day: 17 },
+            |filename, assignments| no_params(assignments).map(|()| year_2018::day_17::simulation(filename)),
+        ),
+        (
+            Day { year: 2024, day: 6 },
+            |filename, assignments| no_params(assignments).map(|()| year_2024::day_06::simulation(filename)),
+        ),
+        (Day { year: 2024, day: 14 }, year_2024::day_14::simulation),
+    ]);
+    match simulations.get(&day) {
+        Some(simulation) => {
+            let animation = Animation {
+                target: Target::from_filename(args.animate.as_ref().and_then(|filename| filename.as_deref())),
+                every: args.every.try_into().unwrap(),
+                delay: Duration::from_millis(args.delay),
+            };
+            let mut simulation = match simulation(&io_utils::input_filename(&day, InputFileType::Input), &args.params) {
+                Ok(simulation) => simulation,
+                Err(err) => {
+                    println!("{err}");
+                    return;
+                },
+            };
+            let frames = animation.run(simulation.as_mut())
+                .expect("Should be able to write the animation.");
+            if animation.target != Target::Terminal {
+                println!("Wrote {frames} frames.");
+            }
+        },
+        None => {
+            let days: Vec<String> = simulations.keys()
+                .sorted()
+                .map(|day| format!("{} day {}", day.year, day.day))
+                .collect();
+            println!("No simulation found for this day. Days with simulations: {}", days.join(", "));
+        },
+    }
+}
+
+/// Prints the graph of the specified day's input in DOT format.
+pub fn print_graph(day: &Day) {
+    let graphs: HashMap<Day, fn(&str) -> Graph> = HashMap::from([
+        (Day { year: 2017, day: 7 }, year_2017::day_07::graph as fn(&str) -> Graph),
+        (Day { year: 2023, day: 8 }, year_2023::day_08::graph),
+        (Day { year: 2023, day: 20 }, year_2023::day_20::graph),
+        (Day { year: 2023, day: 25 }, year_2023::day_25::graph),
+        (Day { year: 2024, day: 23 }, year_2024::day_23::graph),
+    ]);
+    match graphs.get(day) {
+        Some(graph) => print!("{}", graph(&io_utils::input_filename(day, InputFileType::Input))),
+        None => {
+            let days: Vec<String> = graphs.keys()
+                .sorted()
+                .map(|day| format!("{} day {}", day.year, day.day))
+                .collect();
+            println!("No graph found for this day. Days with graphs: {}", days.join(", "));
+        },
+    }
+}
+
+/// A debugging report on a day's input file, given the report's argument if
+/// there is one.
+type Report = fn(&str, Option<&str>) -> String;
+
+/// Prints the named report on the specified day's input.
+pub fn print_report(day: &Day, name: &str, arg: Option<&str>) {
+    let reports: HashMap<(Day, &str), Report> = HashMap::from([
+        (
+            (Day { year: 2015, day: 7 }, "wires_changed_by_override"),
+            (|filename, arg| {
+                year_2015::day_07::wires_changed_by_override(filename, &report_arg(arg, year_2015::day_07::Override::default()))
+            }) as Report,
+        ),
+        (
+            (Day { year: 2018, day: 15 }, "replay"),
+            |filename, arg| year_2018::day_15::replay(filename, report_arg(arg, 3)),
+        ),
+        (
+            (Day { year: 2018, day: 15 }, "attack_power"),
+            |filename, _arg| year_2018::day_15::attack_power_report(filename),
+        ),
+        (
+            (Day { year: 2023, day: 19 }, "workflows"),
+            |filename, _arg| year_2023::day_19::report(filename),
+        ),
+        (
+            (Day { year: 2023, day: 20 }, "pulse_histogram"),
+            |filename, arg| year_2023::day_20::pulse_histogram(filename, report_arg(arg, 1_000)),
+        ),
+        (
+            (Day { year: 2024, day: 14 }, "christmas_tree"),
+            |filename, arg| year_2024::day_14::christmas_tree(filename, report_arg(arg, year_2024::day_14::Score::Entropy)).1,
+        ),
+    ]);
+    match reports.get(&(Day { year: day.year, day: day.day }, name)) {
+        Some(report) => println!("{}", report(&io_utils::input_filename(day, InputFileType::Input), arg)),
+        None => {
+            let names: Vec<String> = reports.keys()
+                .sorted()
+                .map(|(day, name)| format!("{} day {} {name}", day.year, day.day))
+                .collect();
+            println!("No such report for this day. Reports: {}", names.join(", "));
+        },
+    }
+}
+
+/// Parses a report's argument, or returns `default` if there isn't one.
+fn report_arg<T>(arg: Option<&str>, default: T) -> T
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    arg.map_or(default, |arg| {
+        arg.parse().unwrap_or_else(|err| panic!("Report argument `{arg}` should be valid: {err}"))
+    })
+}
+
+/// Gets the day from the command line arguments, which are required unless
+/// running a subcommand.
+fn day_from_args(args: &Args) -> Day {
+    Day {
+        year: args.year.expect("Year should be required without a subcommand."),
+        day: args.day.expect("Day should be required without a subcommand."),
+    }
+}
+
 /// A day's solutions can include a solution to part one and/or a solution to part two,
 /// or neither.
 struct DailySolutions {
@@ -44,6 +232,41 @@ struct DailySolutions {
 fn get_solns(day: &Day) -> DailySolutions {
     // Mutable because we will later move out the daily solutions to be able to return them.
     let mut daily_solutions: HashMap<Day, DailySolutions> = HashMap::from([
+        (
+            Day { year: 2015, day: 1 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_01::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_01::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 2 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_02::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_02::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 3 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_03::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_03::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 4 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_04::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_04::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 5 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_05::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_05::part_two::Soln::default())),
+            },
+        ),
         (
             Day { year: 2015, day: 6 },
             DailySolutions { 
@@ -76,7 +299,7 @@ fn get_solns(day: &Day) -> DailySolutions {
             Day { year: 2015, day: 10 },
             DailySolutions { 
                 part_one: Some(Box::new(year_2015::day_10::part_one::Soln::default())),
-                part_two: None,
+                part_two: Some(Box::new(year_2015::day_10::part_two::Soln::default())),
             },
         ),
         (
@@ -135,6 +358,55 @@ fn get_solns(day: &Day) -> DailySolutions {
                 part_two: Some(Box::new(year_2015::day_18::part_two::Soln::default())),
             },
         ),
+        (
+            Day { year: 2015, day: 19 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_19::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_19::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 20 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_20::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_20::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 21 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_21::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_21::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 22 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_22::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_22::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 23 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_23::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_23::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 24 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_24::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2015::day_24::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2015, day: 25 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2015::day_25::part_one::Soln::default())),
+                part_two: None,
+            },
+        ),
         (
             Day { year: 2016, day: 1 },
             DailySolutions { 
@@ -209,7 +481,7 @@ fn get_solns(day: &Day) -> DailySolutions {
             Day { year: 2016, day: 11 },
             DailySolutions { 
                 part_one: Some(Box::new(year_2016::day_11::part_one::Soln::default())),
-                part_two: None,
+                part_two: Some(Box::new(year_2016::day_11::part_two::Soln::default())),
             },
         ),
         (
@@ -485,6 +757,13 @@ fn get_solns(day: &Day) -> DailySolutions {
                 part_two: None,
             },
         ),
+        (
+            Day { year: 2018, day: 1 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2018::day_01::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2018::day_01::part_two::Soln::default())),
+            },
+        ),
         (
             Day { year: 2018, day: 2 },
             DailySolutions { 
@@ -909,7 +1188,14 @@ fn get_solns(day: &Day) -> DailySolutions {
             Day { year: 2024, day: 12 },
             DailySolutions { 
                 part_one: Some(Box::new(year_2024::day_12::part_one::Soln::default())),
-                part_two: None,
+                part_two: Some(Box::new(year_2024::day_12::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 13 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_13::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_13::part_two::Soln::default())),
             },
         ),
         (
@@ -919,6 +1205,62 @@ fn get_solns(day: &Day) -> DailySolutions {
                 part_two: Some(Box::new(year_2024::day_14::part_two::Soln::default())),
             },
         ),
+        (
+            Day { year: 2024, day: 15 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_15::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_15::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 16 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_16::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_16::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 17 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_17::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_17::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 18 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_18::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_18::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 19 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_19::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_19::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 20 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_20::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_20::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 21 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_21::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_21::part_two::Soln::default())),
+            },
+        ),
+        (
+            Day { year: 2024, day: 22 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_22::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_22::part_two::Soln::default())),
+            },
+        ),
         (
             Day { year: 2024, day: 23 },
             DailySolutions { 
@@ -926,6 +1268,13 @@ fn get_solns(day: &Day) -> DailySolutions {
                 part_two: Some(Box::new(year_2024::day_23::part_two::Soln::default())),
             },
         ),
+        (
+            Day { year: 2024, day: 24 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2024::day_24::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2024::day_24::part_two::Soln::default())),
+            },
+        ),
         (
             Day { year: 2024, day: 25 },
             DailySolutions { 
@@ -989,6 +1338,13 @@ fn get_solns(day: &Day) -> DailySolutions {
                 part_two: Some(Box::new(year_2025::day_08::part_two::Soln::default())),
             },
         ),
+        (
+            Day { year: 2025, day: 9 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2025::day_09::part_one::Soln::default())),
+                part_two: Some(Box::new(year_2025::day_09::part_two::Soln::default())),
+            },
+        ),
         (
             Day { year: 2025, day: 10 },
             DailySolutions { 
@@ -1003,6 +1359,13 @@ fn get_solns(day: &Day) -> DailySolutions {
                 part_two: Some(Box::new(year_2025::day_11::part_two::Soln::default())),
             },
         ),
+        (
+            Day { year: 2025, day: 12 },
+            DailySolutions { 
+                part_one: Some(Box::new(year_2025::day_12::part_one::Soln::default())),
+                part_two: None,
+            },
+        ),
     ]);
     let daily_solns = match daily_solutions.remove(day) {
         Some(daily_solns) => daily_solns,
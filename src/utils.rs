@@ -11,6 +11,8 @@ pub mod solution {
     //! A collection of solution-oriented utilities.
     use std::fmt;
 
+    use super::params::{Param, ParamError};
+
     /// The possible types of an answer to a solution.
     #[derive(PartialEq, Eq, Debug)]
     pub enum Answer {
@@ -42,6 +44,17 @@ pub mod solution {
     pub trait Solution {
         /// Solves the puzzle given a path to an input file.
         fn solve(&mut self, filename: &str) -> Answer;
+
+        /// Lists the parameters the solution accepts (see `params`), with their
+        /// current values.
+        fn params(&self) -> Vec<Param> {
+            Vec::new()
+        }
+
+        /// Overrides one of the parameters listed by `params` before solving.
+        fn set_param(&mut self, name: &str, _value: &str) -> Result<(), ParamError> {
+            Err(ParamError::unknown(name, &self.params()))
+        }
     }
 }
 
@@ -93,6 +106,119 @@ pub mod io_utils {
         let file = File::open(filename)?;
         Ok(io::BufReader::new(file).lines())
     }
+
+    /// A file split into sections separated by blank lines, for inputs whose
+    /// parts have different shapes (e.g., rules followed by updates).
+    ///
+    /// # Examples
+    /// ```
+    /// use advent_of_code_rust::utils::io_utils::Sections;
+    ///
+    /// let sections = Sections::from_lines("47|53\n97|13\n\n75,47,61\n".lines(), 2);
+    /// assert_eq!(sections.nth(0).lines().collect::<Vec<_>>(), vec!["47|53", "97|13"]);
+    /// assert_eq!(sections.nth(1).first_line(), 4);
+    /// ```
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Sections {
+        sections: Vec<Section>,
+    }
+
+    /// One blank-line-separated section of a file.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Section {
+        first_line: usize,
+        lines: Vec<String>,
+    }
+
+    impl Sections {
+        /// Reads the file's sections, panicking unless there are exactly
+        /// `expected` of them.
+        pub fn from_file(filename: &str, expected: usize) -> Self {
+            Self::from_lines(file_to_lines(filename), expected)
+        }
+
+        /// Reads the file's sections, however many there are.
+        pub fn from_file_any(filename: &str) -> Self {
+            Self::split(file_to_lines(filename))
+        }
+
+        /// Splits lines into sections, panicking unless there are exactly
+        /// `expected` of them.
+        pub fn from_lines<L>(lines: impl Iterator<Item = L>, expected: usize) -> Self
+        where L: AsRef<str>, {
+            let sections = Self::split(lines);
+            assert_eq!(sections.len(), expected, "Input should have exactly {expected} sections.");
+            sections
+        }
+
+        fn split<L>(lines: impl Iterator<Item = L>) -> Self
+        where L: AsRef<str>, {
+            let mut sections: Vec<Section> = Vec::new();
+            let mut current: Option<Section> = None;
+            for (idx, line) in lines.enumerate() {
+                let line = line.as_ref();
+                if line.trim().is_empty() {
+                    sections.extend(current.take());
+                } else {
+                    current.get_or_insert_with(|| Section { first_line: idx + 1, lines: Vec::new() })
+                        .lines
+                        .push(String::from(line));
+                }
+            }
+            sections.extend(current);
+            Self { sections }
+        }
+
+        /// Returns the number of sections.
+        pub fn len(&self) -> usize {
+            self.sections.len()
+        }
+
+        /// Returns whether there are no sections.
+        pub fn is_empty(&self) -> bool {
+            self.sections.is_empty()
+        }
+
+        /// Returns the section at index `idx` (0-indexed).
+        pub fn nth(&self, idx: usize) -> &Section {
+            self.sections.get(idx).expect("Section should exist.")
+        }
+
+        /// Iterates over the sections in order.
+        pub fn iter(&self) -> impl Iterator<Item = &Section> {
+            self.sections.iter()
+        }
+    }
+
+    impl Section {
+        /// Returns the (1-based) line number of the section's first line in
+        /// the file.
+        pub fn first_line(&self) -> usize {
+            self.first_line
+        }
+
+        /// Iterates over the section's lines.
+        pub fn lines(&self) -> impl Iterator<Item = &str> {
+            self.lines.iter().map(|line| line.as_str())
+        }
+
+        /// Returns the section as a grid of characters, indexed by row then
+        /// column.
+        pub fn grid(&self) -> Vec<Vec<char>> {
+            super::parse::char_grid(self.lines())
+        }
+
+        /// Parses each line of the section into a `T`. Panics with the file
+        /// line and column if any line fails to parse.
+        pub fn parse_lines<T>(&self) -> Vec<T>
+        where T: std::str::FromStr<Err = super::parse::ParseError>, {
+            super::parse::parse_lines(self.lines())
+                .unwrap_or_else(|err| {
+                    let line = err.line + self.first_line - 1;
+                    panic!("Should be able to parse the section: {}", err.at_line(line))
+                })
+        }
+    }
 }
 
 pub mod math_utils {
@@ -123,12 +249,59 @@ pub mod math_utils {
         let greatest_common_divisor: u64 = common_divisors.iter().fold(1, |acc, (divisor, count)| acc * divisor.pow((*count).try_into().unwrap()));
         inputs.map(|period| period / greatest_common_divisor).product::<u64>() * greatest_common_divisor
     }
+
+    /// Solves a system of congruences `x ≡ residue (mod modulus)`, given as
+    /// `(residue, modulus)` pairs, by the Chinese remainder theorem. Returns
+    /// the smallest non-negative solution, or `None` if the congruences are
+    /// inconsistent. The moduli need not be coprime.
+    ///
+    /// # Examples
+    /// ```
+    /// use advent_of_code_rust::utils::math_utils::chinese_remainder;
+    ///
+    /// assert_eq!(chinese_remainder([(2, 3), (3, 5), (2, 7)]), Some(23));
+    /// assert_eq!(chinese_remainder([(1, 4), (3, 6)]), Some(9));
+    /// assert_eq!(chinese_remainder([(1, 4), (2, 6)]), None);
+    /// ```
+    pub fn chinese_remainder(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<i64> {
+        let (residue, _) = congruences.into_iter()
+            .try_fold((0_i128, 1_i128), |(residue, modulus), (next_residue, next_modulus)| {
+                let next_modulus = next_modulus as i128;
+                let (gcd, inverse, _) = extended_gcd(modulus, next_modulus);
+                let difference = next_residue as i128 - residue;
+                if difference % gcd != 0 { return None; }
+                let lcm = modulus / gcd * next_modulus;
+                let step = (difference / gcd * inverse).rem_euclid(next_modulus / gcd);
+                Some(((residue + modulus * step).rem_euclid(lcm), lcm))
+            })?;
+        Some(residue.try_into().expect("Solution should fit into `i64` datatype."))
+    }
+
+    /// Returns `(gcd, x, y)` such that `a * x + b * y == gcd`.
+    fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+        if b == 0 { return (a, 1, 0); }
+        let (gcd, x, y) = extended_gcd(b, a.rem_euclid(b));
+        (gcd, y, x - a.div_euclid(b) * y)
+    }
 }
 
+pub mod automaton;
+pub mod dag;
+pub mod disjoint_set;
+pub mod dot;
+pub mod exact_cover;
+pub mod ilp;
+pub mod memo;
+pub mod params;
+pub mod parse;
+pub mod polygon;
+pub mod rational;
+pub mod visualize;
+
 #[cfg(test)]
 pub mod test_utils {
     //! A collection of testing-oriented utilities.
-    use super::{solution::{Solution, Answer}, io_utils::{InputFileType, input_filename}, Day};
+    use super::{solution::{Solution, Answer}, io_utils::{InputFileType, input_filename}, params, Day};
 
     /// Confirms that the solution's answer matches the example answer.
     pub fn check_example_case<T: Solution>(
@@ -142,4 +315,21 @@ pub mod test_utils {
             answer,
         );
     }
+
+    /// Confirms that the solution's answer matches the example answer, after
+    /// overriding parameters as the example requires (e.g., a smaller grid).
+    pub fn check_example_case_with_params<T, N, V>(
+        soln: &mut T,
+        params: &[(N, V)],
+        example_key: u8,
+        answer: Answer,
+        day: &Day,
+    ) where
+        T: Solution,
+        N: AsRef<str>,
+        V: AsRef<str>,
+    {
+        params::apply(soln, params).expect("Example parameters should be valid.");
+        check_example_case(soln, example_key, answer, day);
+    }
 }
\ No newline at end of file
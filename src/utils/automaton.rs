@@ -0,0 +1,455 @@
+//! A cellular automaton engine for the "Game of Life" style puzzles.
+//!
+//! An [`Automaton`] combines a [`Neighborhood`] (which cells influence a cell),
+//! [`Bounds`] (how the grid is stored and what lies past its edges) and a rule
+//! function mapping a cell's current state and its neighbors' states to its
+//! next state. Every cell is updated simultaneously each generation, using a
+//! second buffer that is swapped in once the generation is complete.
+//!
+//! One-dimensional automata use a single row (`y == 0`) with a
+//! [`Neighborhood::Window`].
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Sub};
+
+/// A cell's position. `x` is the column and `y` the row.
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Position {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Position {
+    pub fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Position {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Sub for Position {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl Mul<isize> for Position {
+    type Output = Self;
+
+    fn mul(self, factor: isize) -> Self {
+        Self { x: self.x * factor, y: self.y * factor }
+    }
+}
+
+/// The cells whose states are passed to the rule, in the order listed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Neighborhood {
+    /// The `2 * radius + 1` cells of the same row centered on the cell,
+    /// left to right, *including* the cell itself.
+    Window(usize),
+    /// The 8 surrounding cells in reading order, excluding the cell itself.
+    Moore,
+    /// The 4 orthogonally adjacent cells in reading order (up, left, right,
+    /// down), excluding the cell itself.
+    VonNeumann,
+}
+
+impl Neighborhood {
+    fn offsets(&self) -> Vec<Position> {
+        match self {
+            Self::Window(radius) => {
+                let radius = *radius as isize;
+                (-radius..=radius).map(|x| Position::new(x, 0)).collect()
+            },
+            Self::Moore => {
+                (-1..=1)
+                    .flat_map(|y| (-1..=1).map(move |x| Position::new(x, y)))
+                    .filter(|offset| *offset != Position::default())
+                    .collect()
+            },
+            Self::VonNeumann => vec![
+                Position::new(0, -1),
+                Position::new(-1, 0),
+                Position::new(1, 0),
+                Position::new(0, 1),
+            ],
+        }
+    }
+}
+
+/// How the cells are stored and what lies past the edge of the grid.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Bounds<S> {
+    /// A `width` x `height` grid with its top left cell at the origin.
+    /// Neighbors past the edge are omitted, so the rule may receive fewer
+    /// neighbors for edge cells. Not meaningful for window neighborhoods.
+    Bounded { width: usize, height: usize },
+    /// A `width` x `height` grid with its top left cell at the origin.
+    /// Neighbors past the edge always read as `edge`.
+    FixedEdge { width: usize, height: usize, edge: S },
+    /// An unbounded grid. Only cells not in the default state are stored, so
+    /// the rule must map a default cell with all-default neighbors to the
+    /// default state.
+    Infinite,
+}
+
+/// Maps a cell's current state and its neighbors' states to its next state.
+type Rule<S> = Box<dyn Fn(S, &[S]) -> S>;
+
+/// A snapshot of the cells, used to detect repeated generations.
+#[derive(PartialEq, Eq, Hash)]
+enum Snapshot<S> {
+    Dense(Vec<S>),
+    /// Sorted by position, relative to the minimum position in each axis.
+    Sparse(Vec<(Position, S)>),
+}
+
+#[derive(Debug, Clone)]
+enum Cells<S> {
+    Dense { width: usize, height: usize, current: Vec<S>, next: Vec<S> },
+    Sparse { current: HashMap<Position, S>, next: HashMap<Position, S> },
+}
+
+/// A cellular automaton with cells in states of type `S`. `S::default()` is
+/// the initial state of every cell.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::automaton::{Automaton, Bounds, Neighborhood, Position};
+///
+/// // Conway's Game of Life: a blinker oscillates with period 2.
+/// let mut life = Automaton::new(
+///     Neighborhood::Moore,
+///     Bounds::Bounded { width: 5, height: 5 },
+///     |alive: bool, neighbors: &[bool]| {
+///         let alive_neighbors = neighbors.iter().filter(|n| **n).count();
+///         alive_neighbors == 3 || alive && alive_neighbors == 2
+///     },
+/// );
+/// for x in 1..=3 {
+///     life.set(Position::new(x, 2), true);
+/// }
+/// life.step();
+/// assert!(life.get(Position::new(2, 1)) && !life.get(Position::new(1, 2)));
+/// life.advance_to(1_000_000_001);
+/// assert!(life.get(Position::new(2, 1)) && !life.get(Position::new(1, 2)));
+/// assert_eq!(life.count(true), 3);
+/// ```
+pub struct Automaton<S> {
+    offsets: Vec<Position>,
+    bounds: Bounds<S>,
+    rule: Rule<S>,
+    cells: Cells<S>,
+    generation: usize,
+}
+
+impl<S> fmt::Debug for Automaton<S>
+where
+    S: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Automaton")
+            .field("offsets", &self.offsets)
+            .field("bounds", &self.bounds)
+            .field("cells", &self.cells)
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Automaton<S>
+where
+    S: Copy + Eq + Hash + Default
+{
+    pub fn new(
+        neighborhood: Neighborhood,
+        bounds: Bounds<S>,
+        rule: impl Fn(S, &[S]) -> S + 'static,
+    ) -> Self {
+        let cells = match bounds {
+            Bounds::Bounded { width, height } | Bounds::FixedEdge { width, height, .. } => {
+                Cells::Dense {
+                    width,
+                    height,
+                    current: vec![S::default(); width * height],
+                    next: vec![S::default(); width * height],
+                }
+            },
+            Bounds::Infinite => Cells::Sparse { current: HashMap::new(), next: HashMap::new() },
+        };
+        Self {
+            offsets: neighborhood.offsets(),
+            bounds,
+            rule: Box::new(rule),
+            cells,
+            generation: 0,
+        }
+    }
+
+    /// Returns the number of generations stepped so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Returns the state of the cell at `position`. Positions past the edge of
+    /// a bounded grid read as the default state (or the fixed edge state).
+    pub fn get(&self, position: Position) -> S {
+        match &self.cells {
+            Cells::Dense { width, height, current, .. } => {
+                match dense_idx(position, *width, *height) {
+                    Some(idx) => current[idx],
+                    None => match self.bounds {
+                        Bounds::FixedEdge { edge, .. } => edge,
+                        _ => S::default(),
+                    },
+                }
+            },
+            Cells::Sparse { current, .. } => current.get(&position).copied().unwrap_or_default(),
+        }
+    }
+
+    /// Sets the state of the cell at `position`. Panics if `position` is past
+    /// the edge of a bounded grid.
+    pub fn set(&mut self, position: Position, state: S) {
+        match &mut self.cells {
+            Cells::Dense { width, height, current, .. } => {
+                let idx = dense_idx(position, *width, *height)
+                    .expect("Position should be within the bounds of the grid.");
+                current[idx] = state;
+            },
+            Cells::Sparse { current, .. } => {
+                if state == S::default() {
+                    current.remove(&position);
+                } else {
+                    current.insert(position, state);
+                }
+            },
+        }
+    }
+
+    /// Iterates over the stored cells: every cell for a bounded grid, or every
+    /// cell not in the default state for an infinite grid.
+    pub fn cells(&self) -> Box<dyn Iterator<Item = (Position, S)> + '_> {
+        match &self.cells {
+            Cells::Dense { width, current, .. } => Box::new(
+                current.iter().enumerate().map(move |(idx, state)| {
+                    (Position::new((idx % width) as isize, (idx / width) as isize), *state)
+                })
+            ),
+            Cells::Sparse { current, .. } => Box::new(
+                current.iter().map(|(position, state)| (*position, *state))
+            ),
+        }
+    }
+
+    /// Returns the number of cells in `state`. Panics when counting default
+    /// cells on an infinite grid.
+    pub fn count(&self, state: S) -> usize {
+        if let Cells::Sparse { .. } = self.cells {
+            assert!(state != S::default(), "An infinite grid has infinitely many default cells.");
+        }
+        self.cells().filter(|(_, s)| *s == state).count()
+    }
+
+    /// Advances one generation.
+    pub fn step(&mut self) {
+        let mut neighbors = Vec::with_capacity(self.offsets.len());
+        match &mut self.cells {
+            Cells::Dense { width, height, current, next } => {
+                for idx in 0..current.len() {
+                    let position = Position::new((idx % *width) as isize, (idx / *width) as isize);
+                    neighbors.clear();
+                    for offset in self.offsets.iter() {
+                        match dense_idx(position + *offset, *width, *height) {
+                            Some(neighbor_idx) => neighbors.push(current[neighbor_idx]),
+                            None => if let Bounds::FixedEdge { edge, .. } = self.bounds {
+                                neighbors.push(edge);
+                            },
+                        }
+                    }
+                    next[idx] = (self.rule)(current[idx], &neighbors);
+                }
+                std::mem::swap(current, next);
+            },
+            Cells::Sparse { current, next } => {
+                next.clear();
+                let candidates = current.keys()
+                    .flat_map(|position| {
+                        self.offsets.iter().map(move |offset| *position - *offset)
+                    })
+                    .chain(current.keys().copied());
+                for position in candidates {
+                    if next.contains_key(&position) { continue; }
+                    neighbors.clear();
+                    neighbors.extend(self.offsets.iter().map(|offset| {
+                        current.get(&(position + *offset)).copied().unwrap_or_default()
+                    }));
+                    let state = (self.rule)(
+                        current.get(&position).copied().unwrap_or_default(),
+                        &neighbors,
+                    );
+                    if state != S::default() {
+                        next.insert(position, state);
+                    }
+                }
+                std::mem::swap(current, next);
+            },
+        }
+        self.generation += 1;
+    }
+
+    /// Advances `generations` generations.
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+
+    /// Advances until `generation` generations have been stepped, detecting
+    /// when the cells repeat an earlier generation and skipping whole
+    /// periods. On an infinite grid, a pattern that repeats shifted in space
+    /// (a glider, say) is also detected and shifted ahead accordingly.
+    pub fn advance_to(&mut self, generation: usize) {
+        let mut history: HashMap<Snapshot<S>, (usize, Position)> = HashMap::new();
+        while self.generation < generation {
+            let (snapshot, origin) = self.snapshot();
+            if let Some((first_generation, first_origin)) = history.get(&snapshot) {
+                let period = self.generation - first_generation;
+                let periods = (generation - self.generation) / period;
+                let shift = (origin - *first_origin) * periods as isize;
+                self.translate(shift);
+                self.generation += periods * period;
+                break;
+            }
+            history.insert(snapshot, (self.generation, origin));
+            self.step();
+        }
+        self.run(generation - self.generation);
+    }
+
+    /// Returns a snapshot of the cells and the position the snapshot is
+    /// relative to.
+    fn snapshot(&self) -> (Snapshot<S>, Position) {
+        match &self.cells {
+            Cells::Dense { current, .. } => (Snapshot::Dense(current.clone()), Position::default()),
+            Cells::Sparse { current, .. } => {
+                let origin = Position::new(
+                    current.keys().map(|position| position.x).min().unwrap_or_default(),
+                    current.keys().map(|position| position.y).min().unwrap_or_default(),
+                );
+                let mut cells: Vec<(Position, S)> = current.iter()
+                    .map(|(position, state)| (*position - origin, *state))
+                    .collect();
+                cells.sort_by_key(|(position, _)| *position);
+                (Snapshot::Sparse(cells), origin)
+            },
+        }
+    }
+
+    /// Moves every cell by `shift`. Only infinite grids can move.
+    fn translate(&mut self, shift: Position) {
+        if shift == Position::default() { return; }
+        match &mut self.cells {
+            Cells::Dense { .. } => panic!("Cells on a bounded grid cannot move."),
+            Cells::Sparse { current, .. } => {
+                *current = current.drain()
+                    .map(|(position, state)| (position + shift, state))
+                    .collect();
+            },
+        }
+    }
+}
+
+/// Converts a position to an index into a dense row-major grid, if it is
+/// within the grid.
+fn dense_idx(position: Position, width: usize, height: usize) -> Option<usize> {
+    if position.x < 0 || position.y < 0 { return None; }
+    let (x, y) = (position.x as usize, position.y as usize);
+    if x >= width || y >= height { return None; }
+    Some(y * width + x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(bounds: Bounds<bool>) -> Automaton<bool> {
+        Automaton::new(
+            Neighborhood::Moore,
+            bounds,
+            |alive, neighbors| {
+                let alive_neighbors = neighbors.iter().filter(|n| **n).count();
+                alive_neighbors == 3 || alive && alive_neighbors == 2
+            },
+        )
+    }
+
+    #[test]
+    fn glider_moves_on_infinite_grid() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut automaton = life(Bounds::Infinite);
+        for (x, y) in glider {
+            automaton.set(Position::new(x, y), true);
+        }
+        // A glider moves one cell diagonally every 4 generations.
+        automaton.run(4);
+        let mut cells: Vec<Position> = automaton.cells().map(|(position, _)| position).collect();
+        cells.sort();
+        let mut expected: Vec<Position> = glider.iter()
+            .map(|(x, y)| Position::new(x + 1, y + 1))
+            .collect();
+        expected.sort();
+        assert_eq!(cells, expected);
+    }
+
+    #[test]
+    fn advance_to_skips_translated_periods() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut automaton = life(Bounds::Infinite);
+        for (x, y) in glider {
+            automaton.set(Position::new(x, y), true);
+        }
+        automaton.advance_to(4_000_000_002);
+        let mut expected = life(Bounds::Infinite);
+        for (x, y) in glider {
+            expected.set(Position::new(x + 1_000_000_000, y + 1_000_000_000), true);
+        }
+        expected.run(2);
+        assert_eq!(automaton.generation(), 4_000_000_002);
+        for (position, state) in expected.cells() {
+            assert_eq!(automaton.get(position), state);
+        }
+        assert_eq!(automaton.count(true), expected.count(true));
+    }
+
+    #[test]
+    fn fixed_edge_window_is_correct() {
+        // Rule 90: a cell becomes the XOR of its two neighbors.
+        let mut automaton = Automaton::new(
+            Neighborhood::Window(1),
+            Bounds::FixedEdge { width: 7, height: 1, edge: false },
+            |_, neighbors: &[bool]| neighbors[0] ^ neighbors[2],
+        );
+        automaton.set(Position::new(3, 0), true);
+        let render = |automaton: &Automaton<bool>| -> String {
+            (0..7).map(|x| if automaton.get(Position::new(x, 0)) { '#' } else { '.' }).collect()
+        };
+        assert_eq!(render(&automaton), "...#...");
+        automaton.step();
+        assert_eq!(render(&automaton), "..#.#..");
+        automaton.step();
+        assert_eq!(render(&automaton), ".#...#.");
+        automaton.step();
+        assert_eq!(render(&automaton), "#.#.#.#");
+        assert_eq!(automaton.get(Position::new(-1, 0)), false);
+    }
+}
@@ -0,0 +1,282 @@
+//! Directed acyclic graphs: topological order and counting paths.
+//!
+//! Counting paths in a DAG only takes one pass over the nodes in
+//! topological order, adding each node's count to its successors'. The
+//! number of paths grows exponentially with the graph's depth, so counts
+//! are `u128`.
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hash;
+
+/// The graph isn't acyclic: the nodes of one of its cycles, in edge order,
+/// starting and ending with the same node.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CycleError<T> {
+    pub cycle: Vec<T>,
+}
+
+impl<T> fmt::Display for CycleError<T>
+where
+    T: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes: Vec<String> = self.cycle.iter().map(|node| format!("{node:?}")).collect();
+        write!(f, "graph has a cycle: {}", nodes.join(" -> "))
+    }
+}
+
+impl<T> std::error::Error for CycleError<T> where T: fmt::Debug {}
+
+/// A directed graph meant to be acyclic, with nodes of type `T`. Nodes are
+/// added implicitly by `add_edge`, or explicitly with `add_node`.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::dag::Dag;
+///
+/// let dag: Dag<char> = [('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd'), ('d', 'e')].into_iter().collect();
+/// assert_eq!(dag.num_paths(&'a', &'e'), Ok(2));
+/// assert_eq!(dag.num_paths_via(&'a', &'e', &['d', 'b']), Ok(1));
+/// assert_eq!(dag.num_paths_via_in_order(&'a', &'e', &['d', 'b']), Ok(0));
+///
+/// let mut cyclic = dag.clone();
+/// cyclic.add_edge('e', 'b');
+/// assert_eq!(cyclic.num_paths(&'a', &'e').unwrap_err().cycle, vec!['b', 'd', 'e', 'b']);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Dag<T>
+where
+    T: Eq + Hash + Clone
+{
+    indices: HashMap<T, usize>,
+    nodes: Vec<T>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl<T> Default for Dag<T>
+where
+    T: Eq + Hash + Clone
+{
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            nodes: Vec::new(),
+            successors: Vec::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(T, T)> for Dag<T>
+where
+    T: Eq + Hash + Clone
+{
+    /// Creates a graph from its edges.
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut dag = Self::default();
+        iter.into_iter().for_each(|(from, to)| dag.add_edge(from, to));
+        dag
+    }
+}
+
+impl<T> Dag<T>
+where
+    T: Eq + Hash + Clone
+{
+    /// Adds `node` if it isn't already in the graph, and returns its index.
+    pub fn add_node(&mut self, node: T) -> usize {
+        if let Some(idx) = self.indices.get(&node) {
+            return *idx;
+        }
+        self.indices.insert(node.clone(), self.nodes.len());
+        self.nodes.push(node);
+        self.successors.push(Vec::new());
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: T, to: T) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.successors[from].push(to);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &T) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    /// The nodes' indices ordered so that every edge goes forward, using
+    /// [Kahn's algorithm](https://en.wikipedia.org/wiki/Topological_sorting#Kahn's_algorithm).
+    fn order(&self) -> Result<Vec<usize>, CycleError<T>> {
+        let mut in_degrees = vec![0; self.len()];
+        self.successors.iter().flatten().for_each(|to| in_degrees[*to] += 1);
+        let mut order: Vec<usize> = (0..self.len()).filter(|idx| in_degrees[*idx] == 0).collect();
+        let mut next = 0;
+        while let Some(from) = order.get(next).copied() {
+            next += 1;
+            for to in &self.successors[from] {
+                in_degrees[*to] -= 1;
+                if in_degrees[*to] == 0 {
+                    order.push(*to);
+                }
+            }
+        }
+        if order.len() < self.len() {
+            return Err(self.find_cycle(&in_degrees));
+        }
+        Ok(order)
+    }
+
+    /// A cycle among the nodes Kahn's algorithm couldn't order, which are
+    /// those left with edges in. Each of them has a predecessor among them,
+    /// so walking back through predecessors must come round to a node
+    /// again.
+    fn find_cycle(&self, in_degrees: &[usize]) -> CycleError<T> {
+        let mut predecessors: HashMap<usize, usize> = HashMap::new();
+        for (from, successors) in self.successors.iter().enumerate().filter(|(from, _)| in_degrees[*from] > 0) {
+            for to in successors.iter().filter(|to| in_degrees[**to] > 0) {
+                predecessors.insert(*to, from);
+            }
+        }
+        let mut node = *predecessors.keys().min().expect("Some node should be on a cycle.");
+        let mut walk: Vec<usize> = Vec::new();
+        let mut seen: HashSet<usize> = HashSet::new();
+        while seen.insert(node) {
+            walk.push(node);
+            node = predecessors[&node];
+        }
+        // The walk from its first visit to `node` is the cycle backwards.
+        let start = walk.iter().position(|idx| *idx == node).unwrap();
+        let cycle = std::iter::once(&node)
+            .chain(walk[start..].iter().rev())
+            .map(|idx| self.nodes[*idx].clone())
+            .collect();
+        CycleError { cycle }
+    }
+
+    /// The nodes ordered so that every edge goes forward.
+    pub fn topological_order(&self) -> Result<Vec<&T>, CycleError<T>> {
+        Ok(self.order()?.into_iter().map(|idx| &self.nodes[idx]).collect())
+    }
+
+    /// The number of paths from `from` to each node, by index.
+    fn path_counts(&self, from: &T, order: &[usize]) -> Vec<u128> {
+        let mut counts = vec![0; self.len()];
+        let Some(from) = self.indices.get(from) else { return counts; };
+        counts[*from] = 1;
+        for idx in order.iter().skip_while(|idx| *idx != from) {
+            let count = counts[*idx];
+            if count > 0 {
+                self.successors[*idx].iter().for_each(|to| counts[*to] += count);
+            }
+        }
+        counts
+    }
+
+    /// The number of paths from `from` to every node it reaches (including
+    /// itself, by the empty path).
+    pub fn num_paths_from(&self, from: &T) -> Result<HashMap<&T, u128>, CycleError<T>> {
+        let counts = self.path_counts(from, &self.order()?);
+        Ok(
+            self.nodes.iter()
+                .zip(counts)
+                .filter(|(_node, count)| *count > 0)
+                .collect()
+        )
+    }
+
+    pub fn num_paths(&self, from: &T, to: &T) -> Result<u128, CycleError<T>> {
+        self.num_paths_via_in_order(from, to, &[])
+    }
+
+    /// The number of paths from `from` to `to` that pass through each of
+    /// the waypoints, in the order given.
+    pub fn num_paths_via_in_order(&self, from: &T, to: &T, waypoints: &[T]) -> Result<u128, CycleError<T>> {
+        let order = self.order()?;
+        let stops: Vec<&T> = std::iter::once(from).chain(waypoints).chain(std::iter::once(to)).collect();
+        Ok(
+            stops.windows(2)
+                .map(|leg| match self.indices.get(leg[1]) {
+                    Some(idx) => self.path_counts(leg[0], &order)[*idx],
+                    None => u128::from(leg[0] == leg[1]),
+                })
+                .product()
+        )
+    }
+
+    /// The number of paths from `from` to `to` that pass through each of
+    /// the waypoints, in any order. A path through a DAG visits nodes in
+    /// topological order, so that is the only order that can count.
+    pub fn num_paths_via(&self, from: &T, to: &T, waypoints: &[T]) -> Result<u128, CycleError<T>> {
+        let position: HashMap<usize, usize> = self.order()?
+            .into_iter()
+            .enumerate()
+            .map(|(position, idx)| (idx, position))
+            .collect();
+        let mut waypoints = waypoints.to_vec();
+        if waypoints.iter().any(|waypoint| !self.contains(waypoint)) {
+            return Ok(0);
+        }
+        waypoints.sort_by_key(|waypoint| position[&self.indices[waypoint]]);
+        self.num_paths_via_in_order(from, to, &waypoints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A ladder of `rungs` diamonds, each doubling the paths from the
+    /// bottom to the top.
+    fn ladder(rungs: u32) -> Dag<(u32, u8)> {
+        (0..rungs)
+            .flat_map(|rung| [
+                ((rung, 0), (rung, 1)),
+                ((rung, 0), (rung, 2)),
+                ((rung, 1), (rung + 1, 0)),
+                ((rung, 2), (rung + 1, 0)),
+            ])
+            .collect()
+    }
+
+    #[test]
+    fn path_counts_are_correct() {
+        let dag = ladder(100);
+        assert_eq!(dag.num_paths(&(0, 0), &(100, 0)), Ok(1 << 100));
+        assert_eq!(dag.num_paths(&(100, 0), &(0, 0)), Ok(0));
+        assert_eq!(dag.num_paths(&(3, 0), &(3, 0)), Ok(1));
+        assert_eq!(dag.num_paths(&(0, 0), &(200, 0)), Ok(0));
+        let from_top_rung = dag.num_paths_from(&(99, 0)).unwrap();
+        assert_eq!(from_top_rung.len(), 4);
+        assert_eq!(from_top_rung[&(100, 0)], 2);
+    }
+
+    #[test]
+    fn waypoints_are_visited() {
+        let dag = ladder(10);
+        assert_eq!(dag.num_paths_via(&(0, 0), &(10, 0), &[(5, 1), (2, 2)]), Ok(1 << 8));
+        assert_eq!(dag.num_paths_via_in_order(&(0, 0), &(10, 0), &[(2, 2), (5, 1)]), Ok(1 << 8));
+        assert_eq!(dag.num_paths_via_in_order(&(0, 0), &(10, 0), &[(5, 1), (2, 2)]), Ok(0));
+        assert_eq!(dag.num_paths_via(&(0, 0), &(10, 0), &[(5, 1), (5, 2)]), Ok(0));
+        assert_eq!(dag.num_paths_via(&(0, 0), &(10, 0), &[(50, 1)]), Ok(0));
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let mut dag = ladder(3);
+        assert_eq!(dag.topological_order().map(|order| order.len()), Ok(10));
+        dag.add_edge((3, 0), (1, 0));
+        let error = dag.topological_order().unwrap_err();
+        assert_eq!(error.cycle.first(), error.cycle.last());
+        assert_eq!(error.cycle.len(), 6);
+        for edge in error.cycle.windows(2) {
+            assert!(dag.successors[dag.indices[&edge[0]]].contains(&dag.indices[&edge[1]]));
+        }
+        assert_eq!(dag.num_paths(&(0, 0), &(0, 1)).unwrap_err(), error);
+    }
+}
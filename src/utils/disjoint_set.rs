@@ -0,0 +1,253 @@
+//! A disjoint-set (union-find) data structure.
+//!
+//! Uses path compression and union by rank, so `find` and `union` run in
+//! amortized O(α(n)) time, where α is the inverse Ackermann function.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A collection of disjoint sets (components) of elements of type `T`.
+///
+/// Elements are added either explicitly with `make_set` or implicitly when
+/// they are passed to `union`.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::disjoint_set::DisjointSet;
+///
+/// let mut disjoint_set = DisjointSet::default();
+/// disjoint_set.union('a', 'b');
+/// disjoint_set.union('c', 'd');
+/// disjoint_set.make_set('e');
+/// assert_eq!(disjoint_set.num_components(), 3);
+/// assert!(disjoint_set.same_component(&'a', &'b'));
+/// assert!(!disjoint_set.same_component(&'b', &'c'));
+///
+/// disjoint_set.union('b', 'c');
+/// assert_eq!(disjoint_set.num_components(), 2);
+/// assert_eq!(disjoint_set.component_size(&'a'), Some(4));
+/// assert_eq!(disjoint_set.component_size(&'e'), Some(1));
+/// assert_eq!(disjoint_set.component_size(&'f'), None);
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DisjointSet<T>
+where
+    T: Eq + Hash + Clone
+{
+    indices: HashMap<T, usize>,
+    elements: Vec<T>,
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    num_components: usize,
+}
+
+impl<T> Default for DisjointSet<T>
+where
+    T: Eq + Hash + Clone
+{
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            elements: Vec::new(),
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            sizes: Vec::new(),
+            num_components: 0,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for DisjointSet<T>
+where
+    T: Eq + Hash + Clone
+{
+    /// Creates a disjoint set where each element is in its own component.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut disjoint_set = Self::default();
+        iter.into_iter().for_each(|element| { disjoint_set.make_set(element); });
+        disjoint_set
+    }
+}
+
+impl<T> DisjointSet<T>
+where
+    T: Eq + Hash + Clone
+{
+    /// Adds `element` in its own component. Returns whether the element was
+    /// newly added (`false` if it was already present, in which case its
+    /// component is left untouched).
+    pub fn make_set(&mut self, element: T) -> bool {
+        if self.indices.contains_key(&element) { return false; }
+        let idx = self.elements.len();
+        self.indices.insert(element.clone(), idx);
+        self.elements.push(element);
+        self.parents.push(idx);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.num_components += 1;
+        true
+    }
+
+    /// Returns whether `element` has been added.
+    pub fn contains(&self, element: &T) -> bool {
+        self.indices.contains_key(element)
+    }
+
+    /// Returns the total number of elements across all components.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the number of disjoint components.
+    pub fn num_components(&self) -> usize {
+        self.num_components
+    }
+
+    /// Returns the representative element of `element`'s component, or `None`
+    /// if `element` has not been added. Compresses the path to the root.
+    pub fn find(&mut self, element: &T) -> Option<&T> {
+        let idx = *self.indices.get(element)?;
+        let root = self.find_idx(idx);
+        Some(&self.elements[root])
+    }
+
+    /// Merges the components containing `a` and `b`, adding either element if
+    /// it is not already present. Returns whether two distinct components
+    /// were merged.
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        self.make_set(a.clone());
+        self.make_set(b.clone());
+        let root_a = self.find_idx(self.indices[&a]);
+        let root_b = self.find_idx(self.indices[&b]);
+        if root_a == root_b { return false; }
+        let (root, child) = if self.ranks[root_a] >= self.ranks[root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        self.num_components -= 1;
+        true
+    }
+
+    /// Returns whether `a` and `b` are both present and in the same component.
+    pub fn same_component(&self, a: &T, b: &T) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&idx_a), Some(&idx_b)) => self.root_idx(idx_a) == self.root_idx(idx_b),
+            _ => false,
+        }
+    }
+
+    /// Returns the number of elements in `element`'s component, or `None` if
+    /// `element` has not been added.
+    pub fn component_size(&self, element: &T) -> Option<usize> {
+        let idx = *self.indices.get(element)?;
+        Some(self.sizes[self.root_idx(idx)])
+    }
+
+    /// Returns the sizes of all components, in no particular order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.parents.len())
+            .filter(|idx| self.parents[*idx] == *idx)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+
+    /// Returns the members of every component. Neither the order of the
+    /// components nor the order within a component is specified.
+    pub fn components(&self) -> Vec<Vec<T>> {
+        let mut root_to_component: HashMap<usize, usize> = HashMap::new();
+        let mut components: Vec<Vec<T>> = Vec::with_capacity(self.num_components);
+        for (idx, element) in self.elements.iter().enumerate() {
+            let root = self.root_idx(idx);
+            let component_idx = *root_to_component.entry(root).or_insert_with(|| {
+                components.push(Vec::with_capacity(self.sizes[root]));
+                components.len() - 1
+            });
+            components[component_idx].push(element.clone());
+        }
+        components
+    }
+
+    /// Finds the root index of `idx`, pointing every node on the path
+    /// directly at the root.
+    fn find_idx(&mut self, idx: usize) -> usize {
+        let root = self.root_idx(idx);
+        let mut current = idx;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Finds the root index of `idx` without compressing the path. Union by
+    /// rank keeps this path logarithmic in the worst case.
+    fn root_idx(&self, idx: usize) -> usize {
+        let mut current = idx;
+        while self.parents[current] != current {
+            current = self.parents[current];
+        }
+        current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_is_correct() {
+        let mut disjoint_set = DisjointSet::default();
+        assert!(disjoint_set.union(0, 1));
+        assert!(disjoint_set.union(2, 3));
+        assert!(!disjoint_set.union(1, 0));
+        assert_eq!(disjoint_set.num_components(), 2);
+        assert!(disjoint_set.union(3, 0));
+        assert!(!disjoint_set.union(1, 2));
+        assert_eq!(disjoint_set.num_components(), 1);
+        assert_eq!(disjoint_set.component_size(&2), Some(4));
+        assert_eq!(disjoint_set.len(), 4);
+    }
+
+    #[test]
+    fn find_compresses_paths() {
+        let mut disjoint_set: DisjointSet<u32> = (0..8).collect();
+        for i in 0..7 {
+            disjoint_set.union(i, i + 1);
+        }
+        let root = *disjoint_set.find(&7).unwrap();
+        for i in 0..8 {
+            assert_eq!(disjoint_set.find(&i), Some(&root));
+            assert_eq!(disjoint_set.parents[disjoint_set.indices[&i]], disjoint_set.indices[&root]);
+        }
+        assert_eq!(disjoint_set.find(&8), None);
+    }
+
+    #[test]
+    fn components_are_correct() {
+        let mut disjoint_set: DisjointSet<u32> = (0..6).collect();
+        disjoint_set.union(0, 2);
+        disjoint_set.union(4, 2);
+        disjoint_set.union(1, 5);
+        let mut components: Vec<Vec<u32>> = disjoint_set.components()
+            .into_iter()
+            .map(|mut component| { component.sort(); component })
+            .collect();
+        components.sort();
+        assert_eq!(components, vec![vec![0, 2, 4], vec![1, 5], vec![3]]);
+        let mut sizes = disjoint_set.component_sizes();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 2, 3]);
+    }
+}
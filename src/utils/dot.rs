@@ -0,0 +1,241 @@
+//! Writes graphs in the [DOT language](https://graphviz.org/doc/info/lang.html)
+//! so a puzzle's input structure can be rendered with Graphviz (e.g.,
+//! `dot -Tsvg`) instead of being inspected by hand.
+use std::collections::HashMap;
+use std::fmt;
+
+use super::disjoint_set::DisjointSet;
+
+/// Whether edges have a direction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GraphKind {
+    Directed,
+    Undirected,
+}
+
+/// A node and its attributes (e.g., `shape`, `label` or puzzle-specific
+/// attributes such as `weight`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Node {
+    id: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Node {
+    /// Sets an attribute, replacing any earlier value.
+    pub fn attr(&mut self, key: &str, value: impl fmt::Display) -> &mut Self {
+        set_attr(&mut self.attributes, key, value);
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// An edge and its attributes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Edge {
+    from: String,
+    to: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl Edge {
+    /// Sets an attribute, replacing any earlier value.
+    pub fn attr(&mut self, key: &str, value: impl fmt::Display) -> &mut Self {
+        set_attr(&mut self.attributes, key, value);
+        self
+    }
+}
+
+fn set_attr(attributes: &mut Vec<(String, String)>, key: &str, value: impl fmt::Display) {
+    let value = value.to_string();
+    match attributes.iter_mut().find(|(k, _)| k == key) {
+        Some((_, v)) => *v = value,
+        None => attributes.push((String::from(key), value)),
+    }
+}
+
+/// A graph that displays as DOT. Nodes and edges are written in the order
+/// they were first added, so the output is deterministic.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::dot::Graph;
+///
+/// let mut graph = Graph::directed("tower");
+/// graph.node("tknk").attr("weight", 41);
+/// graph.edge("tknk", "ugml");
+/// graph.edge("tknk", "padx").attr("color", "red");
+/// assert_eq!(
+///     graph.to_string(),
+///     "digraph \"tower\" {\n  \
+///         \"tknk\" [weight=\"41\"];\n  \
+///         \"ugml\";\n  \
+///         \"padx\";\n  \
+///         \"tknk\" -> \"ugml\";\n  \
+///         \"tknk\" -> \"padx\" [color=\"red\"];\n\
+///     }\n",
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Graph {
+    name: String,
+    kind: GraphKind,
+    nodes: Vec<Node>,
+    node_indices: HashMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn new(name: &str, kind: GraphKind) -> Self {
+        Self {
+            name: String::from(name),
+            kind,
+            nodes: Vec::new(),
+            node_indices: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn directed(name: &str) -> Self {
+        Self::new(name, GraphKind::Directed)
+    }
+
+    pub fn undirected(name: &str) -> Self {
+        Self::new(name, GraphKind::Undirected)
+    }
+
+    /// Returns the node with the given id, adding it if it doesn't exist.
+    pub fn node(&mut self, id: &str) -> &mut Node {
+        let idx = match self.node_indices.get(id) {
+            Some(idx) => *idx,
+            None => {
+                self.nodes.push(Node { id: String::from(id), attributes: Vec::new() });
+                self.node_indices.insert(String::from(id), self.nodes.len() - 1);
+                self.nodes.len() - 1
+            },
+        };
+        &mut self.nodes[idx]
+    }
+
+    /// Adds an edge, adding either endpoint if it doesn't exist.
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Edge {
+        self.node(from);
+        self.node(to);
+        self.edges.push(Edge { from: String::from(from), to: String::from(to), attributes: Vec::new() });
+        self.edges.last_mut().unwrap()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.nodes.iter()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Sets a `degree` attribute on every node: the number of edges touching
+    /// it, regardless of direction.
+    pub fn annotate_degrees(&mut self) {
+        let mut degrees = vec![0; self.nodes.len()];
+        self.edges.iter()
+            .for_each(|edge| {
+                degrees[self.node_indices[&edge.from]] += 1;
+                degrees[self.node_indices[&edge.to]] += 1;
+            });
+        self.nodes.iter_mut()
+            .zip(degrees)
+            .for_each(|(node, degree)| { node.attr("degree", degree); });
+    }
+
+    /// Sets a `component` attribute on every node, numbering the (weakly)
+    /// connected components from 0 in order of their first node. Returns the
+    /// number of components.
+    pub fn annotate_components(&mut self) -> usize {
+        let mut disjoint_set: DisjointSet<usize> = (0..self.nodes.len()).collect();
+        self.edges.iter()
+            .for_each(|edge| {
+                disjoint_set.union(self.node_indices[&edge.from], self.node_indices[&edge.to]);
+            });
+        let mut component_numbers: HashMap<usize, usize> = HashMap::new();
+        for idx in 0..self.nodes.len() {
+            let root = *disjoint_set.find(&idx).unwrap();
+            let next_number = component_numbers.len();
+            let number = *component_numbers.entry(root).or_insert(next_number);
+            self.nodes[idx].attr("component", number);
+        }
+        component_numbers.len()
+    }
+}
+
+/// Quotes an id or attribute value, escaping as needed.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_attributes(f: &mut fmt::Formatter<'_>, attributes: &[(String, String)]) -> fmt::Result {
+    if attributes.is_empty() { return Ok(()); }
+    let attributes: Vec<String> = attributes.iter()
+        .map(|(key, value)| format!("{key}={}", quote(value)))
+        .collect();
+    write!(f, " [{}]", attributes.join(", "))
+}
+
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (keyword, edge_op) = match self.kind {
+            GraphKind::Directed => ("digraph", "->"),
+            GraphKind::Undirected => ("graph", "--"),
+        };
+        writeln!(f, "{keyword} {} {{", quote(&self.name))?;
+        for node in self.nodes.iter() {
+            write!(f, "  {}", quote(&node.id))?;
+            write_attributes(f, &node.attributes)?;
+            writeln!(f, ";")?;
+        }
+        for edge in self.edges.iter() {
+            write!(f, "  {} {edge_op} {}", quote(&edge.from), quote(&edge.to))?;
+            write_attributes(f, &edge.attributes)?;
+            writeln!(f, ";")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_escaped() {
+        let mut graph = Graph::undirected("a \"quoted\" graph");
+        graph.node("back\\slash").attr("label", "say \"hi\"");
+        assert_eq!(
+            graph.to_string(),
+            "graph \"a \\\"quoted\\\" graph\" {\n  \"back\\\\slash\" [label=\"say \\\"hi\\\"\"];\n}\n",
+        );
+    }
+
+    #[test]
+    fn attributes_are_replaced() {
+        let mut graph = Graph::directed("g");
+        graph.node("a").attr("shape", "box").attr("shape", "diamond");
+        assert_eq!(graph.nodes().next().unwrap().attributes, vec![(String::from("shape"), String::from("diamond"))]);
+    }
+
+    #[test]
+    fn components_are_annotated() {
+        let mut graph = Graph::undirected("g");
+        graph.edge("a", "b");
+        graph.edge("c", "d");
+        graph.node("e");
+        graph.edge("d", "b");
+        assert_eq!(graph.annotate_components(), 2);
+        let components: Vec<&str> = graph.nodes()
+            .map(|node| node.attributes[0].1.as_str())
+            .collect();
+        assert_eq!(components, vec!["0", "0", "0", "0", "1"]);
+    }
+}
@@ -0,0 +1,608 @@
+//! Exact cover problems, solved with Knuth's
+//! [Algorithm X](https://en.wikipedia.org/wiki/Knuth%27s_Algorithm_X) using
+//! [Dancing Links](https://en.wikipedia.org/wiki/Dancing_Links).
+//!
+//! A problem is a set of columns and a list of rows, each row covering some
+//! of the columns. A solution picks rows so that every primary column is
+//! covered exactly once (or exactly its multiplicity times) and every
+//! secondary column at most once. Packing and tiling puzzles turn into exact
+//! cover by making a row for each way of placing a piece (see
+//! `PolyominoPacking`).
+use std::collections::BTreeSet;
+
+/// A node in the dancing links: a column header or a 1 in some row, linked
+/// to its neighbors in both directions.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Node {
+    left: usize,
+    right: usize,
+    up: usize,
+    down: usize,
+    column: usize,
+}
+
+/// An exact cover problem with `num_primary` columns that must be covered
+/// exactly once, followed by `num_secondary` that may be covered at most
+/// once. Rows are numbered in the order they are added. Rows covering only
+/// secondary columns are never part of a solution.
+///
+/// A primary column can instead have a multiplicity, the number of rows that
+/// must cover it. A solution is a set of rows, so picking the same rows for
+/// a column in another order isn't another solution.
+///
+/// Searching unlinks and relinks nodes but leaves the problem as it was, so
+/// a problem can be searched any number of times.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::exact_cover::ExactCover;
+///
+/// // Knuth's example: rows 0, 3 and 4 cover each column once.
+/// let mut problem = ExactCover::new(7, 0);
+/// problem.add_row(&[2, 4, 5]);
+/// problem.add_row(&[0, 3, 6]);
+/// problem.add_row(&[1, 2, 5]);
+/// problem.add_row(&[0, 3]);
+/// problem.add_row(&[1, 6]);
+/// problem.add_row(&[3, 4, 6]);
+/// assert_eq!(problem.first_solution(), Some(vec![0, 3, 4]));
+/// assert_eq!(problem.count_solutions(), 1);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ExactCover {
+    num_columns: usize,
+    // Node 0 is the root, nodes 1 to `num_columns` the column headers, and
+    // the rest the rows' 1s.
+    nodes: Vec<Node>,
+    sizes: Vec<usize>,
+    // How many more times each column must be covered, by column header.
+    remaining: Vec<usize>,
+    rows: Vec<usize>,
+    num_rows: usize,
+}
+
+impl ExactCover {
+    pub fn new(num_primary: usize, num_secondary: usize) -> Self {
+        Self::with_multiplicities(vec![1; num_primary], num_secondary)
+    }
+
+    /// A problem whose primary columns must each be covered by the given
+    /// number of rows. Rows may not cover a column of multiplicity 0.
+    ///
+    /// # Examples
+    /// ```
+    /// use advent_of_code_rust::utils::exact_cover::ExactCover;
+    ///
+    /// // Two of the three rows for column 0, which don't both cover column 1.
+    /// let mut problem = ExactCover::with_multiplicities(vec![2], 1);
+    /// problem.add_row(&[0, 1]);
+    /// problem.add_row(&[0]);
+    /// problem.add_row(&[0, 1]);
+    /// assert_eq!(problem.count_solutions(), 2);
+    /// ```
+    pub fn with_multiplicities(multiplicities: Vec<usize>, num_secondary: usize) -> Self {
+        let num_columns = multiplicities.len() + num_secondary;
+        let mut nodes: Vec<Node> = (0..=num_columns)
+            .map(|idx| Node { left: idx, right: idx, up: idx, down: idx, column: idx })
+            .collect();
+        // Only the primary columns left to cover are linked to the root,
+        // since a solution needn't cover the secondary ones.
+        let linked: Vec<usize> = std::iter::once(0)
+            .chain((1..=multiplicities.len()).filter(|column| multiplicities[column - 1] > 0))
+            .collect();
+        for (idx, column) in linked.iter().enumerate() {
+            let next = linked[(idx + 1) % linked.len()];
+            nodes[*column].right = next;
+            nodes[next].left = *column;
+        }
+        let remaining: Vec<usize> = std::iter::once(0)
+            .chain(multiplicities)
+            .chain(std::iter::repeat_n(1, num_secondary))
+            .collect();
+        Self {
+            num_columns,
+            nodes,
+            sizes: vec![0; num_columns + 1],
+            remaining,
+            rows: vec![0; num_columns + 1],
+            num_rows: 0,
+        }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Adds a row covering the given (0-indexed) columns, and returns its
+    /// index.
+    pub fn add_row(&mut self, columns: &[usize]) -> usize {
+        assert!(!columns.is_empty(), "Row should cover at least one column.");
+        assert_eq!(
+            columns.iter().collect::<BTreeSet<_>>().len(), columns.len(),
+            "Row should cover each column at most once."
+        );
+        let row = self.num_rows;
+        let first = self.nodes.len();
+        for (offset, column) in columns.iter().enumerate() {
+            assert!(*column < self.num_columns, "Column {column} should exist.");
+            assert!(self.remaining[column + 1] > 0, "Column {column} should have a multiplicity.");
+            let header = column + 1;
+            let idx = first + offset;
+            let up = self.nodes[header].up;
+            self.nodes.push(Node {
+                left: if offset == 0 { first + columns.len() - 1 } else { idx - 1 },
+                right: if offset == columns.len() - 1 { first } else { idx + 1 },
+                up,
+                down: header,
+                column: header,
+            });
+            self.nodes[up].down = idx;
+            self.nodes[header].up = idx;
+            self.sizes[header] += 1;
+            self.rows.push(row);
+        }
+        self.num_rows += 1;
+        row
+    }
+
+    fn cover(&mut self, column: usize) {
+        let Node { left, right, .. } = self.nodes[column];
+        self.nodes[left].right = right;
+        self.nodes[right].left = left;
+        let mut row = self.nodes[column].down;
+        while row != column {
+            let mut idx = self.nodes[row].right;
+            while idx != row {
+                let Node { up, down, column, .. } = self.nodes[idx];
+                self.nodes[up].down = down;
+                self.nodes[down].up = up;
+                self.sizes[column] -= 1;
+                idx = self.nodes[idx].right;
+            }
+            row = self.nodes[row].down;
+        }
+    }
+
+    fn uncover(&mut self, column: usize) {
+        let mut row = self.nodes[column].up;
+        while row != column {
+            let mut idx = self.nodes[row].left;
+            while idx != row {
+                let Node { up, down, column, .. } = self.nodes[idx];
+                self.nodes[up].down = idx;
+                self.nodes[down].up = idx;
+                self.sizes[column] += 1;
+                idx = self.nodes[idx].left;
+            }
+            row = self.nodes[row].up;
+        }
+        let Node { left, right, .. } = self.nodes[column];
+        self.nodes[left].right = column;
+        self.nodes[right].left = column;
+    }
+
+    /// Unlinks the row with the node `node` from all its columns.
+    fn hide(&mut self, node: usize) {
+        let mut idx = node;
+        loop {
+            let Node { up, down, column, right, .. } = self.nodes[idx];
+            self.nodes[up].down = down;
+            self.nodes[down].up = up;
+            self.sizes[column] -= 1;
+            idx = right;
+            if idx == node { break; }
+        }
+    }
+
+    fn unhide(&mut self, node: usize) {
+        let mut idx = node;
+        loop {
+            idx = self.nodes[idx].left;
+            let Node { up, down, column, .. } = self.nodes[idx];
+            self.nodes[up].down = idx;
+            self.nodes[down].up = idx;
+            self.sizes[column] += 1;
+            if idx == node { break; }
+        }
+    }
+
+    /// Puts the row with the node `node` in the solution, covering the
+    /// columns it finishes.
+    fn select(&mut self, node: usize) {
+        self.hide(node);
+        let mut idx = node;
+        loop {
+            let column = self.nodes[idx].column;
+            self.remaining[column] -= 1;
+            if self.remaining[column] == 0 {
+                self.cover(column);
+            }
+            idx = self.nodes[idx].right;
+            if idx == node { break; }
+        }
+    }
+
+    fn unselect(&mut self, node: usize) {
+        let mut idx = node;
+        loop {
+            idx = self.nodes[idx].left;
+            let column = self.nodes[idx].column;
+            if self.remaining[column] == 0 {
+                self.uncover(column);
+            }
+            self.remaining[column] += 1;
+            if idx == node { break; }
+        }
+        self.unhide(node);
+    }
+
+    /// The number of rows a column can take as the first of its remaining
+    /// rows, leaving enough after it for the rest.
+    fn choices(&self, column: usize) -> usize {
+        (self.sizes[column] + 1).saturating_sub(self.remaining[column])
+    }
+
+    /// The uncovered primary column with the fewest choices left, which
+    /// keeps the search tree narrow.
+    fn fewest_choices_column(&self) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        let mut column = self.nodes[0].right;
+        while column != 0 {
+            if best.is_none_or(|best| self.choices(column) < self.choices(best)) {
+                best = Some(column);
+            }
+            column = self.nodes[column].right;
+        }
+        best
+    }
+
+    /// Calls `visit` on each solution's rows, in the order they were chosen,
+    /// until it returns `false`. Returns whether the search was stopped
+    /// early.
+    pub fn for_each_solution(&mut self, mut visit: impl FnMut(&[usize]) -> bool) -> bool {
+        self.search(&mut Vec::new(), &mut visit)
+    }
+
+    /// Branches on which row is the first (in column order) of the
+    /// solution's rows for the column with the fewest choices. Each row is
+    /// left out once its branch is done, so every solution is found once.
+    fn search(&mut self, solution: &mut Vec<usize>, visit: &mut impl FnMut(&[usize]) -> bool) -> bool {
+        let Some(column) = self.fewest_choices_column() else {
+            return !visit(solution);
+        };
+        if self.choices(column) == 0 {
+            return false;
+        }
+        let mut left_out = Vec::new();
+        let mut row = self.nodes[column].down;
+        let mut stopped = false;
+        while row != column && !stopped {
+            solution.push(self.rows[row]);
+            self.select(row);
+            stopped = self.search(solution, visit);
+            self.unselect(row);
+            solution.pop();
+            let next = self.nodes[row].down;
+            self.hide(row);
+            left_out.push(row);
+            row = next;
+        }
+        left_out.into_iter().rev().for_each(|row| self.unhide(row));
+        stopped
+    }
+
+    /// The rows of some solution, sorted, if there is one.
+    pub fn first_solution(&mut self) -> Option<Vec<usize>> {
+        let mut first = None;
+        self.for_each_solution(|solution| {
+            first = Some(solution.to_vec());
+            false
+        });
+        first.map(|mut rows: Vec<usize>| { rows.sort(); rows })
+    }
+
+    pub fn count_solutions(&mut self) -> usize {
+        let mut count = 0;
+        self.for_each_solution(|_solution| {
+            count += 1;
+            true
+        });
+        count
+    }
+}
+
+/// A shape made of grid cells, given as (row, column) pairs shifted so that
+/// both the top row and the leftmost column are 0.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct Polyomino {
+    cells: BTreeSet<(usize, usize)>,
+}
+
+impl Polyomino {
+    /// Reads a shape drawn with `#` for its cells (and anything else for
+    /// gaps).
+    ///
+    /// # Examples
+    /// ```
+    /// use advent_of_code_rust::utils::exact_cover::Polyomino;
+    ///
+    /// let ell = Polyomino::from_lines(&["#.", "#.", "##"]);
+    /// assert_eq!(ell.len(), 4);
+    /// assert_eq!(ell.orientations().len(), 8);
+    /// assert_eq!(Polyomino::from_lines(&["##", "##"]).orientations().len(), 1);
+    /// ```
+    pub fn from_lines<L>(lines: &[L]) -> Self
+    where L: AsRef<str>, {
+        Self::from_cells(lines.iter()
+            .enumerate()
+            .flat_map(|(row, line)| line.as_ref()
+                .chars()
+                .enumerate()
+                .filter(|(_col, c)| *c == '#')
+                .map(move |(col, _c)| (row as i64, col as i64))
+                .collect::<Vec<_>>()
+            )
+        )
+    }
+
+    fn from_cells(cells: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let cells: Vec<(i64, i64)> = cells.into_iter().collect();
+        assert!(!cells.is_empty(), "Polyomino should have at least one cell.");
+        let min_row = cells.iter().map(|(row, _)| *row).min().unwrap();
+        let min_col = cells.iter().map(|(_, col)| *col).min().unwrap();
+        Self {
+            cells: cells.iter()
+                .map(|(row, col)| ((row - min_row) as usize, (col - min_col) as usize))
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &(usize, usize)> {
+        self.cells.iter()
+    }
+
+    /// The number of rows and columns the shape spans.
+    pub fn dim(&self) -> (usize, usize) {
+        (
+            self.cells.iter().map(|(row, _)| row + 1).max().unwrap_or(0),
+            self.cells.iter().map(|(_, col)| col + 1).max().unwrap_or(0),
+        )
+    }
+
+    fn rotated_90_deg_clockwise(&self) -> Self {
+        Self::from_cells(self.cells.iter().map(|(row, col)| (*col as i64, -(*row as i64))))
+    }
+
+    fn flipped_over_horizontal(&self) -> Self {
+        Self::from_cells(self.cells.iter().map(|(row, col)| (-(*row as i64), *col as i64)))
+    }
+
+    /// The distinct shapes the polyomino takes when rotated and flipped,
+    /// sorted.
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations = BTreeSet::new();
+        let mut shape = self.clone();
+        for _ in 0..2 {
+            for _ in 0..4 {
+                shape = shape.rotated_90_deg_clockwise();
+                orientations.insert(shape.clone());
+            }
+            shape = shape.flipped_over_horizontal();
+        }
+        orientations.into_iter().collect()
+    }
+}
+
+/// One way of putting a piece in a packing: which piece (or `None` for a
+/// cell left empty), and the cells of the grid it takes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Placement {
+    pub piece: Option<usize>,
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// Packing a number of copies of each of a list of polyominoes, rotated and
+/// flipped as needed, into a grid, as an exact cover problem. Copies of a
+/// piece aren't told apart, so each packing is one solution.
+///
+/// Each piece has a primary column with its number of copies as its
+/// multiplicity, and each cell a primary column. If cells may be left empty,
+/// an extra column with the number of empty cells as its multiplicity
+/// takes a row for each cell that leaves it empty. Every cell then needs a
+/// row, so the search can branch on the cells with the fewest ways to fill
+/// them.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::exact_cover::{Polyomino, PolyominoPacking};
+///
+/// // A 2x3 grid has three domino tilings.
+/// let domino = Polyomino::from_lines(&["##"]);
+/// let packing = PolyominoPacking::new(2, 3, vec![(domino, 3)], false);
+/// assert_eq!(packing.exact_cover().count_solutions(), 3);
+/// assert_eq!(packing.placements().len(), 7);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PolyominoPacking {
+    height: usize,
+    width: usize,
+    pieces: Vec<(Polyomino, usize)>,
+    allow_empty_cells: bool,
+}
+
+impl PolyominoPacking {
+    /// Packs `pieces`, given with how many copies of each to place.
+    pub fn new(height: usize, width: usize, pieces: Vec<(Polyomino, usize)>, allow_empty_cells: bool) -> Self {
+        Self { height, width, pieces, allow_empty_cells }
+    }
+
+    /// The number of cells left empty by a packing, if it's allowed.
+    fn num_empty_cells(&self) -> usize {
+        if !self.allow_empty_cells {
+            return 0;
+        }
+        let num_cells: usize = self.pieces.iter()
+            .map(|(polyomino, count)| polyomino.len() * count)
+            .sum();
+        (self.height * self.width).saturating_sub(num_cells)
+    }
+
+    /// Every placement of every piece in the grid, then every cell left
+    /// empty if some must be, in the order of the exact cover problem's
+    /// rows.
+    pub fn placements(&self) -> Vec<Placement> {
+        let mut placements = Vec::new();
+        for (piece, (polyomino, count)) in self.pieces.iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            for orientation in polyomino.orientations() {
+                let (rows, cols) = orientation.dim();
+                if rows > self.height || cols > self.width {
+                    continue;
+                }
+                for top in 0..=self.height - rows {
+                    for left in 0..=self.width - cols {
+                        placements.push(Placement {
+                            piece: Some(piece),
+                            cells: orientation.cells().map(|(row, col)| (top + row, left + col)).collect(),
+                        });
+                    }
+                }
+            }
+        }
+        if self.num_empty_cells() > 0 {
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    placements.push(Placement { piece: None, cells: vec![(row, col)] });
+                }
+            }
+        }
+        placements
+    }
+
+    /// The exact cover problem whose solutions are the packings, with a row
+    /// for each of `placements`.
+    pub fn exact_cover(&self) -> ExactCover {
+        let num_cells = self.height * self.width;
+        let empty_column = self.pieces.len();
+        let multiplicities: Vec<usize> = self.pieces.iter()
+            .map(|(_polyomino, count)| *count)
+            .chain(std::iter::once(self.num_empty_cells()))
+            .chain(std::iter::repeat_n(1, num_cells))
+            .collect();
+        let mut problem = ExactCover::with_multiplicities(multiplicities, 0);
+        for placement in self.placements() {
+            let columns: Vec<usize> = std::iter::once(placement.piece.unwrap_or(empty_column))
+                .chain(placement.cells.iter().map(|(row, col)| empty_column + 1 + row * self.width + col))
+                .collect();
+            problem.add_row(&columns);
+        }
+        problem
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secondary_columns_are_optional() {
+        let mut problem = ExactCover::new(1, 2);
+        problem.add_row(&[0, 1]);
+        problem.add_row(&[0, 2]);
+        problem.add_row(&[0, 1, 2]);
+        problem.add_row(&[1]);
+        // Row 3 would fit beside row 1, but covers no primary column.
+        assert_eq!(problem.count_solutions(), 3);
+
+        let mut problem = ExactCover::new(2, 1);
+        problem.add_row(&[0, 2]);
+        problem.add_row(&[1, 2]);
+        assert_eq!(problem.first_solution(), None);
+        assert_eq!(problem.count_solutions(), 0);
+    }
+
+    #[test]
+    fn search_restores_links() {
+        let mut problem = ExactCover::new(3, 0);
+        problem.add_row(&[0, 1]);
+        problem.add_row(&[2]);
+        problem.add_row(&[0]);
+        problem.add_row(&[1, 2]);
+        let before = problem.clone();
+        assert_eq!(problem.count_solutions(), 2);
+        assert_eq!(problem, before);
+        assert!(problem.for_each_solution(|_solution| false));
+        assert_eq!(problem, before);
+    }
+
+    #[test]
+    fn multiplicities_count_sets_of_rows() {
+        // Any two of the four rows, but rows 1 and 2 clash on column 1.
+        let mut problem = ExactCover::with_multiplicities(vec![2], 1);
+        problem.add_row(&[0]);
+        problem.add_row(&[0, 1]);
+        problem.add_row(&[0, 1]);
+        problem.add_row(&[0]);
+        let before = problem.clone();
+        assert_eq!(problem.count_solutions(), 5);
+        assert_eq!(problem, before);
+        // Too few rows for the multiplicity.
+        let mut problem = ExactCover::with_multiplicities(vec![3, 1], 0);
+        problem.add_row(&[0, 1]);
+        problem.add_row(&[0]);
+        assert_eq!(problem.count_solutions(), 0);
+        // Nothing needs covering.
+        let mut problem = ExactCover::with_multiplicities(vec![0], 0);
+        assert_eq!(problem.count_solutions(), 1);
+    }
+
+    #[test]
+    fn domino_tilings_are_counted() {
+        let domino = Polyomino::from_lines(&["##"]);
+        let packing = PolyominoPacking::new(3, 4, vec![(domino, 6)], false);
+        assert_eq!(packing.exact_cover().count_solutions(), 11);
+    }
+
+    #[test]
+    fn pieces_pack() {
+        let ell = Polyomino::from_lines(&["#.", "##"]);
+        let packing = PolyominoPacking::new(2, 3, vec![(ell.clone(), 2)], false);
+        assert_eq!(packing.exact_cover().count_solutions(), 2);
+        let packing = PolyominoPacking::new(3, 3, vec![(ell.clone(), 2)], true);
+        let rows = packing.exact_cover().first_solution().expect("Two L-trominoes should fit in a 3x3 grid.");
+        let placements = packing.placements();
+        let cells: BTreeSet<(usize, usize)> = rows.iter()
+            .filter(|row| placements[**row].piece.is_some())
+            .flat_map(|row| placements[*row].cells.clone())
+            .collect();
+        assert_eq!(cells.len(), 6);
+        assert_eq!(rows.len(), 2 + 3);
+        // L-trominoes can't tile a 3x3 grid, since no two can share a corner.
+        let packing = PolyominoPacking::new(3, 3, vec![(ell.clone(), 3)], true);
+        assert_eq!(packing.exact_cover().first_solution(), None);
+        // Nor can more cells than the grid has fit.
+        let packing = PolyominoPacking::new(3, 3, vec![(ell, 4)], true);
+        assert_eq!(packing.exact_cover().count_solutions(), 0);
+    }
+
+    #[test]
+    fn empty_cells_are_not_told_apart() {
+        // One domino in a 1x3 grid, leaving one of its ends empty.
+        let domino = Polyomino::from_lines(&["##"]);
+        let packing = PolyominoPacking::new(1, 3, vec![(domino.clone(), 1)], true);
+        assert_eq!(packing.exact_cover().count_solutions(), 2);
+        // Leaving the middle empty too.
+        let packing = PolyominoPacking::new(1, 4, vec![(domino, 1)], true);
+        assert_eq!(packing.exact_cover().count_solutions(), 3);
+    }
+}
@@ -0,0 +1,392 @@
+//! Integer linear programs: integer variables with bounds, linear
+//! constraints on them, and optionally a linear objective to minimize or
+//! maximize.
+//!
+//! A `Model` is declared once and then solved by either backend:
+//! [z3](https://github.com/Z3Prover/z3)'s optimizer, or a small pure-Rust
+//! branch and bound that tightens the variables' bounds against each
+//! constraint before splitting a domain in two. The latter is fine for
+//! problems with a handful of variables and lets the model be tested without
+//! the solver.
+use std::collections::BTreeMap;
+
+use z3::{ast::Int, Optimize, SatResult};
+
+/// A handle to one of a model's variables.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Variable(usize);
+
+/// A sum of variables times integer coefficients, plus a constant.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct LinearExpr {
+    coefficients: BTreeMap<Variable, i64>,
+    constant: i64,
+}
+
+impl LinearExpr {
+    pub fn constant(constant: i64) -> Self {
+        Self { coefficients: BTreeMap::new(), constant }
+    }
+
+    /// The sum of the variables, each with coefficient 1.
+    pub fn sum(variables: impl IntoIterator<Item = Variable>) -> Self {
+        Self::weighted_sum(variables.into_iter().map(|variable| (1, variable)))
+    }
+
+    /// The sum of the variables times their coefficients.
+    pub fn weighted_sum(terms: impl IntoIterator<Item = (i64, Variable)>) -> Self {
+        terms.into_iter().fold(Self::default(), |expr, (coefficient, variable)| expr.plus(coefficient, variable))
+    }
+
+    /// Adds `coefficient` times `variable`.
+    pub fn plus(mut self, coefficient: i64, variable: Variable) -> Self {
+        *self.coefficients.entry(variable).or_insert(0) += coefficient;
+        self
+    }
+
+    /// The expression's value when each variable has the given value.
+    pub fn eval(&self, values: &[i64]) -> i64 {
+        self.constant + self.coefficients.iter()
+            .map(|(variable, coefficient)| coefficient * values[variable.0])
+            .sum::<i64>()
+    }
+
+    /// The least and greatest values the expression takes with each
+    /// variable between its bounds.
+    fn range(&self, lower: &[i64], upper: &[i64]) -> (i64, i64) {
+        self.coefficients.iter().fold((self.constant, self.constant), |(min, max), (variable, coefficient)| {
+            let (at_lower, at_upper) = (coefficient * lower[variable.0], coefficient * upper[variable.0]);
+            (min + at_lower.min(at_upper), max + at_lower.max(at_upper))
+        })
+    }
+}
+
+impl From<Variable> for LinearExpr {
+    fn from(variable: Variable) -> Self {
+        Self::default().plus(1, variable)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Relation {
+    LessEq,
+    Equal,
+    GreaterEq,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Goal {
+    Minimize,
+    Maximize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Backend {
+    Z3,
+    BranchAndBound,
+}
+
+/// The constraint `expr relation rhs`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Constraint {
+    expr: LinearExpr,
+    relation: Relation,
+    rhs: i64,
+}
+
+/// Values for every variable of a model, and the objective's value if it
+/// has one.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Assignment {
+    values: Vec<i64>,
+    objective: Option<i64>,
+}
+
+impl Assignment {
+    /// The variable's value, converted to the type it stands for.
+    pub fn value<T>(&self, variable: Variable) -> T
+    where T: TryFrom<i64>, {
+        T::try_from(self.values[variable.0]).unwrap_or_else(|_| panic!("Value of {variable:?} should fit the type."))
+    }
+
+    /// The objective's value, converted to the type it stands for.
+    pub fn objective<T>(&self) -> T
+    where T: TryFrom<i64>, {
+        let objective = self.objective.expect("Model should have an objective.");
+        T::try_from(objective).unwrap_or_else(|_| panic!("Objective {objective} should fit the type."))
+    }
+}
+
+/// An integer linear program.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::ilp::{Backend, LinearExpr, Model, Relation};
+///
+/// // The fewest coins of 1, 5 and 7 that make 24.
+/// let mut model = Model::default();
+/// let coins: Vec<_> = ["ones", "fives", "sevens"].iter().map(|name| model.int_var(name, 0, 24)).collect();
+/// model.constrain(LinearExpr::weighted_sum([1, 5, 7].into_iter().zip(coins.iter().copied())), Relation::Equal, 24);
+/// model.minimize(LinearExpr::sum(coins.iter().copied()));
+///
+/// let assignment = model.solve(Backend::BranchAndBound).expect("24 should be payable.");
+/// assert_eq!(assignment.objective::<u32>(), 4);
+/// assert_eq!(coins.iter().map(|coin| assignment.value::<u32>(*coin)).collect::<Vec<_>>(), vec![0, 2, 2]);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Model {
+    names: Vec<String>,
+    lower: Vec<i64>,
+    upper: Vec<i64>,
+    constraints: Vec<Constraint>,
+    objective: Option<(Goal, LinearExpr)>,
+}
+
+impl Model {
+    /// Adds an integer variable between `lower` and `upper` inclusive.
+    pub fn int_var(&mut self, name: &str, lower: i64, upper: i64) -> Variable {
+        assert!(lower <= upper, "Variable {name} should have lower bound at most its upper bound.");
+        self.names.push(name.to_string());
+        self.lower.push(lower);
+        self.upper.push(upper);
+        Variable(self.names.len() - 1)
+    }
+
+    pub fn constrain(&mut self, expr: impl Into<LinearExpr>, relation: Relation, rhs: i64) {
+        self.constraints.push(Constraint { expr: expr.into(), relation, rhs });
+    }
+
+    pub fn minimize(&mut self, expr: impl Into<LinearExpr>) {
+        self.objective = Some((Goal::Minimize, expr.into()));
+    }
+
+    pub fn maximize(&mut self, expr: impl Into<LinearExpr>) {
+        self.objective = Some((Goal::Maximize, expr.into()));
+    }
+
+    /// An optimal assignment (or any feasible one, without an objective),
+    /// or `None` if the constraints can't all hold.
+    pub fn solve(&self, backend: Backend) -> Option<Assignment> {
+        match backend {
+            Backend::Z3 => self.solve_z3(),
+            Backend::BranchAndBound => {
+                let mut best: Option<Vec<i64>> = None;
+                let stop_at_first = self.objective.is_none();
+                self.branch(self.lower.clone(), self.upper.clone(), &mut Search {
+                    incumbent: None,
+                    prune_by_objective: true,
+                    visit: &mut |values| {
+                        best = Some(values.to_vec());
+                        !stop_at_first
+                    },
+                });
+                best.map(|values| self.assignment(values))
+            },
+        }
+    }
+
+    /// Calls `visit` on every feasible assignment, ignoring the objective.
+    /// Only practical when there are few of them.
+    pub fn for_each_feasible(&self, mut visit: impl FnMut(&Assignment)) {
+        self.branch(self.lower.clone(), self.upper.clone(), &mut Search {
+            incumbent: None,
+            prune_by_objective: false,
+            visit: &mut |values| {
+                visit(&self.assignment(values.to_vec()));
+                true
+            },
+        });
+    }
+
+    fn assignment(&self, values: Vec<i64>) -> Assignment {
+        let objective = self.objective.as_ref().map(|(_goal, expr)| expr.eval(&values));
+        Assignment { values, objective }
+    }
+
+    fn solve_z3(&self) -> Option<Assignment> {
+        let optimize = Optimize::new();
+        let variables: Vec<Int> = self.names.iter().map(|name| Int::fresh_const(name)).collect();
+        for (variable, (lower, upper)) in variables.iter().zip(self.lower.iter().zip(&self.upper)) {
+            optimize.assert(&variable.ge(*lower));
+            optimize.assert(&variable.le(*upper));
+        }
+        let to_z3 = |expr: &LinearExpr| expr.coefficients.iter()
+            .fold(Int::from_i64(expr.constant), |sum, (variable, coefficient)| {
+                sum + &variables[variable.0] * *coefficient
+            });
+        for constraint in &self.constraints {
+            let expr = to_z3(&constraint.expr);
+            optimize.assert(&match constraint.relation {
+                Relation::LessEq => expr.le(constraint.rhs),
+                Relation::Equal => expr.eq(constraint.rhs),
+                Relation::GreaterEq => expr.ge(constraint.rhs),
+            });
+        }
+        match &self.objective {
+            Some((Goal::Minimize, expr)) => optimize.minimize(&to_z3(expr)),
+            Some((Goal::Maximize, expr)) => optimize.maximize(&to_z3(expr)),
+            None => {},
+        }
+        match optimize.check(&[]) {
+            SatResult::Sat => {
+                let model = optimize.get_model().expect("Satisfiable problem should have a model.");
+                let values = variables.iter()
+                    .map(|variable| model.eval(variable, true).and_then(|value| value.as_i64()).expect("Variable should have a value."))
+                    .collect();
+                Some(self.assignment(values))
+            },
+            SatResult::Unsat => None,
+            SatResult::Unknown => panic!("Solver should decide the problem."),
+        }
+    }
+
+    /// Tightens the bounds until each constraint is consistent with every
+    /// variable's bounds, returning `false` if some constraint can't hold.
+    fn propagate(&self, lower: &mut [i64], upper: &mut [i64]) -> bool {
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for constraint in &self.constraints {
+                let (min, max) = constraint.expr.range(lower, upper);
+                let check_at_most = constraint.relation != Relation::GreaterEq;
+                let check_at_least = constraint.relation != Relation::LessEq;
+                if (check_at_most && min > constraint.rhs) || (check_at_least && max < constraint.rhs) {
+                    return false;
+                }
+                for (variable, coefficient) in &constraint.expr.coefficients {
+                    let idx = variable.0;
+                    let (at_lower, at_upper) = (coefficient * lower[idx], coefficient * upper[idx]);
+                    // Bounds on coefficient * variable, given the rest of the
+                    // expression's range.
+                    let at_most = check_at_most.then(|| constraint.rhs - (min - at_lower.min(at_upper)));
+                    let at_least = check_at_least.then(|| constraint.rhs - (max - at_lower.max(at_upper)));
+                    let (new_lower, new_upper) = match coefficient.signum() {
+                        1 => (at_least.map(|bound| ceil_div(bound, *coefficient)), at_most.map(|bound| floor_div(bound, *coefficient))),
+                        -1 => (at_most.map(|bound| ceil_div(bound, *coefficient)), at_least.map(|bound| floor_div(bound, *coefficient))),
+                        _ => (None, None),
+                    };
+                    if let Some(new_lower) = new_lower.filter(|new_lower| *new_lower > lower[idx]) {
+                        lower[idx] = new_lower;
+                        changed = true;
+                    }
+                    if let Some(new_upper) = new_upper.filter(|new_upper| *new_upper < upper[idx]) {
+                        upper[idx] = new_upper;
+                        changed = true;
+                    }
+                    if lower[idx] > upper[idx] {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Searches the box between the bounds, passing each assignment with
+    /// every variable fixed to the search's `visit` until it returns
+    /// `false`. Returns whether the search was stopped.
+    fn branch(&self, mut lower: Vec<i64>, mut upper: Vec<i64>, search: &mut Search) -> bool {
+        if !self.propagate(&mut lower, &mut upper) {
+            return false;
+        }
+        let objective = self.objective.as_ref();
+        if let (Some((goal, expr)), Some(incumbent), true) = (objective, search.incumbent, search.prune_by_objective) {
+            // Skip boxes that can't beat the best assignment so far.
+            let (min, max) = expr.range(&lower, &upper);
+            if (*goal == Goal::Minimize && min >= incumbent) || (*goal == Goal::Maximize && max <= incumbent) {
+                return false;
+            }
+        }
+        // Split the smallest domain not yet down to one value.
+        let Some(idx) = (0..lower.len())
+            .filter(|idx| lower[*idx] < upper[*idx])
+            .min_by_key(|idx| upper[*idx] - lower[*idx])
+        else {
+            search.incumbent = objective.map(|(_goal, expr)| expr.eval(&lower));
+            return !(search.visit)(&lower);
+        };
+        let mid = lower[idx] + (upper[idx] - lower[idx]).div_euclid(2);
+        let (mut low_half, mut high_half) = ((lower.clone(), upper.clone()), (lower, upper));
+        low_half.1[idx] = mid;
+        high_half.0[idx] = mid + 1;
+        // Look first where the objective is better, to find good
+        // assignments early.
+        let high_first = objective.is_some_and(|(goal, expr)| {
+            let coefficient = expr.coefficients.get(&Variable(idx)).copied().unwrap_or(0);
+            (*goal == Goal::Maximize) == (coefficient > 0)
+        });
+        let halves = if high_first { [high_half, low_half] } else { [low_half, high_half] };
+        halves.into_iter().any(|(lower, upper)| self.branch(lower, upper, search))
+    }
+}
+
+/// The state of a branch and bound search: the objective's best value so
+/// far, and what to do with each assignment found.
+struct Search<'a> {
+    incumbent: Option<i64>,
+    prune_by_objective: bool,
+    visit: &'a mut dyn FnMut(&[i64]) -> bool,
+}
+
+fn floor_div(numerator: i64, denominator: i64) -> i64 {
+    if denominator < 0 { floor_div(-numerator, -denominator) } else { numerator.div_euclid(denominator) }
+}
+
+fn ceil_div(numerator: i64, denominator: i64) -> i64 {
+    -floor_div(-numerator, denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maximize 3x + 2y - z with x + y + z <= 10, x - y >= -2, 2x + z = 8
+    /// and each variable between -5 and 6.
+    fn small_model() -> (Model, Vec<Variable>) {
+        let mut model = Model::default();
+        let variables: Vec<Variable> = ["x", "y", "z"].iter().map(|name| model.int_var(name, -5, 6)).collect();
+        let [x, y, z] = variables[..] else { unreachable!() };
+        model.constrain(LinearExpr::sum([x, y, z]), Relation::LessEq, 10);
+        model.constrain(LinearExpr::from(x).plus(-1, y), Relation::GreaterEq, -2);
+        model.constrain(LinearExpr::weighted_sum([(2, x), (1, z)]), Relation::Equal, 8);
+        model.maximize(LinearExpr::weighted_sum([(3, x), (2, y), (-1, z)]));
+        (model, variables)
+    }
+
+    #[test]
+    fn branch_and_bound_is_optimal() {
+        let (model, variables) = small_model();
+        let assignment = model.solve(Backend::BranchAndBound).unwrap();
+        assert_eq!(assignment.objective::<i64>(), 34);
+        assert_eq!(variables.iter().map(|variable| assignment.value::<i64>(*variable)).collect::<Vec<_>>(), vec![6, 6, -4]);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let (mut model, _variables) = small_model();
+        for goal in [Goal::Minimize, Goal::Maximize] {
+            model.objective.as_mut().unwrap().0 = goal;
+            assert_eq!(
+                model.solve(Backend::BranchAndBound).map(|assignment| assignment.objective::<i64>()),
+                model.solve(Backend::Z3).map(|assignment| assignment.objective::<i64>()),
+            );
+        }
+        model.constrain(LinearExpr::sum([Variable(0), Variable(1)]), Relation::GreaterEq, 13);
+        assert_eq!(model.solve(Backend::BranchAndBound), None);
+        assert_eq!(model.solve(Backend::Z3), None);
+    }
+
+    #[test]
+    fn feasible_assignments_are_all_visited() {
+        // Ways to write 10 as an ordered sum of three numbers from 0 to 5.
+        let mut model = Model::default();
+        let variables: Vec<Variable> = ["a", "b", "c"].iter().map(|name| model.int_var(name, 0, 5)).collect();
+        model.constrain(LinearExpr::sum(variables.iter().copied()), Relation::Equal, 10);
+        let mut count = 0;
+        model.for_each_feasible(|assignment| {
+            assert_eq!(variables.iter().map(|variable| assignment.value::<i64>(*variable)).sum::<i64>(), 10);
+            count += 1;
+        });
+        assert_eq!(count, 21);
+    }
+}
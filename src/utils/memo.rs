@@ -0,0 +1,204 @@
+//! Memoization for recursive functions, with hit and miss counts.
+//!
+//! A `Memo` is a cache keyed by a function's arguments. The function asks it
+//! for its value with `get_or_compute`, passing the computation as a closure
+//! that receives the memo back, so it can recurse through the same cache.
+//! Its statistics show how often the cache helped, which shows when a key
+//! is too fine-grained to ever repeat. They're printed by `report` once
+//! turned on with `report_stats`, which the CLI does with `--memo-stats`.
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REPORT_STATS: AtomicBool = AtomicBool::new(false);
+
+/// Turns printing memos' statistics in `Memo::report` on or off.
+pub fn report_stats(enabled: bool) {
+    REPORT_STATS.store(enabled, Ordering::Relaxed);
+}
+
+/// How often a memo's lookups found a cached value.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    pub evictions: usize,
+}
+
+impl Stats {
+    /// The fraction of lookups that found a cached value.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evictions",
+            self.hits, self.misses, 100.0 * self.hit_rate(), self.evictions,
+        )
+    }
+}
+
+/// A cache of a function's values by its arguments, optionally holding at
+/// most `capacity` values by evicting the oldest.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::memo::Memo;
+///
+/// fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+///     memo.get_or_compute(n, |memo| match n {
+///         0 | 1 => n,
+///         _ => fibonacci(n - 1, memo) + fibonacci(n - 2, memo),
+///     })
+/// }
+///
+/// let mut memo = Memo::default().named("fibonacci");
+/// assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+/// assert_eq!(memo.stats().misses, 91);
+/// assert_eq!(memo.stats().hits, 88);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Memo<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    name: Option<&'static str>,
+    values: HashMap<K, V>,
+    capacity: Option<usize>,
+    // Keys in the order they were cached, to evict the oldest first.
+    order: VecDeque<K>,
+    stats: Stats,
+}
+
+impl<K, V> Default for Memo<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    fn default() -> Self {
+        Self {
+            name: None,
+            values: HashMap::new(),
+            capacity: None,
+            order: VecDeque::new(),
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    /// A memo holding at most `capacity` values.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "Memo should be able to hold a value.");
+        Self {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Names the memo in its debug statistics.
+    pub fn named(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// The cached value for `key`, computing and caching it with `compute`
+    /// if there isn't one. `compute` gets the memo to recurse with.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = compute(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.values.insert(key.clone(), value).is_some() {
+            return;
+        }
+        if let Some(capacity) = self.capacity {
+            self.order.push_back(key);
+            while self.values.len() > capacity {
+                let oldest = self.order.pop_front().expect("Every cached key should be in order.");
+                self.values.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Forgets every value, keeping the statistics.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+
+    /// Prints the statistics to stderr, if turned on with `report_stats`.
+    pub fn report(&self) {
+        if REPORT_STATS.load(Ordering::Relaxed) {
+            eprintln!("memo {}: {}", self.name.unwrap_or("(unnamed)"), self.stats());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn num_paths(row: u32, col: u32, memo: &mut Memo<(u32, u32), u64>) -> u64 {
+        memo.get_or_compute((row, col), |memo| match (row, col) {
+            (0, _) | (_, 0) => 1,
+            _ => num_paths(row - 1, col, memo) + num_paths(row, col - 1, memo),
+        })
+    }
+
+    #[test]
+    fn bounded_memo_evicts_oldest() {
+        let mut memo = Memo::bounded(2);
+        assert_eq!(memo.get_or_compute(1, |_memo| 'a'), 'a');
+        assert_eq!(memo.get_or_compute(2, |_memo| 'b'), 'b');
+        assert_eq!(memo.get_or_compute(3, |_memo| 'c'), 'c');
+        assert_eq!(memo.len(), 2);
+        assert_eq!(memo.get_or_compute(1, |_memo| 'd'), 'd');
+        assert_eq!(memo.get_or_compute(3, |_memo| 'e'), 'c');
+        assert_eq!(memo.stats(), Stats { hits: 1, misses: 4, evictions: 2 });
+    }
+
+    #[test]
+    fn bounded_memo_still_computes_correctly() {
+        let mut unbounded = Memo::default();
+        let mut bounded = Memo::bounded(5);
+        assert_eq!(num_paths(10, 10, &mut unbounded), 184_756);
+        assert_eq!(num_paths(10, 10, &mut bounded), 184_756);
+        assert_eq!(unbounded.stats().misses, 120);
+        assert!(bounded.stats().misses > unbounded.stats().misses);
+        assert_eq!(bounded.len(), 5);
+    }
+}
@@ -0,0 +1,223 @@
+//! Named, typed parameters that a solution can declare, such as a grid size
+//! or a number of steps, so that the puzzle's examples (which often use
+//! smaller values than the real input) can be run without special
+//! constructors.
+//!
+//! A solution lists its parameters, with their current values, in
+//! [`Solution::params`] and accepts overrides in [`Solution::set_param`].
+//! Both are generated from one list of fields by the
+//! [`solution_params!`](crate::solution_params) macro. The command line
+//! passes overrides as `--param width=11`, and tests use [`apply`] to do the
+//! same.
+use std::any;
+use std::fmt;
+use std::str::FromStr;
+
+use super::solution::Solution;
+
+/// A parameter a solution accepts.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Param {
+    name: &'static str,
+    type_name: &'static str,
+    value: String,
+    description: &'static str,
+}
+
+impl Param {
+    /// Declares a parameter with its current (usually default) value.
+    pub fn new<T: fmt::Display + ?Sized>(name: &'static str, value: &T, description: &'static str) -> Self {
+        Self {
+            name,
+            type_name: any::type_name::<T>(),
+            value: value.to_string(),
+            description,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}, currently {}): {}", self.name, self.type_name, self.value, self.description)
+    }
+}
+
+/// Why a parameter could not be set.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParamError {
+    /// The assignment was not of the form `name=value`.
+    Malformed(String),
+    /// The solution has no parameter with this name.
+    Unknown { name: String, known: Vec<String> },
+    /// The value could not be parsed as the parameter's type.
+    Invalid { name: String, value: String, message: String },
+}
+
+impl ParamError {
+    /// The error for a name not among `params`.
+    pub fn unknown(name: &str, params: &[Param]) -> Self {
+        Self::Unknown {
+            name: String::from(name),
+            known: params.iter().map(|param| param.to_string()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(assignment) => write!(f, "expected `name=value`, found `{assignment}`"),
+            Self::Unknown { name, known } if known.is_empty() => {
+                write!(f, "unknown parameter `{name}`; this solution has no parameters")
+            },
+            Self::Unknown { name, known } => {
+                write!(f, "unknown parameter `{name}`; parameters are:")?;
+                known.iter().try_for_each(|param| write!(f, "\n  {param}"))
+            },
+            Self::Invalid { name, value, message } => {
+                write!(f, "invalid value `{value}` for parameter `{name}`: {message}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Splits a `name=value` assignment, as passed on the command line.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::params::parse_assignment;
+///
+/// assert_eq!(parse_assignment("width=11"), Ok((String::from("width"), String::from("11"))));
+/// assert!(parse_assignment("width").is_err());
+/// ```
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), ParamError> {
+    match assignment.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((String::from(name.trim()), String::from(value.trim())))
+        },
+        _ => Err(ParamError::Malformed(String::from(assignment))),
+    }
+}
+
+/// Parses the value of parameter `name` as a `T`.
+pub fn parse_value<T>(name: &str, value: &str) -> Result<T, ParamError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|err: T::Err| ParamError::Invalid {
+        name: String::from(name),
+        value: String::from(value),
+        message: err.to_string(),
+    })
+}
+
+/// Sets each of the parameters on the solution, stopping at the first one
+/// that fails.
+pub fn apply<S, N, V>(soln: &mut S, assignments: &[(N, V)]) -> Result<(), ParamError>
+where
+    S: Solution + ?Sized,
+    N: AsRef<str>,
+    V: AsRef<str>,
+{
+    assignments.iter()
+        .try_for_each(|(name, value)| soln.set_param(name.as_ref(), value.as_ref()))
+}
+
+/// Implements [`Solution::params`] and [`Solution::set_param`] inside an
+/// `impl Solution` block, from a list of the solution's fields with their
+/// descriptions. Each field is a parameter of the same name, and its type's
+/// [`FromStr`] parses overrides.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::solution_params;
+/// use advent_of_code_rust::utils::{params, solution::{Answer, Solution}};
+///
+/// struct Stepper {
+///     steps: usize,
+/// }
+///
+/// impl Solution for Stepper {
+///     fn solve(&mut self, _filename: &str) -> Answer {
+///         Answer::Usize(self.steps)
+///     }
+///
+///     solution_params! {
+///         steps: "How many steps to take",
+///     }
+/// }
+///
+/// let mut stepper = Stepper { steps: 100 };
+/// assert_eq!(stepper.params()[0].value(), "100");
+/// params::apply(&mut stepper, &[("steps", "4")]).unwrap();
+/// assert_eq!(stepper.solve(""), Answer::Usize(4));
+/// ```
+#[macro_export]
+macro_rules! solution_params {
+    ($($field:ident: $description:literal),+ $(,)?) => {
+        fn params(&self) -> Vec<$crate::utils::params::Param> {
+            vec![$($crate::utils::params::Param::new(stringify!($field), &self.$field, $description)),+]
+        }
+
+        fn set_param(&mut self, name: &str, value: &str) -> Result<(), $crate::utils::params::ParamError> {
+            match name {
+                $(stringify!($field) => self.$field = $crate::utils::params::parse_value(name, value)?,)+
+                _ => return Err($crate::utils::params::ParamError::unknown(name, &self.params())),
+            }
+            Ok(())
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::solution::Answer;
+
+    #[derive(Debug)]
+    struct Stepper {
+        steps: usize,
+    }
+
+    impl Solution for Stepper {
+        fn solve(&mut self, _filename: &str) -> Answer {
+            Answer::Usize(self.steps)
+        }
+
+        crate::solution_params! {
+            steps: "How many steps to take",
+        }
+    }
+
+    #[test]
+    fn params_are_applied() {
+        let mut stepper = Stepper { steps: 100 };
+        assert_eq!(stepper.params()[0].to_string(), "steps (usize, currently 100): How many steps to take");
+        apply(&mut stepper, &[("steps", "4")]).unwrap();
+        assert_eq!(stepper.solve(""), Answer::Usize(4));
+    }
+
+    #[test]
+    fn param_errors_are_reported() {
+        let mut stepper = Stepper { steps: 100 };
+        assert_eq!(
+            apply(&mut stepper, &[("stpes", "4")]).unwrap_err().to_string(),
+            "unknown parameter `stpes`; parameters are:\n  steps (usize, currently 100): How many steps to take",
+        );
+        assert!(matches!(
+            apply(&mut stepper, &[("steps", "-4")]),
+            Err(ParamError::Invalid { .. }),
+        ));
+    }
+}
@@ -0,0 +1,359 @@
+//! A small typed parsing layer for puzzle input.
+//!
+//! The [`scan!`](crate::scan) macro matches a line against a pattern with
+//! placeholders and parses each placeholder into a type, reporting the line
+//! and column of anything that doesn't fit. The remaining helpers cover the
+//! other common input shapes: integers scattered through a line,
+//! blank-line-separated blocks and character grids.
+use std::any;
+use std::fmt;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::io_utils;
+
+lazy_static! {
+    static ref INT_RE: Regex = Regex::new(r"-?\d+").unwrap();
+}
+
+/// An error locating where parsing failed. Lines and columns are 1-based; a
+/// line of 0 means the line is not known (yet).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(column: usize, message: String) -> Self {
+        Self { line: 0, column, message }
+    }
+
+    /// Attaches the 1-based line number the error occurred on.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line != 0 {
+            write!(f, "line {}, ", self.line)?;
+        }
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A piece of text captured by a placeholder, with the 1-based column it
+/// starts at.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Field<'a> {
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl Field<'_> {
+    /// Parses the field's text, reporting the field's column on failure.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.text.parse().map_err(|err| {
+            ParseError::new(
+                self.column,
+                format!("cannot parse `{}` as `{}`: {}", self.text, any::type_name::<T>(), err),
+            )
+        })
+    }
+}
+
+#[derive(Debug)]
+enum Piece {
+    Literal(String),
+    Token,
+    Text,
+}
+
+impl Piece {
+    fn regex(&self) -> String {
+        match self {
+            Self::Literal(literal) => regex::escape(literal),
+            Self::Token => String::from(r"(\S+?)"),
+            Self::Text => String::from(r"(.+?)"),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Literal(literal) => format!("`{literal}`"),
+            Self::Token => String::from("a value"),
+            Self::Text => String::from("some text"),
+        }
+    }
+}
+
+/// A compiled line pattern. In the pattern, `{}` matches a value with no
+/// whitespace and `{..}` matches any non-empty text (which may contain
+/// spaces); everything else must match literally, and the whole line must be
+/// matched.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::parse::Scanner;
+///
+/// let scanner = Scanner::new("{..} {},{} through {},{}");
+/// let fields = scanner.scan("turn on 0,0 through 999,999").unwrap();
+/// assert_eq!(fields[0].text, "turn on");
+/// assert_eq!(fields[4].parse::<u32>(), Ok(999));
+///
+/// let err = scanner.scan("toggle 0,0 to 999,999").unwrap_err();
+/// assert_eq!(err.column, 11);
+/// assert_eq!(err.message, "expected ` through `");
+/// ```
+#[derive(Debug)]
+pub struct Scanner {
+    pieces: Vec<Piece>,
+    regex: Regex,
+}
+
+impl Scanner {
+    pub fn new(pattern: &str) -> Self {
+        let mut pieces = Vec::new();
+        let mut rest = pattern;
+        while !rest.is_empty() {
+            let (idx, len, piece) = match (rest.find("{}"), rest.find("{..}")) {
+                (Some(token), Some(text)) if text < token => (text, 4, Piece::Text),
+                (Some(token), _) => (token, 2, Piece::Token),
+                (None, Some(text)) => (text, 4, Piece::Text),
+                (None, None) => (rest.len(), 0, Piece::Literal(String::new())),
+            };
+            if idx > 0 {
+                pieces.push(Piece::Literal(String::from(&rest[..idx])));
+            }
+            if len > 0 {
+                pieces.push(piece);
+            }
+            rest = &rest[idx + len..];
+        }
+        let regex = Regex::new(&format!(
+            "^{}$",
+            pieces.iter().map(|piece| piece.regex()).collect::<String>(),
+        )).expect("Pattern should compile to a valid regex.");
+        Self { pieces, regex }
+    }
+
+    /// Returns the number of placeholders in the pattern.
+    pub fn fields(&self) -> usize {
+        self.regex.captures_len() - 1
+    }
+
+    /// Matches `line` against the pattern, returning the text captured by
+    /// each placeholder in order.
+    pub fn scan<'a>(&self, line: &'a str) -> Result<Vec<Field<'a>>, ParseError> {
+        match self.regex.captures(line) {
+            Some(captures) => Ok(
+                captures.iter()
+                    .skip(1)
+                    .map(|capture| {
+                        let capture = capture.expect("Every placeholder should capture.");
+                        Field { column: capture.start() + 1, text: capture.as_str() }
+                    })
+                    .collect()
+            ),
+            None => Err(self.mismatch(line)),
+        }
+    }
+
+    /// Explains why `line` does not match: finds the longest prefix of the
+    /// pattern that does match and reports what was expected after it.
+    fn mismatch(&self, line: &str) -> ParseError {
+        for matched in (0..=self.pieces.len()).rev() {
+            let prefix = Regex::new(&format!(
+                "^{}",
+                self.pieces[..matched].iter().map(|piece| piece.regex()).collect::<String>(),
+            )).unwrap();
+            if let Some(found) = prefix.find(line) {
+                let message = match self.pieces.get(matched) {
+                    Some(piece) => format!("expected {}", piece.describe()),
+                    None => String::from("unexpected trailing text"),
+                };
+                return ParseError::new(found.end() + 1, message);
+            }
+        }
+        unreachable!("The empty prefix should always match.");
+    }
+}
+
+/// Matches a line against a pattern (see [`Scanner`]) and parses each
+/// placeholder into the corresponding type, returning a tuple of the values
+/// or the [`ParseError`] locating the first problem. The pattern is compiled
+/// once per call site.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::scan;
+///
+/// let (program, pipes) = scan!("2 <-> 0, 3, 4", "{} <-> {..}", u32, String).unwrap();
+/// assert_eq!(program, 2);
+/// assert_eq!(pipes, "0, 3, 4");
+///
+/// let err = scan!("x <-> 0", "{} <-> {..}", u32, String).unwrap_err();
+/// assert_eq!(err.column, 1);
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($line:expr, $pattern:literal, $($ty:ty),+ $(,)?) => {{
+        static SCANNER: std::sync::OnceLock<$crate::utils::parse::Scanner> = std::sync::OnceLock::new();
+        let scanner = SCANNER.get_or_init(|| {
+            let scanner = $crate::utils::parse::Scanner::new($pattern);
+            assert_eq!(
+                scanner.fields(),
+                [$(stringify!($ty)),+].len(),
+                "Pattern should have one placeholder per type.",
+            );
+            scanner
+        });
+        scanner.scan($line).and_then(|fields| {
+            let mut fields = fields.into_iter();
+            Ok(($(fields.next().unwrap().parse::<$ty>()?,)+))
+        })
+    }};
+}
+
+/// Parses each line into a `T`, attaching the line number to any error.
+pub fn parse_lines<T, L>(lines: impl Iterator<Item = L>) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+    L: AsRef<str>,
+{
+    lines.enumerate()
+        .map(|(idx, line)| line.as_ref().parse().map_err(|err: ParseError| err.at_line(idx + 1)))
+        .collect()
+}
+
+/// Parses each line of the file into a `T`. Panics with the file, line and
+/// column if any line fails to parse.
+pub fn file_to_parsed_lines<T>(filename: &str) -> Vec<T>
+where
+    T: FromStr<Err = ParseError>,
+{
+    parse_lines(io_utils::file_to_lines(filename))
+        .unwrap_or_else(|err| panic!("Should be able to parse {filename}: {err}"))
+}
+
+/// Returns every integer in `line`, in order. A `-` directly before the
+/// digits makes the integer negative.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::parse::ints;
+///
+/// assert_eq!(ints::<i32>("p=0,4 v=3,-3"), vec![0, 4, 3, -3]);
+/// assert_eq!(ints::<u64>("Button A: X+94, Y+34"), vec![94, 34]);
+/// ```
+pub fn ints<T>(line: &str) -> Vec<T>
+where
+    T: FromStr,
+    T::Err: fmt::Debug,
+{
+    INT_RE.find_iter(line)
+        .map(|found| found.as_str().parse().expect("Should be able to parse the integer."))
+        .collect()
+}
+
+/// Splits text into blocks of lines separated by blank lines.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::parse::blocks;
+///
+/// assert_eq!(blocks("a\nb\n\nc\n"), vec![vec!["a", "b"], vec!["c"]]);
+/// ```
+pub fn blocks(text: &str) -> Vec<Vec<&str>> {
+    let mut blocks = vec![];
+    let mut block = vec![];
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !block.is_empty() {
+                blocks.push(block);
+                block = vec![];
+            }
+        } else {
+            block.push(line);
+        }
+    }
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+    blocks
+}
+
+/// Converts lines of text to a grid of characters, indexed by row then
+/// column.
+pub fn char_grid<L>(lines: impl IntoIterator<Item = L>) -> Vec<Vec<char>>
+where
+    L: AsRef<str>,
+{
+    lines.into_iter()
+        .map(|line| line.as_ref().chars().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Claim {
+        id: u32,
+        left: u32,
+        top: u32,
+        width: u32,
+        height: u32,
+    }
+
+    impl FromStr for Claim {
+        type Err = ParseError;
+
+        fn from_str(line: &str) -> Result<Self, Self::Err> {
+            let (id, left, top, width, height) = crate::scan!(line, "#{} @ {},{}: {}x{}", u32, u32, u32, u32, u32)?;
+            Ok(Self { id, left, top, width, height })
+        }
+    }
+
+    #[test]
+    fn scan_is_correct() {
+        assert_eq!(
+            "#123 @ 3,2: 5x4".parse(),
+            Ok(Claim { id: 123, left: 3, top: 2, width: 5, height: 4 }),
+        );
+    }
+
+    #[test]
+    fn parse_errors_are_located() {
+        let err = parse_lines::<Claim, _>(["#1 @ 1,3: 4x4", "#2 @ 3,1: 4xfour"].into_iter()).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 13);
+        assert!(err.message.starts_with("cannot parse `four` as `u32`"));
+        assert_eq!(
+            "#3 @ 5,5 2x2".parse::<Claim>(),
+            Err(ParseError::new(9, String::from("expected `: `"))),
+        );
+        assert_eq!(
+            "#3 @ 5,5: 2x2 extra".parse::<Claim>().unwrap_err(),
+            ParseError::new(14, String::from("unexpected trailing text")),
+        );
+    }
+
+    #[test]
+    fn char_grid_is_correct() {
+        assert_eq!(char_grid(["#.", ".#"]), vec![vec!['#', '.'], vec!['.', '#']]);
+    }
+}
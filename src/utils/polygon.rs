@@ -0,0 +1,175 @@
+//! Closed-form measurements of simple polygons whose vertices lie on the
+//! integer lattice.
+//!
+//! Area comes from the [shoelace formula](https://en.wikipedia.org/wiki/Shoelace_formula),
+//! boundary points from counting lattice points along each edge, and interior
+//! points from [Pick's theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem):
+//! `A = I + B / 2 - 1`. All of these are O(n) in the number of vertices, no
+//! matter how large the polygon is.
+use gcd::Gcd;
+
+/// A point on the integer lattice.
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Vertex {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Vertex {
+    pub fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+}
+
+/// A simple (non-self-intersecting) polygon with vertices on the integer
+/// lattice, listed in order (either orientation). The polygon is implicitly
+/// closed: the last vertex connects back to the first. Vertices that are
+/// collinear with their neighbors are allowed, so the tiles of a grid loop can
+/// be passed directly.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::polygon::{LatticePolygon, Vertex};
+///
+/// // A 4x3 rectangle.
+/// let rectangle = LatticePolygon::new(vec![
+///     Vertex::new(0, 0),
+///     Vertex::new(4, 0),
+///     Vertex::new(4, 3),
+///     Vertex::new(0, 3),
+/// ]);
+/// assert_eq!(rectangle.area(), 12);
+/// assert_eq!(rectangle.boundary_points(), 14);
+/// assert_eq!(rectangle.interior_points(), 6);
+/// assert_eq!(rectangle.enclosed_points(), 20);
+///
+/// // A triangle with a half-integral area.
+/// let triangle = LatticePolygon::new(vec![
+///     Vertex::new(0, 0),
+///     Vertex::new(3, 0),
+///     Vertex::new(0, 3),
+/// ]);
+/// assert_eq!(triangle.doubled_area(), 9);
+/// assert_eq!(triangle.boundary_points(), 9);
+/// assert_eq!(triangle.interior_points(), 1);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct LatticePolygon {
+    vertices: Vec<Vertex>,
+}
+
+impl FromIterator<Vertex> for LatticePolygon {
+    fn from_iter<I: IntoIterator<Item = Vertex>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl LatticePolygon {
+    pub fn new(vertices: Vec<Vertex>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Iterates over every edge as a (start, end) pair, including the closing
+    /// edge from the last vertex back to the first.
+    fn edges(&self) -> impl Iterator<Item = (&Vertex, &Vertex)> {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    /// Returns twice the area. Always an integer for lattice polygons, so this
+    /// is exact even when the area itself is half-integral.
+    pub fn doubled_area(&self) -> u64 {
+        let signed: i128 = self.edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+            .sum();
+        signed.unsigned_abs()
+            .try_into()
+            .expect("Doubled area should fit into `u64` datatype.")
+    }
+
+    /// Returns the area. Panics if the area is not an integer (which cannot
+    /// happen for rectilinear polygons); use `doubled_area` otherwise.
+    pub fn area(&self) -> u64 {
+        let doubled_area = self.doubled_area();
+        assert!(doubled_area.is_multiple_of(2), "Area should be an integer.");
+        doubled_area / 2
+    }
+
+    /// Returns the number of lattice points on the boundary.
+    pub fn boundary_points(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| a.x.abs_diff(b.x).gcd(a.y.abs_diff(b.y)))
+            .sum()
+    }
+
+    /// Returns the number of lattice points strictly inside the polygon, by
+    /// Pick's theorem.
+    pub fn interior_points(&self) -> u64 {
+        (self.doubled_area() + 2 - self.boundary_points()) / 2
+    }
+
+    /// Returns the number of lattice points inside or on the boundary of the
+    /// polygon. When each lattice point stands for a unit grid cell, this is
+    /// the number of cells covered by the polygon including its border.
+    pub fn enclosed_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientation_does_not_matter() {
+        let vertices = vec![
+            Vertex::new(0, 0),
+            Vertex::new(6, 0),
+            Vertex::new(6, 5),
+            Vertex::new(4, 5),
+            Vertex::new(4, 2),
+            Vertex::new(0, 2),
+        ];
+        let clockwise = LatticePolygon::new(vertices.clone());
+        let counterclockwise: LatticePolygon = vertices.into_iter().rev().collect();
+        assert_eq!(clockwise.area(), 18);
+        assert_eq!(counterclockwise.area(), 18);
+        assert_eq!(clockwise.boundary_points(), 22);
+        assert_eq!(counterclockwise.interior_points(), 8);
+    }
+
+    #[test]
+    fn collinear_vertices_are_correct() {
+        // A 2x2 square traced through every lattice point on its boundary.
+        let polygon = LatticePolygon::new(vec![
+            Vertex::new(0, 0),
+            Vertex::new(1, 0),
+            Vertex::new(2, 0),
+            Vertex::new(2, 1),
+            Vertex::new(2, 2),
+            Vertex::new(1, 2),
+            Vertex::new(0, 2),
+            Vertex::new(0, 1),
+        ]);
+        assert_eq!(polygon.area(), 4);
+        assert_eq!(polygon.boundary_points(), 8);
+        assert_eq!(polygon.interior_points(), 1);
+        assert_eq!(polygon.enclosed_points(), 9);
+    }
+
+    #[test]
+    fn large_coordinates_are_correct() {
+        let side = 1_000_000_000;
+        let polygon = LatticePolygon::new(vec![
+            Vertex::new(-side, -side),
+            Vertex::new(side, -side),
+            Vertex::new(side, side),
+            Vertex::new(-side, side),
+        ]);
+        assert_eq!(polygon.area(), 4 * side as u64 * side as u64);
+        assert_eq!(polygon.enclosed_points(), (2 * side as u64 + 1).pow(2));
+    }
+}
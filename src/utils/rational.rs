@@ -0,0 +1,220 @@
+//! Exact rational arithmetic and linear solving, for puzzles whose
+//! coordinates are too large to trust to floating point.
+//!
+//! Numerators and denominators are `i128`s kept in lowest terms. Arithmetic
+//! panics on overflow rather than silently losing precision.
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use gcd::Gcd;
+
+/// A fraction in lowest terms, with a positive denominator.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::rational::Rational;
+///
+/// let third = Rational::new(2, 6);
+/// assert_eq!(third, Rational::new(-1, -3));
+/// assert_eq!(third + Rational::new(1, 1), Rational::new(4, 3));
+/// assert_eq!((third * Rational::new(3, 1)).to_integer(), Some(1));
+/// assert!(third < Rational::new(1, 2));
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numerator: 0, denominator: 1 };
+
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert_ne!(denominator, 0, "Denominator should be nonzero.");
+        let divisor = numerator.unsigned_abs().gcd(denominator.unsigned_abs()) as i128;
+        let sign = denominator.signum();
+        Self {
+            numerator: sign * numerator / divisor,
+            denominator: sign * denominator / divisor,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+}
+
+fn checked(value: Option<i128>) -> i128 {
+    value.expect("Rational arithmetic should fit into `i128` datatype.")
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self { numerator: value.into(), denominator: 1 }
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self { numerator: value, denominator: 1 }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: Rational) -> Rational {
+        let divisor = self.denominator.unsigned_abs().gcd(rhs.denominator.unsigned_abs()) as i128;
+        let lhs_scale = rhs.denominator / divisor;
+        let rhs_scale = self.denominator / divisor;
+        Rational::new(
+            checked(checked(self.numerator.checked_mul(lhs_scale))
+                .checked_add(checked(rhs.numerator.checked_mul(rhs_scale)))),
+            checked(self.denominator.checked_mul(lhs_scale)),
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational { numerator: -self.numerator, denominator: self.denominator }
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: Rational) -> Rational {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: Rational) -> Rational {
+        // Cancel across before multiplying to keep the products small.
+        let left = Rational::new(self.numerator, rhs.denominator);
+        let right = Rational::new(rhs.numerator, self.denominator);
+        Rational::new(
+            checked(left.numerator.checked_mul(right.numerator)),
+            checked(left.denominator.checked_mul(right.denominator)),
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, rhs: Rational) -> Rational {
+        assert!(!rhs.is_zero(), "Should not divide by zero.");
+        self * Rational::new(rhs.denominator, rhs.numerator)
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        checked(self.numerator.checked_mul(other.denominator))
+            .cmp(&checked(other.numerator.checked_mul(self.denominator)))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_integer() {
+            Some(integer) => write!(f, "{integer}"),
+            None => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+/// Solves a system of linear equations by Gaussian elimination. Each row of
+/// `augmented` holds an equation's coefficients followed by its constant
+/// term. There may be more equations than unknowns, as long as they agree.
+///
+/// Returns `None` unless there is exactly one solution.
+///
+/// # Examples
+/// ```
+/// use advent_of_code_rust::utils::rational::{solve_linear_system, Rational};
+///
+/// // x + y = 3, x - y = 1, 2x = 4
+/// let system = [[1, 1, 3], [1, -1, 1], [2, 0, 4]]
+///     .map(|row| row.map(|value: i64| Rational::from(value)).to_vec())
+///     .to_vec();
+/// assert_eq!(solve_linear_system(system), Some(vec![Rational::new(2, 1), Rational::new(1, 1)]));
+///
+/// // x + y = 3, 2x + 2y = 5
+/// let system = [[1, 1, 3], [2, 2, 5]]
+///     .map(|row| row.map(|value: i64| Rational::from(value)).to_vec())
+///     .to_vec();
+/// assert_eq!(solve_linear_system(system), None);
+/// ```
+pub fn solve_linear_system(mut augmented: Vec<Vec<Rational>>) -> Option<Vec<Rational>> {
+    let unknowns = augmented.first()?.len() - 1;
+    if augmented.len() < unknowns { return None; }
+    for col in 0..unknowns {
+        let pivot = (col..augmented.len()).find(|row| !augmented[*row][col].is_zero())?;
+        augmented.swap(col, pivot);
+        let pivot_value = augmented[col][col];
+        augmented[col].iter_mut().for_each(|value| *value = *value / pivot_value);
+        let pivot_row = augmented[col].clone();
+        for (row_idx, row) in augmented.iter_mut().enumerate() {
+            let factor = row[col];
+            if row_idx == col || factor.is_zero() { continue; }
+            row.iter_mut()
+                .zip(pivot_row.iter())
+                .skip(col)
+                .for_each(|(value, pivot_row_value)| *value = *value - factor * *pivot_row_value);
+        }
+    }
+    // Any equations left over must have been combinations of the others.
+    if augmented[unknowns..].iter().any(|row| !row[unknowns].is_zero()) {
+        return None;
+    }
+    Some(augmented.iter().take(unknowns).map(|row| row[unknowns]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_is_exact() {
+        let big = Rational::from(400_000_000_000_000_i64);
+        let third = Rational::new(1, 3);
+        assert_eq!((big * third - big / Rational::new(3, 1)), Rational::ZERO);
+        assert_eq!(Rational::new(3, -6).to_string(), "-1/2");
+        assert_eq!((Rational::new(7, 2) - Rational::new(1, 2)).to_string(), "3");
+    }
+
+    #[test]
+    fn inconsistent_systems_have_no_solution() {
+        let system = [[1, 0, 1], [0, 1, 2], [1, 1, 4]]
+            .map(|row| row.map(|value: i64| Rational::from(value)).to_vec())
+            .to_vec();
+        assert_eq!(solve_linear_system(system), None);
+    }
+}
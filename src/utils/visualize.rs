@@ -0,0 +1,399 @@
+//! Frame-by-frame rendering of simulation-style puzzles, for debugging them
+//! by eye.
+//!
+//! A simulation implements [`Visualize`] by rendering its current state as a
+//! [`Frame`] (a grid of characters, usually in the puzzle's own notation) and
+//! by stepping forward. The frames can then be played in the terminal with
+//! ANSI colours or written to a text file or an animated GIF, none of which
+//! need a display server.
+use std::fmt;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::thread;
+use std::time::Duration;
+
+/// The colours frames are drawn in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Colour {
+    Background,
+    Grey,
+    White,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    const ALL: [Colour; 9] = [
+        Colour::Background,
+        Colour::Grey,
+        Colour::White,
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+    ];
+
+    /// The ANSI SGR code for the colour as a foreground colour.
+    fn ansi_code(self) -> u8 {
+        match self {
+            Colour::Background => 90,
+            Colour::Grey => 37,
+            Colour::White => 97,
+            Colour::Red => 91,
+            Colour::Green => 92,
+            Colour::Yellow => 93,
+            Colour::Blue => 94,
+            Colour::Magenta => 95,
+            Colour::Cyan => 96,
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Colour::Background => [16, 16, 32],
+            Colour::Grey => [128, 128, 128],
+            Colour::White => [240, 240, 240],
+            Colour::Red => [230, 60, 60],
+            Colour::Green => [60, 200, 80],
+            Colour::Yellow => [240, 210, 60],
+            Colour::Blue => [60, 100, 230],
+            Colour::Magenta => [210, 80, 210],
+            Colour::Cyan => [80, 210, 230],
+        }
+    }
+
+    /// The colour's index in the GIF palette.
+    fn index(self) -> u8 {
+        Colour::ALL.iter().position(|colour| *colour == self).unwrap() as u8
+    }
+}
+
+/// The colour used for characters that a simulation doesn't colour itself:
+/// empty space is drawn as background and walls in white.
+pub fn default_colour(ch: char) -> Colour {
+    match ch {
+        '.' | ' ' => Colour::Background,
+        '#' => Colour::White,
+        _ => Colour::Grey,
+    }
+}
+
+/// A rendered state of a simulation: a caption (e.g., the step number) and a
+/// grid of characters. Rows may have different lengths.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Frame {
+    caption: String,
+    rows: Vec<Vec<char>>,
+}
+
+impl Frame {
+    pub fn new(caption: &str) -> Self {
+        Self { caption: String::from(caption), rows: Vec::new() }
+    }
+
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = char>) {
+        self.rows.push(row.into_iter().collect());
+    }
+
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+
+    pub fn rows(&self) -> &[Vec<char>] {
+        &self.rows
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Renders the frame with ANSI colour codes, colouring each character
+    /// with `colour`.
+    pub fn to_ansi(&self, colour: impl Fn(char) -> Colour) -> String {
+        let mut ansi = format!("{}\n", self.caption);
+        for row in self.rows.iter() {
+            let mut current = None;
+            for ch in row.iter() {
+                let ch_colour = colour(*ch);
+                if current != Some(ch_colour) {
+                    ansi.push_str(&format!("\x1b[{}m", ch_colour.ansi_code()));
+                    current = Some(ch_colour);
+                }
+                ansi.push(*ch);
+            }
+            ansi.push_str("\x1b[0m\n");
+        }
+        ansi
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.caption)?;
+        for row in self.rows.iter() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// A simulation that can be watched one step at a time.
+pub trait Visualize {
+    /// Renders the current state.
+    fn frame(&self) -> Frame;
+
+    /// Advances the simulation by one step. Returns `false` (without changing
+    /// anything) once the simulation has finished.
+    fn step(&mut self) -> bool;
+
+    /// The colour to draw a character of a frame in.
+    fn colour(&self, ch: char) -> Colour {
+        default_colour(ch)
+    }
+}
+
+/// Where to send an animation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Target {
+    Terminal,
+    Text(String),
+    Gif(String),
+}
+
+impl Target {
+    /// Plays in the terminal without a filename; otherwise writes a GIF for
+    /// filenames ending in `.gif` and text for anything else.
+    pub fn from_filename(filename: Option<&str>) -> Self {
+        match filename {
+            None => Self::Terminal,
+            Some(filename) if filename.to_lowercase().ends_with(".gif") => Self::Gif(String::from(filename)),
+            Some(filename) => Self::Text(String::from(filename)),
+        }
+    }
+}
+
+/// How to turn a simulation into an animation.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Animation {
+    pub target: Target,
+    /// Only every `every`-th step is drawn (the final state always is).
+    pub every: usize,
+    /// How long each frame is shown in the terminal or GIF.
+    pub delay: Duration,
+}
+
+impl Animation {
+    /// Steps the simulation to the end, sending the frames to the target.
+    /// Returns the number of frames drawn.
+    pub fn run(&self, simulation: &mut dyn Visualize) -> io::Result<usize> {
+        match &self.target {
+            Target::Terminal => {
+                let mut stdout = io::stdout().lock();
+                write!(stdout, "\x1b[2J\x1b[?25l")?;
+                let mut count = 0;
+                let result = self.for_each_frame(simulation, |frame, colour| {
+                    count += 1;
+                    write!(stdout, "\x1b[H{}\x1b[J", frame.to_ansi(colour))?;
+                    stdout.flush()?;
+                    thread::sleep(self.delay);
+                    Ok(())
+                });
+                write!(stdout, "\x1b[?25h")?;
+                result.map(|_| count)
+            },
+            Target::Text(filename) => {
+                let mut file = io::BufWriter::new(std::fs::File::create(filename)?);
+                let mut count = 0;
+                self.for_each_frame(simulation, |frame, _| {
+                    count += 1;
+                    writeln!(file, "{frame}")
+                })?;
+                file.flush()?;
+                Ok(count)
+            },
+            Target::Gif(filename) => {
+                let file = io::BufWriter::new(std::fs::File::create(filename)?);
+                let mut gif = GifWriter::new(file, self.delay);
+                let mut count = 0;
+                self.for_each_frame(simulation, |frame, colour| {
+                    count += 1;
+                    gif.write_frame(frame, colour)
+                })?;
+                gif.finish()?.flush()?;
+                Ok(count)
+            },
+        }
+    }
+
+    fn for_each_frame<F>(&self, simulation: &mut dyn Visualize, mut draw: F) -> io::Result<()>
+    where
+        F: FnMut(&Frame, &dyn Fn(char) -> Colour) -> io::Result<()>,
+    {
+        draw(&simulation.frame(), &|ch| simulation.colour(ch))?;
+        let mut steps = 0;
+        loop {
+            let stepped = simulation.step();
+            if stepped {
+                steps += 1;
+            }
+            if (stepped && steps % self.every == 0) || (!stepped && steps % self.every != 0) {
+                draw(&simulation.frame(), &|ch| simulation.colour(ch))?;
+            }
+            if !stepped {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Writes frames as a looping animated GIF as they come, drawing each
+/// character as a square block of its colour. The block size is picked from
+/// the first frame, and the canvas grows to fit the largest frame once
+/// [`GifWriter::finish`] is called; captions are not drawn.
+pub struct GifWriter<W: Write + Seek> {
+    writer: Option<W>,
+    encoder: Option<gif::Encoder<W>>,
+    delay: Duration,
+    cell_size: usize,
+    width: u16,
+    height: u16,
+}
+
+impl<W: Write + Seek> GifWriter<W> {
+    pub fn new(writer: W, delay: Duration) -> Self {
+        Self { writer: Some(writer), encoder: None, delay, cell_size: 1, width: 1, height: 1 }
+    }
+
+    /// Draws `frame` as the next frame of the GIF.
+    pub fn write_frame(&mut self, frame: &Frame, colour: &dyn Fn(char) -> Colour) -> io::Result<()> {
+        let cols = frame.width().max(1);
+        let rows = frame.height().max(1);
+        if self.encoder.is_none() {
+            // Keep the image a manageable size for large grids.
+            self.cell_size = (1_600 / cols.max(rows)).clamp(1, 8);
+            self.start()?;
+        }
+        let width: u16 = (cols * self.cell_size).try_into().expect("GIF width should fit into `u16` datatype.");
+        let height: u16 = (rows * self.cell_size).try_into().expect("GIF height should fit into `u16` datatype.");
+        self.width = self.width.max(width);
+        self.height = self.height.max(height);
+        let mut pixels = vec![Colour::Background.index(); width as usize * height as usize];
+        for (row, line) in frame.rows().iter().enumerate() {
+            for (col, ch) in line.iter().enumerate() {
+                let idx = colour(*ch).index();
+                for y in row * self.cell_size..(row + 1) * self.cell_size {
+                    pixels[y * width as usize + col * self.cell_size..y * width as usize + (col + 1) * self.cell_size].fill(idx);
+                }
+            }
+        }
+        let mut gif_frame = gif::Frame::from_indexed_pixels(width, height, pixels, None);
+        gif_frame.delay = (self.delay.as_millis() / 10).try_into().unwrap_or(u16::MAX);
+        // Clear the frame once it's shown, in case the next one is smaller.
+        gif_frame.dispose = gif::DisposalMethod::Background;
+        self.encoder.as_mut().unwrap().write_frame(&gif_frame).map_err(io::Error::other)
+    }
+
+    /// Ends the GIF, sizes its canvas to fit every frame, and returns the
+    /// writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.encoder.is_none() {
+            self.start()?;
+        }
+        let mut writer = self.encoder.take().unwrap().into_inner()?;
+        let end = writer.stream_position()?;
+        // The canvas size follows the 6 byte `GIF89a` signature.
+        writer.seek(SeekFrom::Start(6))?;
+        writer.write_all(&self.width.to_le_bytes())?;
+        writer.write_all(&self.height.to_le_bytes())?;
+        writer.seek(SeekFrom::Start(end))?;
+        Ok(writer)
+    }
+
+    fn start(&mut self) -> io::Result<()> {
+        let palette: Vec<u8> = Colour::ALL.iter().flat_map(|colour| colour.rgb()).collect();
+        let writer = self.writer.take().expect("GIF should only be started once.");
+        let mut encoder = gif::Encoder::new(writer, self.width, self.height, &palette).map_err(io::Error::other)?;
+        encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+        self.encoder = Some(encoder);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts down, drawing the count as a row of `*`.
+    struct Countdown(usize);
+
+    impl Visualize for Countdown {
+        fn frame(&self) -> Frame {
+            let mut frame = Frame::new(&format!("{} left", self.0));
+            frame.push_row(std::iter::repeat_n('*', self.0));
+            frame
+        }
+
+        fn step(&mut self) -> bool {
+            if self.0 == 0 { return false; }
+            self.0 -= 1;
+            true
+        }
+    }
+
+    #[test]
+    fn text_animation_is_correct() {
+        let filename = std::env::temp_dir().join("visualize_text_animation_is_correct.txt");
+        let filename = filename.to_str().unwrap();
+        let animation = Animation {
+            target: Target::from_filename(Some(filename)),
+            every: 2,
+            delay: Duration::ZERO,
+        };
+        assert_eq!(animation.run(&mut Countdown(3)).unwrap(), 3);
+        assert_eq!(
+            std::fs::read_to_string(filename).unwrap(),
+            "3 left\n***\n\n1 left\n*\n\n0 left\n\n\n",
+        );
+    }
+
+    #[test]
+    fn ansi_colours_change_only_when_needed() {
+        let mut frame = Frame::new("walls");
+        frame.push_row("##.#".chars());
+        assert_eq!(
+            frame.to_ansi(default_colour),
+            "walls\n\x1b[97m##\x1b[90m.\x1b[97m#\x1b[0m\n",
+        );
+    }
+
+    #[test]
+    fn gif_is_written() {
+        let mut frames = vec![Frame::new("0"), Frame::new("1")];
+        frames[0].push_row("#.".chars());
+        frames[1].push_row("#.#".chars());
+        frames[1].push_row(".".chars());
+        let mut gif = GifWriter::new(io::Cursor::new(Vec::new()), Duration::from_millis(100));
+        for frame in frames.iter() {
+            gif.write_frame(frame, &default_colour).unwrap();
+        }
+        let gif = gif.finish().unwrap().into_inner();
+        assert_eq!(&gif[..6], b"GIF89a");
+        // Logical screen size: 3x2 cells of 8x8 pixels.
+        assert_eq!(&gif[6..10], &[24, 0, 16, 0]);
+        let mut decoder = gif::DecodeOptions::new().read_info(&gif[..]).unwrap();
+        let mut sizes = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            sizes.push((frame.width, frame.height));
+        }
+        assert_eq!(sizes, vec![(16, 8), (24, 16)]);
+    }
+}
@@ -1,5 +1,10 @@
 //! Solutions to the [2015](https://adventofcode.com/2015) puzzles.
 
+pub mod day_01;
+pub mod day_02;
+pub mod day_03;
+pub mod day_04;
+pub mod day_05;
 pub mod day_06;
 pub mod day_07;
 pub mod day_08;
@@ -12,4 +17,11 @@ pub mod day_14;
 pub mod day_15;
 pub mod day_16;
 pub mod day_17;
-pub mod day_18;
\ No newline at end of file
+pub mod day_18;
+pub mod day_19;
+pub mod day_20;
+pub mod day_21;
+pub mod day_22;
+pub mod day_23;
+pub mod day_24;
+pub mod day_25;
\ No newline at end of file
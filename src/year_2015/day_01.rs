@@ -0,0 +1,103 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 1 };
+
+mod utils {
+    /// The change in floor for each instruction.
+    pub fn moves(instructions: &str) -> impl Iterator<Item = i32> + '_ {
+        instructions.chars().map(|ch| match ch {
+            '(' => 1,
+            ')' => -1,
+            _ => panic!("Unrecognized instruction."),
+        })
+    }
+}
+
+pub mod part_one {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::I32(utils::moves(&io_utils::file_to_string(filename)).sum())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::I32(0); "example_1")]
+        #[test_case(2, Answer::I32(0); "example_2")]
+        #[test_case(3, Answer::I32(3); "example_3")]
+        #[test_case(4, Answer::I32(3); "example_4")]
+        #[test_case(5, Answer::I32(3); "example_5")]
+        #[test_case(6, Answer::I32(-1); "example_6")]
+        #[test_case(7, Answer::I32(-1); "example_7")]
+        #[test_case(8, Answer::I32(-3); "example_8")]
+        #[test_case(9, Answer::I32(-3); "example_9")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let position = utils::moves(&io_utils::file_to_string(filename))
+                .scan(0, |floor, change| {
+                    *floor += change;
+                    Some(*floor)
+                })
+                .position(|floor| floor == -1)
+                .expect("Santa should enter the basement.");
+            Answer::Usize(position + 1)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(6, Answer::Usize(3); "example_6")]
+        #[test_case(7, Answer::Usize(1); "example_7")]
+        #[test_case(8, Answer::Usize(1); "example_8")]
+        #[test_case(9, Answer::Usize(1); "example_9")]
+        #[test_case(10, Answer::Usize(1); "example_10")]
+        #[test_case(11, Answer::Usize(5); "example_11")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 2 };
+
+mod utils {
+    use crate::utils::io_utils;
+
+    /// A present's dimensions, smallest first.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Present {
+        dimensions: [u32; 3],
+    }
+
+    impl Present {
+        fn from_str(input: &str) -> Self {
+            let mut dimensions: [u32; 3] = input.split('x')
+                .map(|dimension| dimension.parse().unwrap())
+                .collect::<Vec<u32>>()
+                .try_into()
+                .expect("Present should have three dimensions.");
+            dimensions.sort();
+            Self { dimensions }
+        }
+
+        pub fn wrapping_paper(&self) -> u32 {
+            let [l, w, h] = self.dimensions;
+            2 * (l * w + w * h + h * l) + l * w
+        }
+
+        pub fn ribbon(&self) -> u32 {
+            let [l, w, h] = self.dimensions;
+            2 * (l + w) + l * w * h
+        }
+    }
+
+    pub fn presents(filename: &str) -> impl Iterator<Item = Present> {
+        io_utils::file_to_lines(filename).map(|line| Present::from_str(&line))
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::U32(utils::presents(filename).map(|present| present.wrapping_paper()).sum())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U32(58); "example_1")]
+        #[test_case(2, Answer::U32(43); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::U32(utils::presents(filename).map(|present| present.ribbon()).sum())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U32(34); "example_1")]
+        #[test_case(2, Answer::U32(14); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 3 };
+
+mod utils {
+    use std::collections::HashSet;
+
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+    struct House {
+        x: i32,
+        y: i32,
+    }
+
+    /// The houses visited by `num_santas` taking turns to follow the
+    /// directions, all starting at the same house.
+    pub fn houses_visited(directions: &str, num_santas: usize) -> usize {
+        let mut santas = vec![House::default(); num_santas];
+        let mut visited: HashSet<House> = HashSet::from([House::default()]);
+        for (idx, ch) in directions.chars().enumerate() {
+            let santa = &mut santas[idx % num_santas];
+            match ch {
+                '^' => santa.y += 1,
+                'v' => santa.y -= 1,
+                '>' => santa.x += 1,
+                '<' => santa.x -= 1,
+                _ => panic!("Unrecognized direction."),
+            }
+            visited.insert(*santa);
+        }
+        visited.len()
+    }
+}
+
+pub mod part_one {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::Usize(utils::houses_visited(&io_utils::file_to_string(filename), 1))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(2); "example_1")]
+        #[test_case(2, Answer::Usize(4); "example_2")]
+        #[test_case(3, Answer::Usize(2); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            // Santa and Robo-Santa
+            Answer::Usize(utils::houses_visited(&io_utils::file_to_string(filename), 2))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(4, Answer::Usize(3); "example_4")]
+        #[test_case(2, Answer::Usize(3); "example_2")]
+        #[test_case(3, Answer::Usize(11); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
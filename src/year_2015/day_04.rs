@@ -0,0 +1,71 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 4 };
+
+mod utils {
+    use md5::{Md5, Digest};
+
+    /// The lowest positive number that, appended to the secret key, gives an
+    /// MD5 hash starting with `zeroes` zeroes in hexadecimal.
+    pub fn lowest_number(secret_key: &str, zeroes: usize) -> u32 {
+        (1..).find(|number| {
+            let mut hasher = Md5::new();
+            hasher.update(format!("{}{}", secret_key, number).as_bytes());
+            let result = hasher.finalize();
+            result[..zeroes / 2].iter().all(|byte| *byte == 0)
+                && (zeroes.is_multiple_of(2) || result[zeroes / 2] < 0x10)
+        }).unwrap()
+    }
+}
+
+pub mod part_one {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::U32(utils::lowest_number(io_utils::file_to_string(filename).trim(), 5))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U32(609_043); "example_1")]
+        #[test_case(2, Answer::U32(1_048_970); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::U32(utils::lowest_number(io_utils::file_to_string(filename).trim(), 6))
+        }
+    }
+}
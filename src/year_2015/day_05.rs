@@ -0,0 +1,85 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 5 };
+
+pub mod part_one {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    fn is_nice(string: &str) -> bool {
+        let vowels = string.chars().filter(|ch| "aeiou".contains(*ch)).count();
+        let chars: Vec<char> = string.chars().collect();
+        let has_double = chars.windows(2).any(|pair| pair[0] == pair[1]);
+        let has_forbidden = ["ab", "cd", "pq", "xy"].iter().any(|forbidden| string.contains(forbidden));
+        vowels >= 3 && has_double && !has_forbidden
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::Usize(io_utils::file_to_lines(filename).filter(|line| is_nice(line)).count())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(2); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    fn is_nice(string: &str) -> bool {
+        let chars: Vec<char> = string.chars().collect();
+        // A pair appearing twice without overlapping
+        let has_repeated_pair = (0..chars.len().saturating_sub(1))
+            .any(|idx| string[idx + 2..].contains(&string[idx..idx + 2]));
+        let has_sandwich = chars.windows(3).any(|triple| triple[0] == triple[2]);
+        has_repeated_pair && has_sandwich
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::Usize(io_utils::file_to_lines(filename).filter(|line| is_nice(line)).count())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(2, Answer::Usize(2); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
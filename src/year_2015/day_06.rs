@@ -4,10 +4,12 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2015, day: 6 };
 
 mod utils {
+    use std::str::FromStr;
+
     use itertools::iproduct;
-    use regex::Regex;
 
-    use crate::utils::io_utils;
+    use crate::scan;
+    use crate::utils::parse::{self, ParseError};
 
     pub const DIMENSIONS: usize = 1_000;
 
@@ -31,30 +33,56 @@ mod utils {
         }
     }
 
+    enum Operation {
+        TurnOn,
+        Toggle,
+        TurnOff,
+    }
+
+    impl FromStr for Operation {
+        type Err = String;
+
+        fn from_str(op: &str) -> Result<Self, Self::Err> {
+            match op {
+                "turn on" => Ok(Self::TurnOn),
+                "toggle" => Ok(Self::Toggle),
+                "turn off" => Ok(Self::TurnOff),
+                _ => Err(String::from("unrecognized operation")),
+            }
+        }
+    }
+
+    struct Instruction {
+        op: Operation,
+        rectangle: Rectangle,
+    }
+
+    impl FromStr for Instruction {
+        type Err = ParseError;
+
+        fn from_str(line: &str) -> Result<Self, Self::Err> {
+            let (op, x_min, y_min, x_max, y_max) = scan!(
+                line,
+                "{..} {},{} through {},{}",
+                Operation, usize, usize, usize, usize
+            )?;
+            let top_left = Point { x: x_min, y: y_min };
+            let bottom_right = Point { x: x_max, y: y_max };
+            Ok(Self { op, rectangle: Rectangle { top_left, bottom_right } })
+        }
+    }
+
     pub trait InteractiveLightGrid {
         fn turn_on(&mut self, rectangle: &Rectangle);
         fn toggle(&mut self, rectangle: &Rectangle);
         fn turn_off(&mut self, rectangle: &Rectangle);
 
         fn parse_input_file(&mut self, filename: &str) {
-            let re = Regex::new(
-                r"(?<op>(turn on)|(toggle)|(turn off)) (?<x_min>\d+)\,(?<y_min>\d+) through (?<x_max>\d+)\,(?<y_max>\d+)"
-            ).unwrap();
-            for line in io_utils::file_to_lines(filename) {
-                let caps = re.captures(&line).unwrap();
-                let op = caps.name("op").unwrap().as_str();
-                let x_min = caps.name("x_min").unwrap().as_str().parse().unwrap();
-                let y_min = caps.name("y_min").unwrap().as_str().parse().unwrap();
-                let top_left = Point { x: x_min, y: y_min };
-                let x_max = caps.name("x_max").unwrap().as_str().parse().unwrap();
-                let y_max = caps.name("y_max").unwrap().as_str().parse().unwrap();
-                let bottom_right = Point { x: x_max, y: y_max };
-                let rectangle = Rectangle { top_left, bottom_right };
-                match op {
-                    "turn on" => self.turn_on(&rectangle),
-                    "toggle" => self.toggle(&rectangle),
-                    "turn off" => self.turn_off(&rectangle),
-                    _ => panic!("Unrecognized operation."),
+            for instruction in parse::file_to_parsed_lines::<Instruction>(filename) {
+                match instruction.op {
+                    Operation::TurnOn => self.turn_on(&instruction.rectangle),
+                    Operation::Toggle => self.toggle(&instruction.rectangle),
+                    Operation::TurnOff => self.turn_off(&instruction.rectangle),
                 }
             }
         }
@@ -62,6 +90,7 @@ mod utils {
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::{InteractiveLightGrid, Rectangle, DIMENSIONS};
@@ -105,21 +134,26 @@ pub mod part_one {
         }
     }
 
-    impl Default for LightGrid {
-        fn default() -> Self {
-            Self::new(DIMENSIONS)
-        }
+    #[derive(Debug)]
+    pub struct Soln {
+        dimensions: usize,
     }
 
-    #[derive(Debug, Default)]
-    pub struct Soln {
-        light_grid: LightGrid,
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { dimensions: DIMENSIONS }
+        }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.light_grid.parse_input_file(filename);
-            Answer::Usize(self.light_grid.num_on())
+            let mut light_grid = LightGrid::new(self.dimensions);
+            light_grid.parse_input_file(filename);
+            Answer::Usize(light_grid.num_on())
+        }
+
+        solution_params! {
+            dimensions: "Width and height of the grid of lights",
         }
     }
 
@@ -143,6 +177,7 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::{InteractiveLightGrid, Rectangle, DIMENSIONS};
@@ -190,21 +225,26 @@ pub mod part_two {
         }
     }
 
-    impl Default for LightGrid {
-        fn default() -> Self {
-            Self::new(DIMENSIONS)
-        }
+    #[derive(Debug)]
+    pub struct Soln {
+        dimensions: usize,
     }
 
-    #[derive(Debug, Default)]
-    pub struct Soln {
-        light_grid: LightGrid,
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { dimensions: DIMENSIONS }
+        }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.light_grid.parse_input_file(filename);
-            Answer::Usize(self.light_grid.num_on())
+            let mut light_grid = LightGrid::new(self.dimensions);
+            light_grid.parse_input_file(filename);
+            Answer::Usize(light_grid.num_on())
+        }
+
+        solution_params! {
+            dimensions: "Width and height of the grid of lights",
         }
     }
 
@@ -3,8 +3,10 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2015, day: 7 };
 
+use std::str::FromStr;
+
 mod utils {
-    use std::collections::HashMap;
+    use std::{collections::{HashMap, HashSet}, fmt};
 
     use regex::Regex;
 
@@ -51,117 +53,225 @@ mod utils {
      *  - RSHIFT (two inputs: u16 or wire and u16)
      *  - NOT (one input: u16 or wire)
      */
-    // Input is either the output of a gate or a u16 directly. We could just do a wire gate.
-    // TODO: figure out the ownership complexities
-    // TODO: figure out if we can memoize
+    impl Gate {
+        fn input_wires(&self) -> impl Iterator<Item = &String> {
+            self.inputs.iter()
+                .filter_map(|input| match input {
+                    GateInput::Wire(wire) => Some(wire),
+                    GateInput::Value(_) => None,
+                })
+        }
+
+        /// Computes the gate's output given the values of the wires computed
+        /// so far (which must include all of the gate's input wires).
+        fn output(&self, values: &HashMap<String, u16>) -> u16 {
+            let input = |index: usize| match &self.inputs[index] {
+                GateInput::Wire(wire) => values[wire],
+                GateInput::Value(val) => *val,
+            };
+            match self.gate_type {
+                GateType::Wire => input(0),
+                GateType::And => input(0) & input(1),
+                GateType::Or => input(0) | input(1),
+                GateType::LShift => input(0) << input(1),
+                GateType::RShift => input(0) >> input(1),
+                GateType::Not => !input(0),
+            }
+        }
+    }
 
+    /// Why a circuit can't be evaluated.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub enum CircuitError {
+        /// A wire is used (by the gate driving `used_by`, if any) but no gate
+        /// drives it.
+        UndefinedWire { wire: String, used_by: Option<String> },
+        /// The wires form a loop, listed in signal order starting and ending
+        /// with the same wire.
+        Cycle(Vec<String>),
+    }
+
+    impl fmt::Display for CircuitError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::UndefinedWire { wire, used_by: Some(used_by) } => write!(f, "wire `{wire}` (used by `{used_by}`) is never defined"),
+                Self::UndefinedWire { wire, used_by: None } => write!(f, "wire `{wire}` is never defined"),
+                Self::Cycle(wires) => write!(f, "wires form a cycle: {}", wires.join(" -> ")),
+            }
+        }
+    }
+
+    impl std::error::Error for CircuitError {}
+
+    /// The gates driving each wire, along with an order in which to evaluate
+    /// the wires such that each gate's inputs come before it.
     #[derive(Debug, Default)]
     pub struct Circuit {
         wires: HashMap<String, Gate>,
-        wire_values: HashMap<String, u16>,
+        order: Vec<String>,
     }
 
     impl Circuit {
-        pub fn parse_input_file(&mut self, filename: &str) {
-            let wire_re = Regex::new(r"(?<input_0>\d+|[a-z]+) -> (?<output>[a-z]+)").unwrap();
-            let not_re = Regex::new(r"NOT (?<input_0>\d+|[a-z]+) -> (?<output>[a-z]+)").unwrap();
-            let two_operand_re = Regex::new(r"(?<input_0>\d+|[a-z]+) (?<op>(AND)|(OR)|(LSHIFT)|(RSHIFT)) (?<input_1>\d+|[a-z]+) -> (?<output>[a-z]+)").unwrap();
-            for line in io_utils::file_to_lines(filename) {
-                if let Some(captures) = two_operand_re.captures(&line) {
-                    let input_0 = captures.name("input_0").unwrap().as_str();
-                    let input_0 = GateInput::from_str(input_0);
-                    let input_1 = captures.name("input_1").unwrap().as_str();
-                    let input_1 = GateInput::from_str(input_1);
-                    let op = captures.name("op").unwrap().as_str();
-                    let output = captures.name("output").unwrap().as_str();
-                    let gate_type = match op {
+        pub fn from_file(filename: &str) -> Result<Self, CircuitError> {
+            Self::from_lines(io_utils::file_to_lines(filename))
+        }
+
+        pub fn from_lines<L>(lines: impl Iterator<Item = L>) -> Result<Self, CircuitError>
+        where L: AsRef<str>, {
+            let wire_re = Regex::new(r"^(?<input_0>\d+|[a-z]+) -> (?<output>[a-z]+)$").unwrap();
+            let not_re = Regex::new(r"^NOT (?<input_0>\d+|[a-z]+) -> (?<output>[a-z]+)$").unwrap();
+            let two_operand_re = Regex::new(r"^(?<input_0>\d+|[a-z]+) (?<op>(AND)|(OR)|(LSHIFT)|(RSHIFT)) (?<input_1>\d+|[a-z]+) -> (?<output>[a-z]+)$").unwrap();
+            let mut wires = HashMap::new();
+            for line in lines {
+                let line = line.as_ref();
+                let (output, gate) = if let Some(captures) = two_operand_re.captures(line) {
+                    let input_0 = GateInput::from_str(captures.name("input_0").unwrap().as_str());
+                    let input_1 = GateInput::from_str(captures.name("input_1").unwrap().as_str());
+                    let gate_type = match captures.name("op").unwrap().as_str() {
                         "AND" => GateType::And,
                         "OR" => GateType::Or,
                         "LSHIFT" => GateType::LShift,
                         "RSHIFT" => GateType::RShift,
                         _ => panic!("Unrecognized operation"),
                     };
-                    self.wires.insert(
-                        output.to_string(), 
-                        Gate { 
-                            gate_type,
-                            inputs: vec![input_0, input_1],
-                        },
-                    );
-                } else if let Some(captures) = not_re.captures(&line) {
-                    let input = captures.name("input_0").unwrap().as_str();
-                    let input = GateInput::from_str(input);
-                    let output = captures.name("output").unwrap().as_str();
-                    self.wires.insert(
-                        output.to_string(), 
-                        Gate { 
-                            gate_type: GateType::Not,
-                            inputs: vec![input],
+                    (captures.name("output").unwrap().as_str(), Gate { gate_type, inputs: vec![input_0, input_1] })
+                } else if let Some(captures) = not_re.captures(line) {
+                    let input = GateInput::from_str(captures.name("input_0").unwrap().as_str());
+                    (captures.name("output").unwrap().as_str(), Gate { gate_type: GateType::Not, inputs: vec![input] })
+                } else if let Some(captures) = wire_re.captures(line) {
+                    let input = GateInput::from_str(captures.name("input_0").unwrap().as_str());
+                    (captures.name("output").unwrap().as_str(), Gate { gate_type: GateType::Wire, inputs: vec![input] })
+                } else {
+                    panic!("Unrecognized line: {line}");
+                };
+                wires.insert(output.to_string(), gate);
+            }
+            let order = Self::topological_order(&wires)?;
+            Ok(Self { wires, order })
+        }
+
+        /// Orders the wires so every wire comes after the wires its gate
+        /// reads, using an iterative depth-first search (so deep circuits
+        /// can't overflow the stack). Reports the first undefined wire or
+        /// cycle found, visiting wires in name order.
+        fn topological_order(wires: &HashMap<String, Gate>) -> Result<Vec<String>, CircuitError> {
+            let mut names: Vec<&String> = wires.keys().collect();
+            names.sort();
+            for name in names.iter() {
+                for input in wires[*name].input_wires() {
+                    if !wires.contains_key(input) {
+                        return Err(CircuitError::UndefinedWire { wire: input.clone(), used_by: Some((*name).clone()) });
+                    }
+                }
+            }
+            let mut order: Vec<String> = Vec::with_capacity(wires.len());
+            let mut done: HashSet<&String> = HashSet::new();
+            for root in names {
+                if done.contains(root) { continue; }
+                // Each entry is a wire on the current path and how many of its
+                // inputs have been visited.
+                let mut path: Vec<(&String, usize)> = vec![(root, 0)];
+                while let Some((wire, next_input)) = path.last_mut() {
+                    let wire: &String = wire;
+                    match wires[wire].input_wires().nth(*next_input) {
+                        Some(input) => {
+                            *next_input += 1;
+                            if done.contains(input) { continue; }
+                            if let Some(start) = path.iter().position(|(on_path, _)| *on_path == input) {
+                                // Signals flow from each wire on the path to the one before it.
+                                let cycle = std::iter::once(input)
+                                    .chain(path[start..].iter().rev().map(|(on_path, _)| *on_path))
+                                    .cloned()
+                                    .collect();
+                                return Err(CircuitError::Cycle(cycle));
+                            }
+                            path.push((input, 0));
                         },
-                    );
-                } else if let Some(captures) = wire_re.captures(&line) {
-                    let input = captures.name("input_0").unwrap().as_str();
-                    let input = GateInput::from_str(input);
-                    let output = captures.name("output").unwrap().as_str();
-                    self.wires.insert(
-                        output.to_string(), 
-                        Gate { 
-                            gate_type: GateType::Wire,
-                            inputs: vec![input],
+                        None => {
+                            done.insert(wire);
+                            order.push(wire.clone());
+                            path.pop();
                         },
-                    );
-                } else {
-                    panic!("Unrecognized line");
+                    }
                 }
             }
+            Ok(order)
+        }
+
+        /// Returns a view of the circuit with no overrides.
+        pub fn view(&self) -> CircuitView<'_> {
+            CircuitView { circuit: self, parent: None, overrides: HashMap::new() }
         }
+    }
+
+    /// An immutable view of a circuit in which some wires may be overridden
+    /// to fixed values. Views are layered: a view created from another view
+    /// keeps the other's overrides unless it overrides the same wire, and
+    /// neither the circuit nor the other view is changed.
+    #[derive(Debug)]
+    pub struct CircuitView<'a> {
+        circuit: &'a Circuit,
+        parent: Option<&'a CircuitView<'a>>,
+        overrides: HashMap<String, u16>,
+    }
 
-        pub fn wire_value(&mut self, wire: &str) -> u16 {
-            if let Some(val) = self.wire_values.get(wire) {
-                return *val;
+    impl<'a> CircuitView<'a> {
+        /// Returns a new layer on top of this view in which `wire` is driven
+        /// directly by `val`, cutting it off from its gate.
+        pub fn with_override(&'a self, wire: &str, val: u16) -> Result<CircuitView<'a>, CircuitError> {
+            if !self.circuit.wires.contains_key(wire) {
+                return Err(CircuitError::UndefinedWire { wire: wire.to_string(), used_by: None });
             }
-            let gate = self.wires.get(wire).unwrap().clone();
-            let signal = match gate.gate_type {
-                GateType::Wire => {
-                    self.evaluate_gate_input(&gate, 0)
-                },
-                GateType::And => {
-                    self.evaluate_gate_input(&gate, 0) & self.evaluate_gate_input(&gate, 1)
-                },
-                GateType::Or => {
-                    self.evaluate_gate_input(&gate, 0) | self.evaluate_gate_input(&gate, 1)                    
-                },
-                GateType::LShift => {
-                    self.evaluate_gate_input(&gate, 0) << self.evaluate_gate_input(&gate, 1)
-                },
-                GateType::RShift => {
-                    self.evaluate_gate_input(&gate, 0) >> self.evaluate_gate_input(&gate, 1)                    
-                },
-                GateType::Not => {
-                    !self.evaluate_gate_input(&gate, 0)
-                },
-            };
-            self.wire_values.insert(wire.to_string(), signal);
-            signal
+            Ok(CircuitView {
+                circuit: self.circuit,
+                parent: Some(self),
+                overrides: HashMap::from([(wire.to_string(), val)]),
+            })
         }
 
-        fn evaluate_gate_input(&mut self, gate: &Gate, index: usize) -> u16 {
-            match gate.inputs.get(index).unwrap() {
-                GateInput::Wire(wire) => self.wire_value(wire),
-                GateInput::Value(val) => {
-                    *val
-                }
+        /// Returns the value `wire` is overridden to in the nearest layer that
+        /// overrides it, if any.
+        fn override_value(&self, wire: &str) -> Option<u16> {
+            self.overrides.get(wire)
+                .copied()
+                .or_else(|| self.parent.and_then(|parent| parent.override_value(wire)))
+        }
+
+        /// Evaluates every wire. Overriding a wire only removes edges from the
+        /// circuit, so the circuit's evaluation order still applies.
+        pub fn evaluate(&self) -> Signals {
+            let mut values: HashMap<String, u16> = HashMap::with_capacity(self.circuit.order.len());
+            for wire in self.circuit.order.iter() {
+                let val = match self.override_value(wire) {
+                    Some(val) => val,
+                    None => self.circuit.wires[wire].output(&values),
+                };
+                values.insert(wire.clone(), val);
             }
+            Signals { values }
         }
+    }
+
+    /// The value on every wire of an evaluated circuit.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct Signals {
+        values: HashMap<String, u16>,
+    }
 
-        pub fn set_wire(&mut self, wire: &str, val: u16) {
-            let gate = self.wires.get_mut(wire).unwrap();
-            assert_eq!(gate.gate_type, GateType::Wire);
-            gate.inputs[0] = GateInput::Value(val);
+    impl Signals {
+        pub fn get(&self, wire: &str) -> Option<u16> {
+            self.values.get(wire).copied()
         }
 
-        pub fn reset_all_wires(&mut self) {
-            self.wire_values = HashMap::new();
+        /// Returns the wires whose values differ from those in `other`,
+        /// sorted by name.
+        pub fn changed_wires(&self, other: &Signals) -> Vec<String> {
+            let mut changed: Vec<String> = self.values.iter()
+                .filter(|(wire, val)| other.values.get(*wire) != Some(val))
+                .map(|(wire, _val)| wire.clone())
+                .collect();
+            changed.sort();
+            changed
         }
     }
 
@@ -170,7 +280,7 @@ mod utils {
         use io_utils::{InputFileType, input_filename};
         use test_case::test_case;
         use super::*;
-        use super::super::DAY;
+        use super::super::{wires_changed_by_override, Override, DAY};
 
         #[test_case("d", 72; "d")]
         #[test_case("e", 507; "e")]
@@ -181,27 +291,123 @@ mod utils {
         #[test_case("x", 123; "x")]
         #[test_case("y", 456; "y")]
         fn examples_are_correct(wire: &str, answer: u16) {
-            let mut circuit = Circuit::default();
-            circuit.parse_input_file(&input_filename(&DAY, InputFileType::Example(1)));
-            assert_eq!(answer, circuit.wire_value(wire));
+            let circuit = Circuit::from_file(&input_filename(&DAY, InputFileType::Example(1))).unwrap();
+            assert_eq!(Some(answer), circuit.view().evaluate().get(wire));
+        }
+
+        #[test]
+        fn overrides_are_layered() {
+            let circuit = Circuit::from_file(&input_filename(&DAY, InputFileType::Example(1))).unwrap();
+            let base = circuit.view();
+            let x_overridden = base.with_override("x", 0).unwrap();
+            let both_overridden = x_overridden.with_override("y", 0).unwrap();
+            assert_eq!(x_overridden.evaluate().get("d"), Some(0));
+            assert_eq!(x_overridden.evaluate().get("e"), Some(456));
+            assert_eq!(both_overridden.evaluate().get("e"), Some(0));
+            assert_eq!(
+                base.evaluate().changed_wires(&x_overridden.evaluate()),
+                vec!["d", "e", "f", "h", "x"],
+            );
+            assert_eq!(base.evaluate().get("x"), Some(123));
+            assert_eq!(
+                base.with_override("z", 1).unwrap_err(),
+                CircuitError::UndefinedWire { wire: String::from("z"), used_by: None },
+            );
+        }
+
+        #[test]
+        fn override_report_is_correct() {
+            let filename = input_filename(&DAY, InputFileType::Example(1));
+            assert_eq!(
+                wires_changed_by_override(&filename, &"x=0".parse().unwrap()),
+                "d: 72 -> 0\ne: 507 -> 456\nf: 492 -> 0\nh: 65412 -> 65535\nx: 123 -> 0",
+            );
+            assert_eq!("y".parse::<Override>().unwrap(), Override { wire: String::from("y"), val: None });
+            assert!("y=z".parse::<Override>().is_err());
+        }
+
+        #[test]
+        fn undefined_wires_are_reported() {
+            assert_eq!(
+                Circuit::from_lines(["1 -> a", "a AND q -> b"].into_iter()).unwrap_err(),
+                CircuitError::UndefinedWire { wire: String::from("q"), used_by: Some(String::from("b")) },
+            );
+        }
+
+        #[test]
+        fn cycles_are_reported() {
+            let err = Circuit::from_lines(["c -> a", "a OR d -> b", "b -> c", "1 -> d"].into_iter()).unwrap_err();
+            assert_eq!(err, CircuitError::Cycle(vec![
+                String::from("a"),
+                String::from("b"),
+                String::from("c"),
+                String::from("a"),
+            ]));
+            assert_eq!(err.to_string(), "wires form a cycle: a -> b -> c -> a");
         }
     }
 }
 
+/// A wire to override, and the signal to override it with. Without a
+/// signal, the wire gets the signal on `a`, as in part two.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Override {
+    wire: String,
+    val: Option<u16>,
+}
+
+impl Default for Override {
+    fn default() -> Self {
+        Self { wire: String::from("b"), val: None }
+    }
+}
+
+impl FromStr for Override {
+    type Err = String;
+
+    /// Parses `WIRE` or `WIRE=SIGNAL`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((wire, val)) => Ok(Self {
+                wire: wire.to_string(),
+                val: Some(val.parse().map_err(|err| format!("invalid signal `{val}`: {err}"))?),
+            }),
+            None => Ok(Self { wire: s.to_string(), val: None }),
+        }
+    }
+}
+
+/// Returns the wires whose signals change when a wire is overridden, one
+/// `wire: before -> after` line each, e.g., to see what part two's override
+/// of `b` affects.
+pub fn wires_changed_by_override(filename: &str, over: &Override) -> String {
+    let circuit = utils::Circuit::from_file(filename)
+        .unwrap_or_else(|err| panic!("Circuit should be valid: {err}"));
+    let base = circuit.view();
+    let before = base.evaluate();
+    let val = over.val.unwrap_or_else(|| before.get("a").expect("Wire `a` should exist."));
+    let overridden = base.with_override(&over.wire, val)
+        .unwrap_or_else(|err| panic!("Wire `{}` should be overridable: {err}", over.wire));
+    let after = overridden.evaluate();
+    before.changed_wires(&after).iter()
+        .map(|wire| format!("{wire}: {} -> {}", before.get(wire).unwrap(), after.get(wire).unwrap()))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 pub mod part_one {
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Circuit;
 
     #[derive(Debug, Default)]
-    pub struct Soln {
-        circuit: Circuit,
-    }
+    pub struct Soln {}
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.circuit.parse_input_file(filename);
-            Answer::U16(self.circuit.wire_value("a"))
+            let circuit = Circuit::from_file(filename)
+                .unwrap_or_else(|err| panic!("Circuit should be valid: {err}"));
+            Answer::U16(circuit.view().evaluate().get("a").expect("Wire `a` should exist."))
         }
     }
 }
@@ -212,17 +418,17 @@ pub mod part_two {
     use super::utils::Circuit;
 
     #[derive(Debug, Default)]
-    pub struct Soln {
-        circuit: Circuit,
-    }
+    pub struct Soln {}
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.circuit.parse_input_file(filename);
-            let a = self.circuit.wire_value("a");
-            self.circuit.set_wire("b", a);
-            self.circuit.reset_all_wires();
-            Answer::U16(self.circuit.wire_value("a"))
+            let circuit = Circuit::from_file(filename)
+                .unwrap_or_else(|err| panic!("Circuit should be valid: {err}"));
+            let base = circuit.view();
+            let a = base.evaluate().get("a").expect("Wire `a` should exist.");
+            let overridden = base.with_override("b", a)
+                .unwrap_or_else(|err| panic!("Wire `b` should be overridable: {err}"));
+            Answer::U16(overridden.evaluate().get("a").unwrap())
         }
     }
 }
\ No newline at end of file
@@ -4,6 +4,9 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2015, day: 10 };
 
 mod utils {
+    use std::collections::HashMap;
+    use std::fmt::{self, Write};
+
     pub fn look_and_say(input: &str) -> String {
         let mut res = String::new();
         let mut chars = input.chars();
@@ -14,7 +17,7 @@ mod utils {
                 if cur == ch {
                     cur_count += 1;
                 } else {
-                    res.push_str(&format!("{}{}", cur_count, cur));
+                    write!(res, "{}{}", cur_count, cur).unwrap();
                     cur_ch = Some(ch);
                     cur_count = 1;
                 }
@@ -23,10 +26,176 @@ mod utils {
                 cur_count += 1;
             }
         }
-        res.push_str(&format!("{}{}", cur_count, cur_ch.unwrap()));
+        write!(res, "{}{}", cur_count, cur_ch.unwrap()).unwrap();
         res
     }
 
+    /// How many rounds to follow the start of a string when checking a split.
+    /// The start of a string evolves as its first atom does, and the first
+    /// atom of each round is decided by the one before, so within twice the
+    /// number of atoms (92 common elements, plus a few transuranic ones and
+    /// atoms of the seed itself) the first digit has taken every value it
+    /// ever will.
+    const SPLIT_CHECK_ROUNDS: usize = 200;
+    /// How much of the start of a string to follow when checking a split.
+    const SPLIT_CHECK_PREFIX_LEN: usize = 100;
+
+    /// Whether a string whose left part ends in `left_end` splits before
+    /// `right` for good, i.e., whether the two parts evolve independently
+    /// forever after. The left part always ends in the same digit, so they
+    /// do unless `right` ever starts with that digit.
+    ///
+    /// The end of `right` must itself be a split for good (or the end of the
+    /// whole string).
+    fn splits(left_end: char, right: &str) -> bool {
+        let mut prefix = String::from(right);
+        let mut truncated = false;
+        for _ in 0..SPLIT_CHECK_ROUNDS {
+            if prefix.starts_with(left_end) { return false; }
+            if truncated {
+                // The last run may continue past the prefix, so only the runs
+                // before it can be said for certain.
+                let last = prefix.chars().last().unwrap();
+                prefix.truncate(prefix.trim_end_matches(last).len());
+            }
+            prefix = look_and_say(&prefix);
+            truncated |= prefix.len() > SPLIT_CHECK_PREFIX_LEN;
+            prefix.truncate(SPLIT_CHECK_PREFIX_LEN);
+        }
+        true
+    }
+
+    /// Splits a string into atoms: the smallest pieces that evolve
+    /// independently of each other forever after. For strings at least two
+    /// rounds old, these are Conway's elements.
+    pub fn split(string: &str) -> Vec<&str> {
+        let bytes = string.as_bytes();
+        let mut atoms = Vec::new();
+        let mut start = 0;
+        for idx in 1..string.len() {
+            if bytes[idx - 1] != bytes[idx] && splits(bytes[idx - 1] as char, &string[idx..]) {
+                atoms.push(&string[start..idx]);
+                start = idx;
+            }
+        }
+        atoms.push(&string[start..]);
+        atoms
+    }
+
+    /// A count of any size, stored in base 10^9 digits with the least
+    /// significant first, since the sequence's length grows by about 30% each
+    /// round.
+    #[derive(Debug, Default, PartialEq, Eq, Clone)]
+    pub struct Count(Vec<u32>);
+
+    impl Count {
+        const BASE: u64 = 1_000_000_000;
+
+        pub fn one() -> Self {
+            Self(vec![1])
+        }
+
+        /// Adds `other * factor` to the count.
+        pub fn add_scaled(&mut self, other: &Count, factor: u32) {
+            let mut carry = 0;
+            for idx in 0..self.0.len().max(other.0.len()) {
+                if idx == self.0.len() { self.0.push(0); }
+                let sum = self.0[idx] as u64
+                    + other.0.get(idx).copied().unwrap_or(0) as u64 * factor as u64
+                    + carry;
+                self.0[idx] = (sum % Self::BASE) as u32;
+                carry = sum / Self::BASE;
+            }
+            while carry > 0 {
+                self.0.push((carry % Self::BASE) as u32);
+                carry /= Self::BASE;
+            }
+        }
+
+        pub fn to_usize(&self) -> Option<usize> {
+            self.0.iter()
+                .rev()
+                .try_fold(0usize, |value, digit| value.checked_mul(Self::BASE as usize)?.checked_add(*digit as usize))
+        }
+    }
+
+    impl fmt::Display for Count {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.0.split_last() {
+                None => write!(f, "0"),
+                Some((most_significant, rest)) => {
+                    write!(f, "{most_significant}")?;
+                    rest.iter().rev().try_for_each(|digit| write!(f, "{digit:09}"))
+                },
+            }
+        }
+    }
+
+    /// A look-and-say sequence tracked as counts of the atoms it's made of,
+    /// following Conway's audioactive decay: each atom's next round is the
+    /// same list of atoms wherever it appears, so a round only needs one step
+    /// per kind of atom, however long the sequence gets.
+    #[derive(Debug, Default)]
+    pub struct AudioactiveDecay {
+        atoms: Vec<String>,
+        ids: HashMap<String, usize>,
+        decays: Vec<Vec<usize>>,
+        counts: Vec<Count>,
+    }
+
+    impl AudioactiveDecay {
+        pub fn new(seed: &str) -> Self {
+            let mut decay = Self::default();
+            let ids: Vec<usize> = split(seed).into_iter()
+                .map(|atom| decay.id(atom))
+                .collect();
+            decay.counts = vec![Count::default(); decay.atoms.len()];
+            ids.into_iter().for_each(|id| decay.counts[id].add_scaled(&Count::one(), 1));
+            decay
+        }
+
+        /// The atom's id, adding it (and everything it decays into) if it's
+        /// new.
+        fn id(&mut self, atom: &str) -> usize {
+            if let Some(id) = self.ids.get(atom) {
+                return *id;
+            }
+            let id = self.atoms.len();
+            self.atoms.push(String::from(atom));
+            self.ids.insert(String::from(atom), id);
+            self.decays.push(Vec::new());
+            let decay = split(&look_and_say(atom)).into_iter()
+                .map(|product| self.id(product))
+                .collect();
+            self.decays[id] = decay;
+            id
+        }
+
+        /// Plays one round of look-and-say.
+        pub fn step(&mut self) {
+            let mut counts = vec![Count::default(); self.atoms.len()];
+            for (id, count) in self.counts.iter().enumerate() {
+                self.decays[id].iter().for_each(|product| counts[*product].add_scaled(count, 1));
+            }
+            self.counts = counts;
+        }
+
+        /// The length of the sequence.
+        pub fn len(&self) -> Count {
+            let mut len = Count::default();
+            self.counts.iter()
+                .zip(self.atoms.iter())
+                .for_each(|(count, atom)| len.add_scaled(count, atom.len() as u32));
+            len
+        }
+
+        /// The number of different atoms the sequence can ever contain.
+        #[cfg(test)]
+        pub fn num_atoms(&self) -> usize {
+            self.atoms.len()
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use test_case::test_case;
@@ -45,6 +214,42 @@ mod utils {
                 expanded,
             );
         }
+
+        #[test]
+        fn helium_decays_into_elements() {
+            assert_eq!(
+                split(&look_and_say("13112221133211322112211213322112")),
+                vec!["11132", "13", "22", "12", "312211322212221121123222112"],
+            );
+        }
+
+        #[test]
+        fn all_elements_are_found() {
+            // Uranium decays through every other common element.
+            assert_eq!(AudioactiveDecay::new("3").num_atoms(), 92);
+        }
+
+        #[test_case(1; "example_1")]
+        #[test_case(2; "example_2")]
+        #[test_case(5; "example_5")]
+        fn lengths_match_strings(example_key: u8) {
+            let mut string = io_utils::file_to_string(&io_utils::input_filename(&DAY, io_utils::InputFileType::Example(example_key)));
+            let mut decay = AudioactiveDecay::new(&string);
+            for _ in 0..30 {
+                assert_eq!(decay.len().to_usize(), Some(string.len()));
+                string = look_and_say(&string);
+                decay.step();
+            }
+        }
+
+        #[test]
+        fn counts_are_displayed() {
+            let mut count = Count::one();
+            count.add_scaled(&Count(vec![999_999_999, 7]), 3);
+            assert_eq!(count.to_string(), "23999999998");
+            assert_eq!(count.to_usize(), Some(23_999_999_998));
+            assert_eq!(Count::default().to_string(), "0");
+        }
     }    
 }
 
@@ -66,4 +271,60 @@ pub mod part_one {
             Answer::Usize(res.len())
         }
     }
-}
\ No newline at end of file
+}
+
+pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils::AudioactiveDecay;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        rounds: usize,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { rounds: 50 }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let mut decay = AudioactiveDecay::new(&io_utils::file_to_string(filename));
+            for _ in 0..self.rounds {
+                decay.step();
+            }
+            let len = decay.len();
+            match len.to_usize() {
+                Some(len) => Answer::Usize(len),
+                None => Answer::String(len.to_string()),
+            }
+        }
+
+        solution_params! {
+            rounds: "Number of rounds of look-and-say to play",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, 5, Answer::Usize(6); "example_1_5")]
+        #[test_case(1, 50, Answer::Usize(1_166_642); "example_1_50")]
+        fn examples_are_correct(example_key: u8, rounds: usize, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("rounds", rounds.to_string())],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
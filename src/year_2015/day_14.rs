@@ -80,6 +80,7 @@ mod utils {
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::ReindeerRace;
@@ -90,26 +91,24 @@ pub mod part_one {
         reindeer_race: ReindeerRace,
     }
 
-    impl Soln {
-        fn with_time(time: usize) -> Self {
+    impl Default for Soln {
+        fn default() -> Self {
             Self {
-                time,
+                time: 2_503,
                 reindeer_race: ReindeerRace::default(),
             }
         }
     }
 
-    impl Default for Soln {
-        fn default() -> Self {
-            Self::with_time(2_503)
-        }
-    }
-
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.reindeer_race.parse_input_file(filename);
             Answer::Usize(self.reindeer_race.winner_distance(self.time))
         }
+
+        solution_params! {
+            time: "Seconds the race lasts",
+        }
     }
 
     #[cfg(test)]
@@ -121,8 +120,9 @@ pub mod part_one {
 
         #[test_case(1, Answer::Usize(1_120); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_time(1_000),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("time", "1000")],
                 example_key,
                 answer,
                 &DAY,
@@ -132,6 +132,7 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::ReindeerRace;
@@ -142,26 +143,24 @@ pub mod part_two {
         reindeer_race: ReindeerRace,
     }
 
-    impl Soln {
-        fn with_time(time: usize) -> Self {
+    impl Default for Soln {
+        fn default() -> Self {
             Self {
-                time,
+                time: 2_503,
                 reindeer_race: ReindeerRace::default(),
             }
         }
     }
 
-    impl Default for Soln {
-        fn default() -> Self {
-            Self::with_time(2_503)
-        }
-    }
-
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.reindeer_race.parse_input_file(filename);
             Answer::Usize(self.reindeer_race.winner_points(self.time))
         }
+
+        solution_params! {
+            time: "Seconds the race lasts",
+        }
     }
 
     #[cfg(test)]
@@ -173,8 +172,9 @@ pub mod part_two {
 
         #[test_case(1, Answer::Usize(689); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_time(1_000),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("time", "1000")],
                 example_key,
                 answer,
                 &DAY,
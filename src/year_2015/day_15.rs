@@ -6,11 +6,12 @@ const DAY: Day = crate::utils::Day { year: 2015, day: 15 };
 mod utils {
     use std::cmp;
 
-    use itertools::Itertools;
     use ndarray::{Array, Array1, Array2};
     use regex::Regex;
 
-    use crate::utils::io_utils;
+    use crate::utils::{ilp::{LinearExpr, Model, Relation, Variable}, io_utils};
+
+    const TEASPOONS: i64 = 100;
 
     #[derive(Debug, Default)]
     struct Ingredients {
@@ -39,10 +40,6 @@ mod utils {
             self.calories = Array::from_vec(cals);
         }
 
-        fn calories(&self, recipe: &Array1<i64>) -> i64 {
-            self.calories.dot(recipe)
-        }
-
         fn score(&self, recipe: &Array1<i64>) -> i64 {
             self.features.dot(recipe).iter().map(|s| cmp::max(*s, 0)).product()
         }
@@ -62,27 +59,28 @@ mod utils {
             self.ingredients.parse_input_file(filename);
         }
 
+        /// The best score of a recipe with 100 teaspoons of ingredients, and
+        /// the given number of calories if any. The score multiplies the
+        /// properties together, so it can't be the model's objective; the
+        /// model just lists the recipes to score.
         pub fn max_total_score(&self, calorie_count: Option<i64>) -> i64 {
-            // TODO: figure out how to do this dynamically
-            let n = self.ingredients.num_ingredients();
-            itertools::repeat_n(0..=100, n)
-                .multi_cartesian_product()
-                .filter(|amounts| {
-                    if amounts.iter().sum::<i64>() != 100 { return false; }
-                    if let Some(calories) = calorie_count {
-                        let recipe = Array::from_vec(amounts.clone());
-                        let cals = self.ingredients.calories(&recipe);
-                        return cals == calories
-                    } else {
-                        return true;
-                    }
-                })
-                .map(|amounts| {
-                    let recipe = Array::from_vec(amounts);
-                    self.ingredients.score(&recipe)
-                })
-                .max()
-                .unwrap()
+            let mut model = Model::default();
+            let amounts: Vec<Variable> = (0..self.ingredients.num_ingredients())
+                .map(|idx| model.int_var(&format!("amount_{idx}"), 0, TEASPOONS))
+                .collect();
+            model.constrain(LinearExpr::sum(amounts.iter().copied()), Relation::Equal, TEASPOONS);
+            if let Some(calories) = calorie_count {
+                let recipe_calories = LinearExpr::weighted_sum(
+                    self.ingredients.calories.iter().copied().zip(amounts.iter().copied())
+                );
+                model.constrain(recipe_calories, Relation::Equal, calories);
+            }
+            let mut max_score = 0;
+            model.for_each_feasible(|assignment| {
+                let recipe = Array::from_iter(amounts.iter().map(|amount| assignment.value::<i64>(*amount)));
+                max_score = cmp::max(max_score, self.ingredients.score(&recipe));
+            });
+            max_score
         }
     }
 }
@@ -4,9 +4,9 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2015, day: 17 };
 
 mod utils {
-    use std::collections::{BTreeMap, HashMap};
+    use std::collections::BTreeMap;
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, memo::Memo};
 
     #[derive(Debug, PartialEq, Eq, Hash, Clone)]
     struct CacheKey {
@@ -32,7 +32,7 @@ mod utils {
     #[derive(Debug, Default)]
     pub struct Distributor {
         containers: Vec<usize>, // Sorted, descending
-        cache: HashMap<CacheKey, BTreeMap<usize, usize>>, // Maps length to the count of number of ways
+        memo: Memo<CacheKey, BTreeMap<usize, usize>>, // Maps length to the count of number of ways
     }
 
     impl Distributor {
@@ -43,12 +43,14 @@ mod utils {
         }
 
         pub fn num_combos(&mut self, amount: usize) -> usize {
-            let len_to_combos = self.num_combos_recursive(&self.containers.clone(), amount);
+            let len_to_combos = Self::num_combos_recursive(&mut self.memo, &self.containers, amount);
+            self.memo.report();
             len_to_combos.values().sum()
         }
 
         pub fn num_combos_with_min_containers(&mut self, amount: usize) -> usize {
-            let len_to_counts = self.num_combos_recursive(&self.containers.clone(), amount);
+            let len_to_counts = Self::num_combos_recursive(&mut self.memo, &self.containers, amount);
+            self.memo.report();
             *len_to_counts.iter()
                 .min_by_key(|len_to_count| len_to_count.0)
                 .unwrap()
@@ -56,7 +58,11 @@ mod utils {
         }
 
         /// Returns a map of length to number of combos of that length
-        fn num_combos_recursive(&mut self, mut containers: &[usize], remaining: usize) -> BTreeMap<usize, usize> {
+        fn num_combos_recursive(
+            memo: &mut Memo<CacheKey, BTreeMap<usize, usize>>,
+            mut containers: &[usize],
+            remaining: usize,
+        ) -> BTreeMap<usize, usize> {
             // Base cases
             if remaining == 0 { return BTreeMap::from([(0, 1)]); }
             if let Some(idx) = containers.iter().enumerate()
@@ -75,23 +81,22 @@ mod utils {
             if containers.iter().sum::<usize>() < remaining { return BTreeMap::new(); }
             if containers.len() == 1 && containers[0] == remaining { return BTreeMap::from([(1, 1)]); }
             // Check memoization
-            let cache_key = CacheKey::new(&containers, remaining);
-            if let Some(cached) = self.cache.get(&cache_key) { 
-                return cached.clone();
-            }
-            // Recursive cases
-            let incl = self.num_combos_recursive(&containers[1..], remaining - containers[0]);
-            let mut excl = self.num_combos_recursive(&containers[1..], remaining);
-            incl.into_iter().for_each(|(len, count)| {
-                excl.entry(len + 1).and_modify(|c| *c += count).or_insert(count);
-            });
-            self.cache.insert(cache_key, excl.clone());
-            excl
+            let cache_key = CacheKey::new(containers, remaining);
+            memo.get_or_compute(cache_key, |memo| {
+                // Recursive cases
+                let incl = Self::num_combos_recursive(memo, &containers[1..], remaining - containers[0]);
+                let mut excl = Self::num_combos_recursive(memo, &containers[1..], remaining);
+                incl.into_iter().for_each(|(len, count)| {
+                    excl.entry(len + 1).and_modify(|c| *c += count).or_insert(count);
+                });
+                excl
+            })
         }
     }
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Distributor;
@@ -102,26 +107,24 @@ pub mod part_one {
         distributor: Distributor,
     }
 
-    impl Soln {
-        fn with_amount(amount: usize) -> Self {
+    impl Default for Soln {
+        fn default() -> Self {
             Self {
-                amount,
+                amount: 150,
                 distributor: Distributor::default(),
             }
         }
     }
 
-    impl Default for Soln {
-        fn default() -> Self {
-            Self::with_amount(150)
-        }
-    }
-
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.distributor.parse_input_file(filename);
             Answer::Usize(self.distributor.num_combos(self.amount))
         }
+
+        solution_params! {
+            amount: "Liters of eggnog to store",
+        }
     }
 
     #[cfg(test)]
@@ -133,8 +136,9 @@ pub mod part_one {
 
         #[test_case(1, Answer::Usize(4); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_amount(25),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("amount", "25")],
                 example_key,
                 answer,
                 &DAY,
@@ -144,6 +148,7 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Distributor;
@@ -154,26 +159,24 @@ pub mod part_two {
         distributor: Distributor,
     }
 
-    impl Soln {
-        fn with_amount(amount: usize) -> Self {
+    impl Default for Soln {
+        fn default() -> Self {
             Self {
-                amount,
+                amount: 150,
                 distributor: Distributor::default(),
             }
         }
     }
 
-    impl Default for Soln {
-        fn default() -> Self {
-            Self::with_amount(150)
-        }
-    }
-
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.distributor.parse_input_file(filename);
             Answer::Usize(self.distributor.num_combos_with_min_containers(self.amount))
         }
+
+        solution_params! {
+            amount: "Liters of eggnog to store",
+        }
     }
 
     #[cfg(test)]
@@ -185,8 +188,9 @@ pub mod part_two {
 
         #[test_case(1, Answer::Usize(3); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_amount(25),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("amount", "25")],
                 example_key,
                 answer,
                 &DAY,
@@ -4,36 +4,11 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2015, day: 18 };
 
 mod utils {
-    use std::collections::BTreeSet;
-
-    use itertools::iproduct;
-
-    use crate::utils::io_utils;
-
-    #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-    struct Point {
-        col: isize,
-        row: isize,
-    }
-
-    impl Point {
-        fn neighbors(&self) -> BTreeSet<Self> {
-            iproduct!(self.col - 1..=self.col + 1, self.row - 1..=self.row + 1)
-                .filter_map(|(col, row)| {
-                    let pt = Point { col, row };
-                    if pt == *self {
-                        None
-                    } else {
-                        Some(pt)
-                    }
-                })
-                .collect()
-        }
-    }
+    use crate::utils::{automaton::{Automaton, Bounds, Neighborhood, Position}, io_utils};
 
     #[derive(Debug)]
     pub struct LightGrid {
-        on: BTreeSet<Point>,
+        lights: Option<Automaton<bool>>,
         rows: usize,
         corners_always_on: bool,
     }
@@ -41,65 +16,59 @@ mod utils {
     impl LightGrid {
         pub fn new(corners_always_on: bool) -> Self {
             Self {
-                on: BTreeSet::default(),
+                lights: None,
                 rows: usize::default(),
                 corners_always_on,
             }
         }
 
         pub fn parse_input_file(&mut self, filename: &str) {
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    line.chars().enumerate()
-                        .filter_map(|(idx, ch)| if ch == '#' { Some(idx) } else { None })
-                        .for_each(|col| { 
-                            self.on.insert(Point { col: col as isize, row: self.rows as isize }); 
-                        });
-                    self.rows += 1;
-                });
+            let lines: Vec<String> = io_utils::file_to_lines(filename).collect();
+            self.rows = lines.len();
+            let mut lights = Automaton::new(
+                Neighborhood::Moore,
+                Bounds::Bounded { width: self.rows, height: self.rows },
+                |on, neighbors| {
+                    let neighbors_on = neighbors.iter().filter(|neighbor| **neighbor).count();
+                    neighbors_on == 3 || on && neighbors_on == 2
+                },
+            );
+            lines.iter().enumerate().for_each(|(row, line)| {
+                line.chars().enumerate()
+                    .filter(|(_col, ch)| *ch == '#')
+                    .for_each(|(col, _ch)| {
+                        lights.set(Position::new(col as isize, row as isize), true);
+                    });
+            });
+            self.lights = Some(lights);
+            self.turn_on_corners();
         }
 
-        fn tick(&mut self) {
-            self.on = iproduct!(0..self.rows, 0..self.rows)
-                .filter_map(|(col, row)| {
-                    let pt = Point { col: col as isize, row: row as isize };
-                    let neighbors_on = pt.neighbors().intersection(&self.on).count();
-                    match self.on.contains(&pt) {
-                        true => {
-                            if neighbors_on >= 2 && neighbors_on <= 3 {
-                                Some(pt)
-                            } else {
-                                None
-                            }
-                        },
-                        false => {
-                            if neighbors_on == 3 {
-                                Some(pt)
-                            } else {
-                                None
-                            }
-                        },
-                    }
-                })
-                .collect();
-            if self.corners_always_on {
-                self.on.extend(
-                    iproduct!([0, self.rows - 1], [0, self.rows - 1])
-                        .map(|(col, row)| Point { col: col as isize, row: row as isize})
-                );
+        fn turn_on_corners(&mut self) {
+            if !self.corners_always_on { return; }
+            let max = self.rows as isize - 1;
+            let lights = self.lights.as_mut().unwrap();
+            for (col, row) in [(0, 0), (0, max), (max, 0), (max, max)] {
+                lights.set(Position::new(col, row), true);
             }
         }
 
+        fn tick(&mut self) {
+            self.lights.as_mut().unwrap().step();
+            self.turn_on_corners();
+        }
+
         pub fn num_on_after(&mut self, ticks: usize) -> usize {
             for _ in 0..ticks {
                 self.tick();
             }
-            self.on.len()
+            self.lights.as_ref().unwrap().count(true)
         }
     }
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::LightGrid;
@@ -110,26 +79,24 @@ pub mod part_one {
         ticks: usize,
     }
 
-    impl Soln {
-        fn with_ticks(ticks: usize) -> Self {
+    impl Default for Soln {
+        fn default() -> Self {
             Self {
                 light_grid: LightGrid::new(false),
-                ticks,
+                ticks: 100,
             }
         }
     }
 
-    impl Default for Soln {
-        fn default() -> Self {
-            Self::with_ticks(100)
-        }
-    }
-
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.light_grid.parse_input_file(filename);
             Answer::Usize(self.light_grid.num_on_after(self.ticks))
         }
+
+        solution_params! {
+            ticks: "Number of steps to animate",
+        }
     }
 
     #[cfg(test)]
@@ -141,8 +108,9 @@ pub mod part_one {
 
         #[test_case(1, Answer::Usize(4); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_ticks(4),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("ticks", "4")],
                 example_key,
                 answer,
                 &DAY,
@@ -152,6 +120,7 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::LightGrid;
@@ -162,26 +131,24 @@ pub mod part_two {
         ticks: usize,
     }
 
-    impl Soln {
-        fn with_ticks(ticks: usize) -> Self {
+    impl Default for Soln {
+        fn default() -> Self {
             Self {
                 light_grid: LightGrid::new(true),
-                ticks,
+                ticks: 100,
             }
         }
     }
 
-    impl Default for Soln {
-        fn default() -> Self {
-            Self::with_ticks(100)
-        }
-    }
-
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.light_grid.parse_input_file(filename);
             Answer::Usize(self.light_grid.num_on_after(self.ticks))
         }
+
+        solution_params! {
+            ticks: "Number of steps to animate",
+        }
     }
 
     #[cfg(test)]
@@ -193,8 +160,9 @@ pub mod part_two {
 
         #[test_case(2, Answer::Usize(17); "example_2")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_ticks(5),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("ticks", "5")],
                 example_key,
                 answer,
                 &DAY,
@@ -0,0 +1,227 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 19 };
+
+mod utils {
+    use std::collections::{HashMap, HashSet};
+
+    use regex::Regex;
+
+    use crate::utils::io_utils;
+
+    /// Splits a molecule into its elements (a capital letter followed by any
+    /// lowercase letters), or the electron `e`.
+    fn elements(molecule: &str) -> Vec<String> {
+        let re = Regex::new(r"[A-Z][a-z]*|e").unwrap();
+        re.find_iter(molecule).map(|element| element.as_str().to_string()).collect()
+    }
+
+    /// A rule of the grammar, with each element numbered. Rules with more
+    /// than two elements on the right are split into a chain of rules with
+    /// two each, through helper symbols, and only the first rule in the
+    /// chain costs a step.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct BinaryRule {
+        from: usize,
+        left: usize,
+        right: usize,
+        steps: usize,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Machine {
+        replacements: Vec<(String, Vec<String>)>,
+        molecule: Vec<String>,
+    }
+
+    impl Machine {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let replacement_re = Regex::new(r"(?<from>\w+) => (?<to>\w+)").unwrap();
+            for line in io_utils::file_to_lines(filename) {
+                if let Some(captures) = replacement_re.captures(&line) {
+                    self.replacements.push((
+                        captures.name("from").unwrap().as_str().to_string(),
+                        elements(captures.name("to").unwrap().as_str()),
+                    ));
+                } else if !line.is_empty() {
+                    self.molecule = elements(&line);
+                }
+            }
+        }
+
+        /// The number of different molecules one replacement can make.
+        pub fn num_calibration_molecules(&self) -> usize {
+            let mut molecules: HashSet<String> = HashSet::new();
+            for (idx, element) in self.molecule.iter().enumerate() {
+                for (_from, to) in self.replacements.iter().filter(|(from, _to)| from == element) {
+                    molecules.insert(
+                        self.molecule[..idx].iter()
+                            .chain(to.iter())
+                            .chain(self.molecule[idx + 1..].iter())
+                            .cloned()
+                            .collect()
+                    );
+                }
+            }
+            molecules.len()
+        }
+
+        /// The fewest replacements needed to make the molecule starting from
+        /// a single electron.
+        ///
+        /// Every replacement turns one element into several, so the
+        /// replacements form a context-free grammar and this is the cheapest
+        /// parse of the molecule, found with the
+        /// [CYK algorithm](https://en.wikipedia.org/wiki/CYK_algorithm):
+        /// for every span of the molecule, the fewest steps to make it from
+        /// each symbol.
+        pub fn fewest_steps_from_electron(&self) -> usize {
+            let mut symbols: HashMap<String, usize> = HashMap::new();
+            let mut symbol = |name: &str| {
+                let next = symbols.len();
+                *symbols.entry(name.to_string()).or_insert(next)
+            };
+            let electron = symbol("e");
+            let molecule: Vec<usize> = self.molecule.iter().map(|element| symbol(element)).collect();
+            // Unit rules (`from`, `to`) each cost a step.
+            let mut unit_rules: Vec<(usize, usize)> = Vec::new();
+            let mut binary_rules: Vec<BinaryRule> = Vec::new();
+            let mut num_helpers = 0;
+            for (from, to) in self.replacements.iter() {
+                let to: Vec<usize> = to.iter().map(|element| symbol(element)).collect();
+                if to.len() == 1 {
+                    unit_rules.push((symbol(from), to[0]));
+                    continue;
+                }
+                let mut from = symbol(from);
+                let mut steps = 1;
+                for left in &to[..to.len() - 2] {
+                    num_helpers += 1;
+                    let helper = symbol(&format!("#{num_helpers}"));
+                    binary_rules.push(BinaryRule { from, left: *left, right: helper, steps });
+                    from = helper;
+                    steps = 0;
+                }
+                binary_rules.push(BinaryRule { from, left: to[to.len() - 2], right: to[to.len() - 1], steps });
+            }
+            let mut rules_by_left: HashMap<usize, Vec<BinaryRule>> = HashMap::new();
+            binary_rules.into_iter().for_each(|rule| rules_by_left.entry(rule.left).or_default().push(rule));
+
+            // steps[start][len - 1] holds the fewest steps to make the span
+            // from each symbol that can make it.
+            let len = molecule.len();
+            let mut steps: Vec<Vec<HashMap<usize, usize>>> = vec![Vec::new(); len];
+            for span_len in 1..=len {
+                for start in 0..=len - span_len {
+                    let mut span_steps: HashMap<usize, usize> = HashMap::new();
+                    if span_len == 1 {
+                        span_steps.insert(molecule[start], 0);
+                    }
+                    for left_len in 1..span_len {
+                        let right_steps = &steps[start + left_len][span_len - left_len - 1];
+                        for (left, left_steps) in steps[start][left_len - 1].iter() {
+                            for rule in rules_by_left.get(left).into_iter().flatten() {
+                                if let Some(right_steps) = right_steps.get(&rule.right) {
+                                    let total = left_steps + right_steps + rule.steps;
+                                    span_steps.entry(rule.from)
+                                        .and_modify(|steps| *steps = total.min(*steps))
+                                        .or_insert(total);
+                                }
+                            }
+                        }
+                    }
+                    // Unit rules can chain, so apply them until nothing improves.
+                    let mut improved = true;
+                    while improved {
+                        improved = false;
+                        for (from, to) in unit_rules.iter() {
+                            let Some(total) = span_steps.get(to).map(|steps| steps + 1) else { continue; };
+                            if span_steps.get(from).is_none_or(|steps| total < *steps) {
+                                span_steps.insert(*from, total);
+                                improved = true;
+                            }
+                        }
+                    }
+                    steps[start].push(span_steps);
+                }
+            }
+            *steps[0][len - 1].get(&electron).expect("Molecule should be possible to make.")
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Machine;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        machine: Machine,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.machine.parse_input_file(filename);
+            Answer::Usize(self.machine.num_calibration_molecules())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(4); "example_1")]
+        #[test_case(2, Answer::Usize(7); "example_2")]
+        #[test_case(3, Answer::Usize(4); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Machine;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        machine: Machine,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.machine.parse_input_file(filename);
+            Answer::Usize(self.machine.fewest_steps_from_electron())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(3, Answer::Usize(3); "example_3")]
+        #[test_case(4, Answer::Usize(6); "example_4")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 20 };
+
+mod utils {
+    /// The lowest-numbered house getting at least `target` presents, when
+    /// each elf delivers `per_elf` presents times its own number to every
+    /// multiple of its number, stopping after `max_houses` houses if given.
+    ///
+    /// House `n` always gets at least `n * per_elf` presents from elf `n`, so
+    /// no house past `target / per_elf` needs to be sieved.
+    pub fn lowest_house(target: usize, per_elf: usize, max_houses: Option<usize>) -> usize {
+        let limit = target.div_ceil(per_elf);
+        let mut presents = vec![0; limit + 1];
+        for elf in 1..=limit {
+            let houses = (elf..=limit).step_by(elf);
+            let houses: Box<dyn Iterator<Item = usize>> = match max_houses {
+                Some(max_houses) => Box::new(houses.take(max_houses)),
+                None => Box::new(houses),
+            };
+            for house in houses {
+                presents[house] += elf * per_elf;
+            }
+        }
+        (1..=limit).find(|house| presents[*house] >= target)
+            .expect("The house past the limit should get enough presents.")
+    }
+}
+
+pub mod part_one {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let target = io_utils::file_to_string(filename).trim().parse().unwrap();
+            Answer::Usize(utils::lowest_house(target, 10, None))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(4); "example_1")]
+        #[test_case(2, Answer::Usize(8); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::{io_utils, solution::{Answer, Solution}};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let target = io_utils::file_to_string(filename).trim().parse().unwrap();
+            Answer::Usize(utils::lowest_house(target, 11, Some(50)))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(4); "example_1")]
+        #[test_case(2, Answer::Usize(6); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
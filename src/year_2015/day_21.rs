@@ -0,0 +1,212 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 21 };
+
+mod utils {
+    use itertools::Itertools;
+    use regex::Regex;
+
+    use crate::utils::io_utils;
+
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    struct Item {
+        cost: u32,
+        damage: u32,
+        armor: u32,
+    }
+
+    impl Item {
+        const fn new(cost: u32, damage: u32, armor: u32) -> Self {
+            Self { cost, damage, armor }
+        }
+    }
+
+    const WEAPONS: [Item; 5] = [
+        Item::new(8, 4, 0),
+        Item::new(10, 5, 0),
+        Item::new(25, 6, 0),
+        Item::new(40, 7, 0),
+        Item::new(74, 8, 0),
+    ];
+
+    const ARMOR: [Item; 5] = [
+        Item::new(13, 0, 1),
+        Item::new(31, 0, 2),
+        Item::new(53, 0, 3),
+        Item::new(75, 0, 4),
+        Item::new(102, 0, 5),
+    ];
+
+    const RINGS: [Item; 6] = [
+        Item::new(25, 1, 0),
+        Item::new(50, 2, 0),
+        Item::new(100, 3, 0),
+        Item::new(20, 0, 1),
+        Item::new(40, 0, 2),
+        Item::new(80, 0, 3),
+    ];
+
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    pub struct Fighter {
+        hit_points: u32,
+        damage: u32,
+        armor: u32,
+    }
+
+    impl Fighter {
+        pub fn boss_from_file(filename: &str) -> Self {
+            let stat_re = Regex::new(r"(?<stat>[\w ]+): (?<value>\d+)").unwrap();
+            let mut boss = Self::default();
+            for line in io_utils::file_to_lines(filename) {
+                let captures = stat_re.captures(&line).expect("Line should be a stat.");
+                let value = captures.name("value").unwrap().as_str().parse().unwrap();
+                match captures.name("stat").unwrap().as_str() {
+                    "Hit Points" => boss.hit_points = value,
+                    "Damage" => boss.damage = value,
+                    "Armor" => boss.armor = value,
+                    stat => panic!("Unrecognized stat {stat}."),
+                }
+            }
+            boss
+        }
+
+        /// Whether this fighter, attacking first, beats the other.
+        fn beats(&self, other: &Self) -> bool {
+            let turns_to_win = other.hit_points.div_ceil(self.damage.saturating_sub(other.armor).max(1));
+            let turns_to_lose = self.hit_points.div_ceil(other.damage.saturating_sub(self.armor).max(1));
+            turns_to_win <= turns_to_lose
+        }
+    }
+
+    /// Every loadout the shop allows (one weapon, at most one armor and at
+    /// most two different rings), as its total cost and the player with it.
+    fn loadouts(hit_points: u32) -> impl Iterator<Item = (u32, Fighter)> {
+        let armors = std::iter::once(Item::default()).chain(ARMOR);
+        let rings = (0..=2).flat_map(|num_rings| RINGS.into_iter().combinations(num_rings));
+        WEAPONS.into_iter()
+            .cartesian_product(armors)
+            .cartesian_product(rings)
+            .map(move |((weapon, armor), rings)| {
+                let items: Vec<Item> = [weapon, armor].into_iter().chain(rings).collect();
+                let player = Fighter {
+                    hit_points,
+                    damage: items.iter().map(|item| item.damage).sum(),
+                    armor: items.iter().map(|item| item.armor).sum(),
+                };
+                (items.iter().map(|item| item.cost).sum(), player)
+            })
+    }
+
+    pub fn cheapest_win(hit_points: u32, boss: &Fighter) -> u32 {
+        loadouts(hit_points)
+            .filter(|(_cost, player)| player.beats(boss))
+            .map(|(cost, _player)| cost)
+            .min()
+            .expect("Some loadout should win.")
+    }
+
+    pub fn priciest_loss(hit_points: u32, boss: &Fighter) -> u32 {
+        loadouts(hit_points)
+            .filter(|(_cost, player)| !player.beats(boss))
+            .map(|(cost, _player)| cost)
+            .max()
+            .expect("Some loadout should lose.")
+    }
+}
+
+pub mod part_one {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::{self, Fighter};
+
+    #[derive(Debug)]
+    pub struct Soln {
+        hit_points: u32,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { hit_points: 100 }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let boss = Fighter::boss_from_file(filename);
+            Answer::U32(utils::cheapest_win(self.hit_points, &boss))
+        }
+
+        solution_params! {
+            hit_points: "Hit points the player starts with",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U32(65); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("hit_points", "8")],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::{self, Fighter};
+
+    #[derive(Debug)]
+    pub struct Soln {
+        hit_points: u32,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { hit_points: 100 }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let boss = Fighter::boss_from_file(filename);
+            Answer::U32(utils::priciest_loss(self.hit_points, &boss))
+        }
+
+        solution_params! {
+            hit_points: "Hit points the player starts with",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U32(188); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("hit_points", "8")],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,253 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 22 };
+
+mod utils {
+    use std::{cmp::Reverse, collections::{BinaryHeap, HashSet}};
+
+    use regex::Regex;
+
+    use crate::utils::io_utils;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum Spell {
+        MagicMissile,
+        Drain,
+        Shield,
+        Poison,
+        Recharge,
+    }
+
+    impl Spell {
+        const ALL: [Spell; 5] = [Spell::MagicMissile, Spell::Drain, Spell::Shield, Spell::Poison, Spell::Recharge];
+
+        fn cost(&self) -> u32 {
+            match self {
+                Spell::MagicMissile => 53,
+                Spell::Drain => 73,
+                Spell::Shield => 113,
+                Spell::Poison => 173,
+                Spell::Recharge => 229,
+            }
+        }
+    }
+
+    /// The fight at the start of one of the player's turns, with the turns
+    /// left on each effect.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+    struct Fight {
+        player_hit_points: i32,
+        mana: u32,
+        boss_hit_points: i32,
+        shield: u8,
+        poison: u8,
+        recharge: u8,
+    }
+
+    impl Fight {
+        fn boss_is_dead(&self) -> bool {
+            self.boss_hit_points <= 0
+        }
+
+        fn apply_effects(&mut self) {
+            if self.poison > 0 {
+                self.boss_hit_points -= 3;
+                self.poison -= 1;
+            }
+            if self.recharge > 0 {
+                self.mana += 101;
+                self.recharge -= 1;
+            }
+            self.shield = self.shield.saturating_sub(1);
+        }
+
+        /// The fight after the player casts the spell, or `None` if it can't
+        /// be cast.
+        fn cast(&self, spell: Spell) -> Option<Self> {
+            if spell.cost() > self.mana {
+                return None;
+            }
+            let mut fight = Self { mana: self.mana - spell.cost(), ..*self };
+            match spell {
+                Spell::MagicMissile => fight.boss_hit_points -= 4,
+                Spell::Drain => {
+                    fight.boss_hit_points -= 2;
+                    fight.player_hit_points += 2;
+                },
+                Spell::Shield if fight.shield == 0 => fight.shield = 6,
+                Spell::Poison if fight.poison == 0 => fight.poison = 6,
+                Spell::Recharge if fight.recharge == 0 => fight.recharge = 5,
+                _ => return None,
+            }
+            Some(fight)
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct WizardSimulator {
+        boss_hit_points: i32,
+        boss_damage: i32,
+    }
+
+    impl WizardSimulator {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let stat_re = Regex::new(r"(?<stat>[\w ]+): (?<value>\d+)").unwrap();
+            for line in io_utils::file_to_lines(filename) {
+                let captures = stat_re.captures(&line).expect("Line should be a stat.");
+                let value = captures.name("value").unwrap().as_str().parse().unwrap();
+                match captures.name("stat").unwrap().as_str() {
+                    "Hit Points" => self.boss_hit_points = value,
+                    "Damage" => self.boss_damage = value,
+                    stat => panic!("Unrecognized stat {stat}."),
+                }
+            }
+        }
+
+        /// The fight after the boss's turn, or `None` if the player dies.
+        fn boss_turn(&self, mut fight: Fight) -> Option<Fight> {
+            let armor = if fight.shield > 0 { 7 } else { 0 };
+            fight.apply_effects();
+            if !fight.boss_is_dead() {
+                fight.player_hit_points -= (self.boss_damage - armor).max(1);
+            }
+            (fight.player_hit_points > 0).then_some(fight)
+        }
+
+        /// The least mana the player can spend and still win, found with
+        /// Dijkstra's algorithm over the fights at the start of each of the
+        /// player's turns. On hard mode the player loses a hit point at the
+        /// start of each of their turns.
+        pub fn least_mana_to_win(&self, hit_points: i32, mana: u32, hard: bool) -> u32 {
+            let start = Fight {
+                player_hit_points: hit_points,
+                mana,
+                boss_hit_points: self.boss_hit_points,
+                shield: 0,
+                poison: 0,
+                recharge: 0,
+            };
+            let mut queue: BinaryHeap<Reverse<(u32, Fight)>> = BinaryHeap::from([Reverse((0, start))]);
+            let mut visited: HashSet<Fight> = HashSet::new();
+            while let Some(Reverse((spent, mut fight))) = queue.pop() {
+                if fight.boss_is_dead() {
+                    return spent;
+                }
+                if !visited.insert(fight) {
+                    continue;
+                }
+                if hard {
+                    fight.player_hit_points -= 1;
+                    if fight.player_hit_points <= 0 {
+                        continue;
+                    }
+                }
+                fight.apply_effects();
+                if fight.boss_is_dead() {
+                    queue.push(Reverse((spent, fight)));
+                    continue;
+                }
+                for spell in Spell::ALL {
+                    let Some(next) = fight.cast(spell) else { continue; };
+                    let next = if next.boss_is_dead() { Some(next) } else { self.boss_turn(next) };
+                    if let Some(next) = next {
+                        queue.push(Reverse((spent + spell.cost(), next)));
+                    }
+                }
+            }
+            panic!("The player should be able to win.")
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::WizardSimulator;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        hit_points: i32,
+        mana: u32,
+        simulator: WizardSimulator,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                hit_points: 50,
+                mana: 500,
+                simulator: WizardSimulator::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.simulator.parse_input_file(filename);
+            Answer::U32(self.simulator.least_mana_to_win(self.hit_points, self.mana, false))
+        }
+
+        solution_params! {
+            hit_points: "Hit points the player starts with",
+            mana: "Mana the player starts with",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U32(226); "example_1")]
+        #[test_case(2, Answer::U32(641); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("hit_points", "10"), ("mana", "250")],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::WizardSimulator;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        hit_points: i32,
+        mana: u32,
+        simulator: WizardSimulator,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                hit_points: 50,
+                mana: 500,
+                simulator: WizardSimulator::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.simulator.parse_input_file(filename);
+            Answer::U32(self.simulator.least_mana_to_win(self.hit_points, self.mana, true))
+        }
+
+        solution_params! {
+            hit_points: "Hit points the player starts with",
+            mana: "Mana the player starts with",
+        }
+    }
+}
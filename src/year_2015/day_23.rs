@@ -0,0 +1,152 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 23 };
+
+mod utils {
+    use crate::utils::io_utils;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum Instruction {
+        Half(usize),
+        Triple(usize),
+        Increment(usize),
+        Jump(isize),
+        JumpIfEven(usize, isize),
+        JumpIfOne(usize, isize),
+    }
+
+    impl Instruction {
+        fn from_str(input: &str) -> Self {
+            let register = |name: &str| match name.trim_end_matches(',') {
+                "a" => 0,
+                "b" => 1,
+                name => panic!("Unrecognized register {name}."),
+            };
+            let offset = |offset: &str| offset.parse().expect("Offset should be a signed integer.");
+            let parts: Vec<&str> = input.split_whitespace().collect();
+            match parts[..] {
+                ["hlf", r] => Instruction::Half(register(r)),
+                ["tpl", r] => Instruction::Triple(register(r)),
+                ["inc", r] => Instruction::Increment(register(r)),
+                ["jmp", o] => Instruction::Jump(offset(o)),
+                ["jie", r, o] => Instruction::JumpIfEven(register(r), offset(o)),
+                ["jio", r, o] => Instruction::JumpIfOne(register(r), offset(o)),
+                _ => panic!("Unrecognized instruction {input}."),
+            }
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Computer {
+        program: Vec<Instruction>,
+        registers: [u64; 2],
+    }
+
+    impl Computer {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            self.program = io_utils::file_to_lines(filename)
+                .map(|line| Instruction::from_str(&line))
+                .collect();
+        }
+
+        /// Runs the program from the start, with register `a` set to `a`
+        /// and `b` to zero, until it jumps out of the program.
+        pub fn run(&mut self, a: u64) {
+            self.registers = [a, 0];
+            let mut pointer: isize = 0;
+            while let Some(instruction) = usize::try_from(pointer).ok().and_then(|idx| self.program.get(idx)) {
+                let mut jump = 1;
+                match *instruction {
+                    Instruction::Half(r) => self.registers[r] /= 2,
+                    Instruction::Triple(r) => self.registers[r] *= 3,
+                    Instruction::Increment(r) => self.registers[r] += 1,
+                    Instruction::Jump(offset) => jump = offset,
+                    Instruction::JumpIfEven(r, offset) => if self.registers[r].is_multiple_of(2) { jump = offset },
+                    Instruction::JumpIfOne(r, offset) => if self.registers[r] == 1 { jump = offset },
+                }
+                pointer += jump;
+            }
+        }
+
+        #[cfg(test)]
+        pub fn a(&self) -> u64 {
+            self.registers[0]
+        }
+
+        pub fn b(&self) -> u64 {
+            self.registers[1]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils;
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn example_leaves_two_in_a() {
+            let mut computer = Computer::default();
+            computer.parse_input_file(&io_utils::input_filename(&DAY, io_utils::InputFileType::Example(1)));
+            computer.run(0);
+            assert_eq!(computer.a(), 2);
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Computer;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        computer: Computer,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.computer.parse_input_file(filename);
+            self.computer.run(0);
+            Answer::U64(self.computer.b())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(0); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Computer;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        computer: Computer,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.computer.parse_input_file(filename);
+            self.computer.run(1);
+            Answer::U64(self.computer.b())
+        }
+    }
+}
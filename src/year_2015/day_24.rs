@@ -0,0 +1,156 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 24 };
+
+mod utils {
+    use itertools::Itertools;
+
+    use crate::utils::io_utils;
+
+    #[derive(Debug, Default)]
+    pub struct Sleigh {
+        weights: Vec<u64>,
+    }
+
+    impl Sleigh {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            self.weights = io_utils::file_to_lines(filename)
+                .map(|line| line.parse::<u64>().expect("Line should be a weight."))
+                .sorted_by(|a, b| b.cmp(a))
+                .collect();
+        }
+
+        /// Whether the weights can be split into `groups` groups all
+        /// weighing `target`.
+        fn can_partition(weights: &[u64], groups: usize, target: u64) -> bool {
+            fn fill(weights: &[u64], used: &mut Vec<bool>, start: usize, remaining: u64, groups: usize, target: u64) -> bool {
+                if remaining == 0 {
+                    return Sleigh::can_partition(
+                        &weights.iter().zip(used.iter()).filter(|(_, used)| !**used).map(|(weight, _)| *weight).collect::<Vec<u64>>(),
+                        groups - 1,
+                        target,
+                    );
+                }
+                for idx in start..weights.len() {
+                    if used[idx] || weights[idx] > remaining {
+                        continue;
+                    }
+                    used[idx] = true;
+                    if fill(weights, used, idx + 1, remaining - weights[idx], groups, target) {
+                        return true;
+                    }
+                    used[idx] = false;
+                }
+                false
+            }
+
+            if groups == 1 {
+                return weights.iter().sum::<u64>() == target;
+            }
+            fill(weights, &mut vec![false; weights.len()], 0, target, groups, target)
+        }
+
+        /// The lowest quantum entanglement (product of weights) of the first
+        /// group, when the packages are split into `groups` equally heavy
+        /// groups and the first has as few packages as possible.
+        ///
+        /// Tries first groups in increasing size, keeping only those that
+        /// leave packages which can be split into the other groups.
+        pub fn ideal_quantum_entanglement(&self, groups: usize) -> u64 {
+            let total: u64 = self.weights.iter().sum();
+            assert_eq!(total % groups as u64, 0, "Weights should split into equal groups.");
+            let target = total / groups as u64;
+            for size in 1..=self.weights.len() {
+                let best = (0..self.weights.len()).combinations(size)
+                    .filter(|first| first.iter().map(|idx| self.weights[*idx]).sum::<u64>() == target)
+                    .map(|first| {
+                        let rest: Vec<u64> = (0..self.weights.len())
+                            .filter(|idx| !first.contains(idx))
+                            .map(|idx| self.weights[idx])
+                            .collect();
+                        (first.iter().map(|idx| self.weights[*idx]).product::<u64>(), rest)
+                    })
+                    .sorted_by_key(|(entanglement, _rest)| *entanglement)
+                    .find(|(_entanglement, rest)| Self::can_partition(rest, groups - 1, target));
+                if let Some((entanglement, _rest)) = best {
+                    return entanglement;
+                }
+            }
+            panic!("Weights should split into equal groups.")
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Sleigh;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        sleigh: Sleigh,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.sleigh.parse_input_file(filename);
+            Answer::U64(self.sleigh.ideal_quantum_entanglement(3))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(99); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Sleigh;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        sleigh: Sleigh,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.sleigh.parse_input_file(filename);
+            // With the trunk
+            Answer::U64(self.sleigh.ideal_quantum_entanglement(4))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(44); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2015, day: 25 };
+
+mod utils {
+    use regex::Regex;
+
+    use crate::utils::io_utils;
+
+    const FIRST_CODE: u64 = 20_151_125;
+    const MULTIPLIER: u64 = 252_533;
+    const MODULUS: u64 = 33_554_393;
+
+    fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+        let mut result = 1;
+        base %= modulus;
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = result * base % modulus;
+            }
+            base = base * base % modulus;
+            exponent /= 2;
+        }
+        result
+    }
+
+    /// The code at the row and column the manual asks for.
+    ///
+    /// The codes fill the grid along its diagonals, so the code at a
+    /// position is the first code multiplied by `MULTIPLIER` once for every
+    /// position filled before it.
+    pub fn code(filename: &str) -> u64 {
+        let position_re = Regex::new(r"row (?<row>\d+), column (?<column>\d+)").unwrap();
+        let input = io_utils::file_to_string(filename);
+        let captures = position_re.captures(&input).expect("Input should give a row and column.");
+        let row: u64 = captures.name("row").unwrap().as_str().parse().unwrap();
+        let column: u64 = captures.name("column").unwrap().as_str().parse().unwrap();
+        let diagonal = row + column - 1;
+        let filled_before = diagonal * (diagonal - 1) / 2 + column - 1;
+        FIRST_CODE * pow_mod(MULTIPLIER, filled_before, MODULUS) % MODULUS
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::U64(utils::code(filename))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(32_451_966); "example_1")]
+        #[test_case(2, Answer::U64(27_995_004); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
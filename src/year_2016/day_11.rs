@@ -3,216 +3,226 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2016, day: 11 };
 
-pub mod part_one {
-    use std::{cmp::Ordering, collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet}};
+mod utils {
+    use std::collections::{HashMap, HashSet};
 
     use itertools::Itertools;
     use regex::Regex;
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::io_utils;
 
-    #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-    enum Device {
-        Generator(String),
-        Microchip(String),
-    }
+    const NUM_FLOORS: u8 = 4;
+    const BITS_PER_FLOOR: u32 = 2;
+    /// Enough pairs to fill a `u64` key after the elevator's floor.
+    const MAX_PAIRS: usize = 15;
 
-    #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-    struct FloorContents {
-        generators: BTreeSet<String>,
-        microchips: BTreeSet<String>,
+    /// The floors of a material's generator and microchip. Which material it
+    /// is doesn't matter: swapping every device of one material with those of
+    /// another gives a state that takes just as many steps to finish.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+    struct Pair {
+        generator: u8,
+        microchip: u8,
     }
 
-    impl FloorContents {
-        fn is_valid(&self) -> bool {
-            self.generators.is_empty() || self.microchips.is_subset(&self.generators)
-        }
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum Device {
+        Generator,
+        Microchip,
+    }
 
-        fn is_empty(&self) -> bool {
-            self.generators.is_empty() && self.microchips.is_empty()
-        }
+    /// The state of the facility, canonicalized as the elevator's floor and
+    /// the sorted pairs of floors (with floors numbered from 0).
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+    pub struct Facility {
+        elevator: u8,
+        pairs: Vec<Pair>,
+    }
 
-        fn remove_devices(&mut self, devices: &Vec<Device>) {
-            for device in devices {
-                match device {
-                    Device::Generator(material) => self.generators.remove(material),
-                    Device::Microchip(material) => self.microchips.remove(material),
+    impl Facility {
+        pub fn from_file(filename: &str) -> Self {
+            let line_re = Regex::new(r"The (?<floor>[a-z]+) floor contains (?<contents>.*)\.").unwrap();
+            let device_re = Regex::new(r"a (?<material>[a-z]+)(?<device_type>( generator)|(\-compatible microchip))").unwrap();
+            let mut generators: HashMap<String, u8> = HashMap::new();
+            let mut microchips: HashMap<String, u8> = HashMap::new();
+            io_utils::file_to_lines(filename).for_each(|line| {
+                let captures = line_re.captures(&line).unwrap();
+                let floor: u8 = match captures.name("floor").unwrap().as_str() {
+                    "first" => 0,
+                    "second" => 1,
+                    "third" => 2,
+                    "fourth" => 3,
+                    _ => panic!("Unrecognized floor."),
                 };
+                device_re.captures_iter(captures.name("contents").unwrap().as_str()).for_each(|captures| {
+                    let material = captures.name("material").unwrap().as_str().to_string();
+                    match captures.name("device_type").unwrap().as_str() {
+                        " generator" => generators.insert(material, floor),
+                        "-compatible microchip" => microchips.insert(material, floor),
+                        _ => panic!("Unrecognized device type."),
+                    };
+                });
+            });
+            let mut facility = Self::default();
+            for (material, generator) in generators {
+                let microchip = microchips.remove(&material).expect("Each generator should have a microchip.");
+                facility.add_pair(generator, microchip);
             }
+            assert!(microchips.is_empty(), "Each microchip should have a generator.");
+            facility
         }
 
-        fn add_devices(&mut self, devices: &Vec<Device>) {
-            for device in devices {
-                match device {
-                    Device::Generator(material) => self.generators.insert(material.clone()),
-                    Device::Microchip(material) => self.microchips.insert(material.clone()),
-                };
-            }
+        /// Adds a material's generator and microchip on the given floors.
+        pub fn add_pair(&mut self, generator: u8, microchip: u8) {
+            assert!(self.pairs.len() < MAX_PAIRS, "Should be at most {MAX_PAIRS} materials.");
+            self.pairs.push(Pair { generator, microchip });
+            self.pairs.sort();
         }
-    }
 
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
-    struct Facility {
-        bottom_floor: u8,
-        top_floor: u8,
-        elevator_floor: u8,
-        floor_contents: BTreeMap<u8, FloorContents>,
-    }
-
-    impl Default for Facility {
-        fn default() -> Self {
-            Self {
-                bottom_floor: 1,
-                top_floor: 4,
-                elevator_floor: 1,
-                floor_contents: BTreeMap::new(),
-            }
+        /// Packs the state into 2 bits per floor: the elevator's, then each
+        /// pair's generator and microchip in sorted order.
+        fn key(&self) -> u64 {
+            self.pairs.iter()
+                .flat_map(|pair| [pair.generator, pair.microchip])
+                .fold(self.elevator as u64, |key, floor| key << BITS_PER_FLOOR | floor as u64)
         }
-    }
 
-    impl Facility {
-        fn is_valid_state(&self) -> bool {
-            self.floor_contents.iter().all(|(_floor, contents)| {
-                contents.is_valid()
-            })
+        fn is_safe(&self) -> bool {
+            self.pairs.iter()
+                .filter(|pair| pair.microchip != pair.generator)
+                .all(|unprotected| self.pairs.iter().all(|pair| pair.generator != unprotected.microchip))
         }
 
-        fn is_end_state(&self) -> bool {
-            self.floor_contents.iter()
-                .all(|(floor, contents)| {
-                    *floor == self.top_floor || contents.is_empty()
-                })
+        fn is_finished(&self) -> bool {
+            self.pairs.iter().all(|pair| pair.generator == NUM_FLOORS - 1 && pair.microchip == NUM_FLOORS - 1)
         }
 
-        fn next_actions(&self) -> Vec<Vec<Device>> {
-            let contents = self.floor_contents.get(&self.elevator_floor).unwrap();
-            let mut devices: Vec<Device> = Vec::from_iter(contents.generators.iter().map(|material| Device::Generator(material.clone())));
-            let mut microchips = contents.microchips.iter().map(|material| Device::Microchip(material.clone())).collect();
-            devices.append(&mut microchips);
-            let mut actions: Vec<Vec<Device>> = devices.iter().map(|device| Vec::from([device.clone()])).collect();
-            actions.append(&mut devices.into_iter().combinations(2).collect_vec());
-            actions
+        fn floor_is_empty(&self, floor: u8) -> bool {
+            self.pairs.iter().all(|pair| pair.generator != floor && pair.microchip != floor)
         }
 
-        fn next_valid_facilities(&self, next_actions: Vec<Vec<Device>>) -> Vec<Facility> {
-            let mut next_valid_facilities: Vec<Facility> = Vec::new();
-            let mut next_floors = Vec::new();
-            if self.elevator_floor != self.bottom_floor { next_floors.push(self.elevator_floor - 1); }
-            if self.elevator_floor != self.top_floor { next_floors.push(self.elevator_floor + 1); }
-            next_actions.iter().for_each(|devices| {
-                let mut removed_floor_contents = self.floor_contents.clone();
-                removed_floor_contents.entry(self.elevator_floor).and_modify(|contents| contents.remove_devices(devices));
-                for floor in next_floors.iter() {
-                    let mut floor_contents = removed_floor_contents.clone();
-                    floor_contents.entry(*floor).and_modify(|contents| contents.add_devices(devices));
-                    let next_facility = Facility {
-                        bottom_floor: self.bottom_floor,
-                        top_floor: self.top_floor,
-                        elevator_floor: *floor,
-                        floor_contents,
-                    };
-                    if next_facility.is_valid_state() {
-                        next_valid_facilities.push(next_facility);
+        fn next_facilities(&self) -> Vec<Facility> {
+            let devices: Vec<(usize, Device)> = self.pairs.iter()
+                .enumerate()
+                .flat_map(|(idx, pair)| [
+                    (pair.generator == self.elevator).then_some((idx, Device::Generator)),
+                    (pair.microchip == self.elevator).then_some((idx, Device::Microchip)),
+                ])
+                .flatten()
+                .collect();
+            let loads = devices.iter()
+                .map(|device| vec![*device])
+                .chain(devices.iter().copied().combinations(2));
+            let mut floors = Vec::new();
+            if self.elevator + 1 < NUM_FLOORS { floors.push(self.elevator + 1); }
+            // Nothing below needs bringing up once those floors are empty.
+            if (0..self.elevator).any(|floor| !self.floor_is_empty(floor)) { floors.push(self.elevator - 1); }
+            loads.cartesian_product(floors)
+                .map(|(load, floor)| {
+                    let mut next = self.clone();
+                    next.elevator = floor;
+                    for (idx, device) in load {
+                        match device {
+                            Device::Generator => next.pairs[idx].generator = floor,
+                            Device::Microchip => next.pairs[idx].microchip = floor,
+                        }
                     }
+                    next.pairs.sort();
+                    next
+                })
+                .filter(|next| next.is_safe())
+                .collect()
+        }
+
+        /// The fewest elevator trips needed to bring everything to the top
+        /// floor, found by a breadth-first search over canonical states.
+        pub fn min_steps(&self) -> usize {
+            assert!(self.is_safe(), "Starting state should not fry any microchips.");
+            let mut visited: HashSet<u64> = HashSet::from([self.key()]);
+            let mut frontier = vec![self.clone()];
+            let mut steps = 0;
+            while !frontier.is_empty() {
+                if frontier.iter().any(|facility| facility.is_finished()) {
+                    return steps;
                 }
-            });
-            next_valid_facilities
-        }
-
-        fn next_facilities(&self) -> Vec<Facility> {
-            self.next_valid_facilities(self.next_actions())
+                frontier = frontier.iter()
+                    .flat_map(|facility| facility.next_facilities())
+                    .filter(|next| visited.insert(next.key()))
+                    .collect();
+                steps += 1;
+            }
+            panic!("Explored all states without finding solution.");
         }
     }
 
-    #[derive(Debug, PartialEq, Eq)]
-    struct State {
-        steps: usize,
-        facility: Facility,
-    }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
 
-    impl State {
-        fn is_end_state(&self) -> bool {
-            self.facility.is_end_state()
-        }
-    }
-    
-    impl Ord for State {
-        fn cmp(&self, other: &Self) -> Ordering {
-            other.steps.cmp(&self.steps)
-                .then_with(|| self.facility.cmp(&other.facility))
+        #[test]
+        fn materials_are_interchangeable() {
+            let mut facility = Facility::default();
+            facility.add_pair(1, 0);
+            facility.add_pair(2, 0);
+            let mut swapped = Facility::default();
+            swapped.add_pair(2, 0);
+            swapped.add_pair(1, 0);
+            assert_eq!(facility.key(), swapped.key());
+            assert_eq!(facility.key(), 0b00_01_00_10_00);
         }
     }
+}
 
-    impl PartialOrd for State {
-        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-            Some(self.cmp(other))
-        }
-    }
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Facility;
 
     #[derive(Debug, Default)]
-    pub struct Soln {
-        starting_facility: Facility,
-    }
+    pub struct Soln {}
 
     impl Solution for Soln {
-        fn solve(&mut self, filename: &str) -> Answer{
-            self.parse_input_file(filename);
-            Answer::Usize(self.min_steps())
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::Usize(Facility::from_file(filename).min_steps())
         }
     }
 
-    impl Soln {
-        fn parse_input_file(&mut self, filename: &str) {
-            let line_re = Regex::new(r"The (?<floor>[a-z]+) floor contains (?<contents>.*)\.").unwrap();
-            let device_re = Regex::new(r"a (?<material>[a-z]+)(?<device_type>( generator)|(\-compatible microchip))").unwrap();
-            io_utils::file_to_lines(filename).for_each(|line| {
-                let captures = line_re.captures(&line).unwrap();
-                let floor: u8 = match captures.name("floor").unwrap().as_str() {
-                    "first" => 1,
-                    "second" => 2,
-                    "third" => 3,
-                    "fourth" => 4,
-                    _ => panic!("Unrecognized floor."),
-                };
-                let contents = captures.name("contents").unwrap().as_str();
-                if contents == "nothing relevant" { 
-                    self.starting_facility.floor_contents.insert(floor, FloorContents::default());
-                    return;
-                }
-                device_re.captures_iter(contents).for_each(|captures| {
-                    let material = captures.name("material").unwrap().as_str();
-                    let device_type = captures.name("device_type").unwrap().as_str();
-                    let device = match device_type {
-                        " generator" => Device::Generator(material.to_string()),
-                        "-compatible microchip" => Device::Microchip(material.to_string()),
-                        _ => panic!("Unrecognized device type."),
-                    };
-                    self.starting_facility
-                        .floor_contents
-                        .entry(floor)
-                        .or_insert(FloorContents::default())
-                        .add_devices(&vec![device]);
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
 
-                });
-            });
+        #[test_case(1, Answer::Usize(11); "example_1")]
+        #[test_case(2, Answer::Usize(15); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
         }
+    }
+}
 
-        fn min_steps(&self) -> usize {
-            let mut visited: HashSet<Facility> = HashSet::new();
-            let mut pq = BinaryHeap::from([State { steps: 0, facility: self.starting_facility.clone() }]);
-            while !pq.is_empty() {
-                let state = pq.pop().unwrap();
-                if visited.contains(&state.facility) { continue; }
-                if state.is_end_state() { 
-                    return state.steps;
-                }
-                for facility in state.facility.next_facilities() {
-                    let new_state = State { steps: state.steps + 1, facility };
-                    pq.push(new_state);
-                }
-                visited.insert(state.facility);
-            }
-            panic!("Explored all states without finding solution.");
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Facility;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {}
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let mut facility = Facility::from_file(filename);
+            // An elerium and a dilithium generator and microchip, all on the
+            // first floor.
+            facility.add_pair(0, 0);
+            facility.add_pair(0, 0);
+            Answer::Usize(facility.min_steps())
         }
     }
 
@@ -223,7 +233,7 @@ pub mod part_one {
         use super::*;
         use super::super::DAY;
 
-        #[test_case(1, Answer::Usize(11); "example_1")]
+        #[test_case(2, Answer::Usize(39); "example_2")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
             test_utils::check_example_case(
                 &mut Soln::default(),
@@ -232,5 +242,5 @@ pub mod part_one {
                 &DAY,
             );
         }
-    }    
-}
\ No newline at end of file
+    }
+}
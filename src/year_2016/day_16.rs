@@ -48,33 +48,31 @@ mod utils {
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::DragonChecksum;
 
     #[derive(Debug)]
     pub struct Soln {
-        dragon_checksum: DragonChecksum,
+        disk_len: usize,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_disk_len(272)
+            Self { disk_len: 272 }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.dragon_checksum.parse_input_file(filename);
-            Answer::String(self.dragon_checksum.checksum(self.dragon_checksum.generate_data()))
+            let mut dragon_checksum = DragonChecksum::with_disk_len(self.disk_len);
+            dragon_checksum.parse_input_file(filename);
+            Answer::String(dragon_checksum.checksum(dragon_checksum.generate_data()))
         }
-    }
 
-    impl Soln { 
-        fn with_disk_len(disk_len: usize) -> Self {
-            Self {
-                dragon_checksum: DragonChecksum::with_disk_len(disk_len),
-            }
+        solution_params! {
+            disk_len: "Length of the disk to fill",
         }
     }
 
@@ -87,8 +85,9 @@ pub mod part_one {
 
         #[test_case(1, 20, Answer::String("01100".to_string()); "example_1")]
         fn examples_are_correct(example_key: u8, disk_len: usize, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_disk_len(disk_len),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("disk_len", disk_len.to_string())],
                 example_key,
                 answer,
                 &DAY,
@@ -98,27 +97,31 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::DragonChecksum;
 
     #[derive(Debug)]
     pub struct Soln {
-        dragon_checksum: DragonChecksum,
+        disk_len: usize,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self {
-                dragon_checksum: DragonChecksum::with_disk_len(35_651_584),
-            }
+            Self { disk_len: 35_651_584 }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.dragon_checksum.parse_input_file(filename);
-            Answer::String(self.dragon_checksum.checksum(self.dragon_checksum.generate_data()))
+            let mut dragon_checksum = DragonChecksum::with_disk_len(self.disk_len);
+            dragon_checksum.parse_input_file(filename);
+            Answer::String(dragon_checksum.checksum(dragon_checksum.generate_data()))
+        }
+
+        solution_params! {
+            disk_len: "Length of the disk to fill",
         }
     }
 }
\ No newline at end of file
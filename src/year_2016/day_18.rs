@@ -4,10 +4,11 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2016, day: 18 };
 
 mod utils {
-    use crate::utils::io_utils;
+    use crate::utils::{automaton::{Automaton, Bounds, Neighborhood, Position}, io_utils};
 
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
     enum Tile {
+        #[default]
         Safe,
         Trap,
     }
@@ -23,109 +24,90 @@ mod utils {
     }
 
     /*
-    TODO: there are at least two potential optimizations we could do here.
-    Neither is necessary for the parameters of this problem though.
-
-    Memory optimization: only track what we actually need:
-        - How many rows we've processed
-        - The running tally of safe tiles
-        - The most recent row
-
-    Runtime optimization (maybe): Search for cycles. There are 2^row_length
-    possible combinations, so the larger the row gets the less likely we are to run
-    into cycles. As soon as we found a repeat row, we could know the rest of the
-    rows and how many safe tiles they had. We'd need a hashmap of the row vec to
-    the row index, and a running vec of number of safe tiles in each row.
+    Each row is one generation of a one-dimensional automaton whose walls are
+    always safe, so only the most recent row is kept alongside a running tally
+    of safe tiles.
+
+    TODO: a potential runtime optimization (not necessary for the parameters of
+    this problem): search for cycles. There are 2^row_length possible rows, so
+    the larger the row gets the less likely we are to run into cycles. As soon
+    as we found a repeat row, we could know the rest of the rows and how many
+    safe tiles they had.
     */
     #[derive(Debug)]
     pub struct Room {
-        tiles: Vec<Vec<Tile>>,
+        tiles: Option<Automaton<Tile>>,
         rows: usize,
+        safe_tiles: usize,
     }
 
     impl Room {
         pub fn with_rows(rows: usize) -> Self {
             Self {
-                tiles: Vec::new(),
+                tiles: None,
                 rows,
+                safe_tiles: 0,
             }
         }
 
         pub fn parse_input_file(&mut self, filename: &str) {
-            self.tiles.push(io_utils::file_to_string(filename).chars().map(|ch| Tile::from_char(ch)).collect());
-        }
-
-        fn add_row(&mut self) {
-            let mut new_row = Vec::new();
-            // Leftmost tile considers first two, assumes left wall is safe.
-            new_row.push(
-                match self.tiles[self.tiles.len() - 1][..2] {
-                    [_, Tile::Trap] => Tile::Trap,
-                    _ => Tile::Safe,
-                }
-            );
-            // Middle section all considers windows of 3.
-            new_row.append(&mut 
-                self.tiles[self.tiles.len() - 1].windows(3).map(|window| {
-                    match *window {
-                        [Tile::Trap, _, Tile::Safe] | [Tile::Safe, _, Tile::Trap] => Tile::Trap,
-                        _ => Tile::Safe,
-                    }
-                }).collect()                
+            let first_row: Vec<Tile> = io_utils::file_to_string(filename).chars().map(Tile::from_char).collect();
+            // A tile is a trap exactly when the tiles to its left and right differ.
+            let mut tiles = Automaton::new(
+                Neighborhood::Window(1),
+                Bounds::FixedEdge { width: first_row.len(), height: 1, edge: Tile::Safe },
+                |_tile, neighbors| {
+                    if neighbors[0] != neighbors[2] { Tile::Trap } else { Tile::Safe }
+                },
             );
-            // Rightmost tile considers last two, assumes right wall is safe.
-            new_row.push(
-                match self.tiles[self.tiles.len() - 1][self.tiles[self.tiles.len() - 1].len() - 2..] {
-                    [Tile::Trap, _] => Tile::Trap,
-                    _ => Tile::Safe,
-                }
-            );
-            self.tiles.push(new_row);
+            first_row.into_iter().enumerate().for_each(|(col, tile)| {
+                tiles.set(Position::new(col as isize, 0), tile);
+            });
+            self.tiles = Some(tiles);
         }
 
         pub fn complete_rows(&mut self) {
-            while self.tiles.len() < self.rows {
-                self.add_row();
+            let tiles = self.tiles.as_mut().unwrap();
+            self.safe_tiles = tiles.count(Tile::Safe);
+            for _ in 1..self.rows {
+                tiles.step();
+                self.safe_tiles += tiles.count(Tile::Safe);
             }
         }
 
         pub fn safe_tiles(&self) -> usize {
-            self.tiles.iter().map(|row| {
-                row.iter().filter(|tile| **tile == Tile::Safe).count()
-            }).sum()
+            self.safe_tiles
         }
     }
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Room;
 
     #[derive(Debug)]
     pub struct Soln {
-        room: Room,
+        rows: usize,
     }
     
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_rows(40)
+            Self { rows: 40 }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.room.parse_input_file(filename);
-            self.room.complete_rows();
-            Answer::Usize(self.room.safe_tiles())
+            let mut room = Room::with_rows(self.rows);
+            room.parse_input_file(filename);
+            room.complete_rows();
+            Answer::Usize(room.safe_tiles())
         }
-    }
 
-    impl Soln {
-        fn with_rows(rows: usize) -> Self {
-            Self {
-                room: Room::with_rows(rows),
-            }
+        solution_params! {
+            rows: "Number of rows of tiles",
         }
     }
 
@@ -139,8 +121,9 @@ pub mod part_one {
         #[test_case(1, 3, Answer::Usize(6); "example_1")]
         #[test_case(2, 10, Answer::Usize(38); "example_2")]
         fn examples_are_correct(example_key: u8, rows: usize, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_rows(rows),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("rows", rows.to_string())],
                 example_key,
                 answer,
                 &DAY,
@@ -150,34 +133,32 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Room;
 
     #[derive(Debug)]
     pub struct Soln {
-        room: Room,
+        rows: usize,
     }
     
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_rows(400_000)
+            Self { rows: 400_000 }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.room.parse_input_file(filename);
-            self.room.complete_rows();
-            Answer::Usize(self.room.safe_tiles())
+            let mut room = Room::with_rows(self.rows);
+            room.parse_input_file(filename);
+            room.complete_rows();
+            Answer::Usize(room.safe_tiles())
         }
-    }
 
-    impl Soln {
-        fn with_rows(rows: usize) -> Self {
-            Self {
-                room: Room::with_rows(rows),
-            }
+        solution_params! {
+            rows: "Number of rows of tiles",
         }
     }
 }
\ No newline at end of file
@@ -4,10 +4,10 @@
 /// days 12 and 23 (and maybe 25)).
 pub mod assembunny_computer {
     use std::collections::HashMap;
+    use std::str::FromStr;
 
-    use regex::Regex;
-
-    use crate::utils::io_utils;
+    use crate::scan;
+    use crate::utils::parse::{self, ParseError};
 
     #[derive(Debug, Default)]
     pub struct AssembunnyComputer {
@@ -26,44 +26,7 @@ pub mod assembunny_computer {
         }
 
         pub fn parse_input_file(&mut self, filename: &str) {
-            let re = Regex::new(r"(?<instruction>(cpy)|(inc)|(dec)|(jnz)|(tgl)) (?<x>[a-d]|\-?\d+)( (?<y>[a-d]|(\-?\d+)))?").unwrap();
-            self.instructions = io_utils::file_to_lines(filename).map(|line| {
-                let captures = re.captures(&line).unwrap();
-                let instruction = captures.name("instruction").unwrap().as_str();
-                match instruction {
-                    "cpy" => {
-                        let x = captures.name("x").unwrap().as_str();
-                        let res = x.parse();
-                        let x = if res.is_ok() { Argument::Value(res.unwrap()) } else { Argument::Register(x.chars().next().unwrap()) };
-                        let y = captures.name("y").unwrap().as_str().chars().next().unwrap();
-                        Instruction::Cpy(x, Argument::Register(y))
-                    },
-                    "inc" => {
-                        let register = captures.name("x").unwrap().as_str().chars().next().unwrap();
-                        Instruction::Inc(Argument::Register(register))
-                    },
-                    "dec" => {
-                        let register = captures.name("x").unwrap().as_str().chars().next().unwrap();
-                        Instruction::Dec(Argument::Register(register))                        
-                    },
-                    "jnz" => {
-                        let x = captures.name("x").unwrap().as_str();
-                        let res = x.parse();
-                        let x = if res.is_ok() { Argument::Value(res.unwrap()) } else { Argument::Register(x.chars().next().unwrap()) };
-                        let y = captures.name("y").unwrap().as_str();
-                        let res = y.parse();
-                        let y = if res.is_ok() { Argument::Value(res.unwrap()) } else { Argument::Register(y.chars().next().unwrap()) };
-                        Instruction::Jnz(x, y)
-                    },
-                    "tgl" => {
-                        let x = captures.name("x").unwrap().as_str();
-                        let res = x.parse();
-                        let x = if res.is_ok() { Argument::Value(res.unwrap()) } else { Argument::Register(x.chars().next().unwrap()) };
-                        Instruction::Tgl(x)
-                    },
-                    _ => panic!("Unrecognized instruction."),
-                }
-            }).collect();
+            self.instructions = parse::file_to_parsed_lines(filename);
         }
 
         fn execute(&mut self, instruction: Instruction) {
@@ -135,6 +98,21 @@ pub mod assembunny_computer {
         Tgl(Argument),
     }
 
+    impl FromStr for Instruction {
+        type Err = ParseError;
+
+        fn from_str(line: &str) -> Result<Self, Self::Err> {
+            match line.split(' ').next() {
+                Some("cpy") => scan!(line, "cpy {} {}", Argument, Argument).map(|(x, y)| Self::Cpy(x, y)),
+                Some("inc") => scan!(line, "inc {}", Argument).map(|(x,)| Self::Inc(x)),
+                Some("dec") => scan!(line, "dec {}", Argument).map(|(x,)| Self::Dec(x)),
+                Some("jnz") => scan!(line, "jnz {} {}", Argument, Argument).map(|(x, y)| Self::Jnz(x, y)),
+                Some("tgl") => scan!(line, "tgl {}", Argument).map(|(x,)| Self::Tgl(x)),
+                _ => Err(ParseError::new(1, String::from("unrecognized instruction"))),
+            }
+        }
+    }
+
     impl Instruction {
         fn toggle(&mut self) {
             *self = match self {
@@ -151,4 +129,19 @@ pub mod assembunny_computer {
         Value(i64),
         Register(char),
     }
+
+    impl FromStr for Argument {
+        type Err = String;
+
+        /// Parses either a literal value or a register name (`a` through `d`).
+        fn from_str(arg: &str) -> Result<Self, Self::Err> {
+            if let Ok(val) = arg.parse() {
+                return Ok(Self::Value(val));
+            }
+            match arg.chars().collect::<Vec<char>>()[..] {
+                [register @ 'a'..='d'] => Ok(Self::Register(register)),
+                _ => Err(String::from("expected a value or a register")),
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -3,6 +3,15 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2017, day: 7 };
 
+use crate::utils::dot::Graph;
+
+/// Returns the program tower as a graph from each program to the programs it
+/// is holding, with each program's own `weight` and its `tower_weight`
+/// (including everything it holds) as node attributes.
+pub fn graph(filename: &str) -> Graph {
+    part_two::Soln::default().graph(filename)
+}
+
 mod utils {
     use regex::Regex;
     use std::collections::HashMap;
@@ -133,7 +142,8 @@ pub mod part_one {
 
 pub mod part_two {
     use std::collections::HashMap;
-    use crate::utils::solution::{Solution, Answer};
+    use itertools::Itertools;
+    use crate::utils::{dot::Graph, solution::{Solution, Answer}};
     use super::utils::{self, Program, Year2017Day07Solution};
 
     #[derive(Default)]
@@ -171,6 +181,26 @@ pub mod part_two {
     }
 
     impl Soln {
+        pub(super) fn graph(&mut self, filename: &str) -> Graph {
+            utils::parse_input_file(self, filename);
+            let mut weights_incl_holding = HashMap::new();
+            self.weight_incl_holding(&mut weights_incl_holding, utils::base_program(self));
+            let mut graph = Graph::directed("tower");
+            self.programs.keys()
+                .sorted()
+                .for_each(|name| {
+                    let program = &self.programs[name];
+                    let node = graph.node(name)
+                        .attr("weight", program.individual_weight)
+                        .attr("tower_weight", weights_incl_holding[name]);
+                    if program.held_by.is_none() {
+                        node.attr("shape", "box");
+                    }
+                    program.holding.iter().for_each(|held| { graph.edge(name, held); });
+                });
+            graph
+        }
+
         fn weight_incl_holding(
             &self,
             weights_including_holding: &mut HashMap<String, u32>,
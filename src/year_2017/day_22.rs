@@ -3,10 +3,12 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2017, day: 22 };
 
+use crate::utils::visualize::Visualize;
+
 mod utils {
     use std::collections::HashMap;
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, visualize::{Colour, Frame, Visualize}};
 
     #[derive(Debug, PartialEq, Eq)]
     pub enum Part {
@@ -86,6 +88,7 @@ mod utils {
         direction: Direction,
         infected: HashMap<Point, NodeStatus>,
         bursts_causing_infection: u32,
+        bursts_done: u32,
         bursts: u32,
         part: Part,
     }
@@ -97,6 +100,7 @@ mod utils {
                 direction: Direction::default(),
                 infected: HashMap::default(),
                 bursts_causing_infection: 0,
+                bursts_done: 0,
                 bursts,
                 part,
             }
@@ -131,7 +135,7 @@ mod utils {
         }
 
         pub fn burst_all(&mut self) {
-            for _ in 0..self.bursts {
+            while self.bursts_done < self.bursts {
                 self.burst();
             }
         }
@@ -169,6 +173,7 @@ mod utils {
                 },
             };
             self.forward();
+            self.bursts_done += 1;
         }
 
         pub fn bursts_causing_infection(&self) -> u32 {
@@ -189,6 +194,85 @@ mod utils {
             self.position.forward(&self.direction);
         }
     }
+
+    impl Visualize for VirusCarrier {
+        /// Draws the nodes around the infected ones and the carrier in the
+        /// puzzle's notation, with the carrier's node in brackets.
+        fn frame(&self) -> Frame {
+            let points = self.infected.keys().chain([&self.position]);
+            let min_x = points.clone().map(|pt| pt.x).min().unwrap();
+            let max_x = points.clone().map(|pt| pt.x).max().unwrap();
+            let min_y = points.clone().map(|pt| pt.y).min().unwrap();
+            let max_y = points.map(|pt| pt.y).max().unwrap();
+            let mut frame = Frame::new(&format!(
+                "After {} bursts, {} caused an infection:",
+                self.bursts_done,
+                self.bursts_causing_infection,
+            ));
+            for y in min_y..=max_y {
+                let mut row = Vec::new();
+                for x in min_x..=max_x + 1 {
+                    row.push(match (self.position.y == y, self.position.x) {
+                        (true, carrier_x) if carrier_x == x => '[',
+                        (true, carrier_x) if carrier_x == x - 1 => ']',
+                        _ => ' ',
+                    });
+                    if x > max_x { break; }
+                    row.push(match self.infected.get(&Point { x, y }) {
+                        None => '.',
+                        Some(NodeStatus::Weakened) => 'W',
+                        Some(NodeStatus::Infected) => '#',
+                        Some(NodeStatus::Flagged) => 'F',
+                    });
+                }
+                frame.push_row(row);
+            }
+            frame
+        }
+
+        fn step(&mut self) -> bool {
+            if self.bursts_done == self.bursts { return false; }
+            self.burst();
+            true
+        }
+
+        fn colour(&self, ch: char) -> Colour {
+            match ch {
+                '[' | ']' => Colour::Green,
+                '#' => Colour::Red,
+                'W' => Colour::Yellow,
+                'F' => Colour::Magenta,
+                _ => Colour::Background,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{self, InputFileType};
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn frames_match_example() {
+            let mut virus_carrier = VirusCarrier::new(1, Part::One);
+            virus_carrier.parse_input_file(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            let rows = |frame: Frame| -> Vec<String> {
+                frame.rows().iter().map(|row| row.iter().collect()).collect()
+            };
+            assert_eq!(rows(virus_carrier.frame()), vec![" . . # ", " #[.]. "]);
+            assert!(virus_carrier.step());
+            assert_eq!(rows(virus_carrier.frame()), vec![" . . # ", "[#]# . "]);
+            assert!(!virus_carrier.step());
+        }
+    }
+}
+
+/// Returns the virus carrier's simulation for part one, for animating.
+pub fn simulation(filename: &str) -> Box<dyn Visualize> {
+    let mut virus_carrier = utils::VirusCarrier::new(10_000, utils::Part::One);
+    virus_carrier.parse_input_file(filename);
+    Box::new(virus_carrier)
 }
 
 pub mod part_one {
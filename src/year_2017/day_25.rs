@@ -8,7 +8,7 @@ pub mod part_one {
 
     use regex::Regex;
 
-    use crate::utils::{solution::{Solution, Answer}, io_utils};
+    use crate::utils::{solution::{Solution, Answer}, io_utils::Sections};
 
     #[derive(Debug, PartialEq, Eq, Default)]
     struct Instructions {
@@ -17,6 +17,37 @@ pub mod part_one {
         continuation_state: char,
     }
 
+    impl Instructions {
+        /// Parses the block of four lines describing what to do when the
+        /// current value is `current_value`.
+        fn from_lines(lines: &[&str], current_value: bool) -> Self {
+            let current_value_re = Regex::new(r"  If the current value is (?<val>[01]):").unwrap();
+            let write_re = Regex::new(r"    - Write the value (?<val>[01]).").unwrap();
+            let move_re = Regex::new(r"    - Move one slot to the (?<dir>right|left).").unwrap();
+            let continuation_state_re = Regex::new(r"    - Continue with state (?<state>[A-Z]).").unwrap();
+
+            let captures = current_value_re.captures(lines[0]).unwrap();
+            let val: u8 = captures.name("val").unwrap().as_str().parse().unwrap();
+            assert_eq!(val != 0, current_value, "Instructions should be listed for 0 then 1.");
+            let captures = write_re.captures(lines[1]).unwrap();
+            let val: u8 = captures.name("val").unwrap().as_str().parse().unwrap();
+            let write_value = val != 0;
+            let captures = move_re.captures(lines[2]).unwrap();
+            let move_direction = match captures.name("dir").unwrap().as_str() {
+                "right" => 1,
+                "left" => -1,
+                _ => panic!("Unknown direction."),
+            };
+            let captures = continuation_state_re.captures(lines[3]).unwrap();
+            let continuation_state = captures.name("state").unwrap().as_str().chars().next().unwrap();
+            Self {
+                write_value,
+                move_direction,
+                continuation_state,
+            }
+        }
+    }
+
     #[derive(Debug, PartialEq, Eq, Default)]
     struct StateInstructions {
         zero: Instructions,
@@ -47,61 +78,28 @@ pub mod part_one {
             let start_state_re = Regex::new(r"Begin in state (?<state>[A-Z]).").unwrap();
             let steps_re = Regex::new(r"Perform a diagnostic checksum after (?<steps>\d+) steps.").unwrap();
             let state_re = Regex::new(r"In state (?<state>[A-Z]):").unwrap();
-            let current_value_re = Regex::new(r"  If the current value is (?<val>[01]):").unwrap();
-            let write_re = Regex::new(r"    - Write the value (?<val>[01]).").unwrap();
-            let move_re = Regex::new(r"    - Move one slot to the (?<dir>right|left).").unwrap();
-            let continuation_state_re = Regex::new(r"    - Continue with state (?<state>[A-Z]).").unwrap();
-            let mut state: Option<char> = None;
-            let mut current_value: Option<bool> = None;
 
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    if let Some(captures) = start_state_re.captures(&line) {
-                        self.state = captures.name("state").unwrap().as_str().chars().next().unwrap();
-                    } else if let Some(captures) = steps_re.captures(&line) {
-                        self.steps = captures.name("steps").unwrap().as_str().parse().unwrap();
-                    } else if let Some(captures) = state_re.captures(&line) {
-                        let st = captures.name("state").unwrap().as_str().chars().next().unwrap();
-                        self.all_instructions.insert(st, StateInstructions::default());
-                        state = Some(st);
-                    } else if let Some(captures) = current_value_re.captures(&line) {
-                        let val: u8 = captures.name("val").unwrap().as_str().parse().unwrap();
-                        current_value = Some(val != 0);
-                    } else if let Some(captures) = write_re.captures(&line) {
-                        let val: u8 = captures.name("val").unwrap().as_str().parse().unwrap();
-                        let val = val != 0;
-                        self.all_instructions.entry(state.unwrap())
-                            .and_modify(|state_instructions| {
-                                match current_value.unwrap() {
-                                    true => state_instructions.one.write_value = val,
-                                    false => state_instructions.zero.write_value = val,
-                                }
-                            });
-                    } else if let Some(captures) = move_re.captures(&line) {
-                        let dir = captures.name("dir").unwrap().as_str();
-                        let val: isize = match dir {
-                            "right" => 1,
-                            "left" => -1,
-                            _ => panic!("Unknown direction."),
-                        };
-                        self.all_instructions.entry(state.unwrap())
-                            .and_modify(|state_instructions| {
-                                match current_value.unwrap() {
-                                    true => state_instructions.one.move_direction = val,
-                                    false => state_instructions.zero.move_direction = val,
-                                }
-                            });
-                    } else if let Some(captures) = continuation_state_re.captures(&line) {
-                        let continuation_state = captures.name("state").unwrap().as_str().chars().next().unwrap();
-                        self.all_instructions.entry(state.unwrap())
-                            .and_modify(|state_instructions| {
-                                match current_value.unwrap() {
-                                    true => state_instructions.one.continuation_state = continuation_state,
-                                    false => state_instructions.zero.continuation_state = continuation_state,
-                                }
-                            });
-                    }
-                });
+            let sections = Sections::from_file_any(filename);
+            let mut preamble = sections.nth(0).lines();
+            let captures = start_state_re.captures(preamble.next().unwrap()).unwrap();
+            self.state = captures.name("state").unwrap().as_str().chars().next().unwrap();
+            let captures = steps_re.captures(preamble.next().unwrap()).unwrap();
+            self.steps = captures.name("steps").unwrap().as_str().parse().unwrap();
+
+            self.all_instructions = sections.iter()
+                .skip(1)
+                .map(|section| {
+                    let lines: Vec<&str> = section.lines().collect();
+                    assert_eq!(lines.len(), 9, "State starting on line {} should have 9 lines.", section.first_line());
+                    let captures = state_re.captures(lines[0]).unwrap();
+                    let state = captures.name("state").unwrap().as_str().chars().next().unwrap();
+                    let state_instructions = StateInstructions {
+                        zero: Instructions::from_lines(&lines[1..5], false),
+                        one: Instructions::from_lines(&lines[5..9], true),
+                    };
+                    (state, state_instructions)
+                })
+                .collect();
         }
 
         fn step(&mut self) {
@@ -295,29 +295,29 @@ pub mod knot_hasher {
 }
 
 /// A generic implementation of a map with keys mapping to sets of keys. Required
-/// for solutions to days 12 and 14.
-// TODO: add more documentation? add more doctests?
+/// for solutions to days 12 and 14. Backed by a
+/// [`DisjointSet`](crate::utils::disjoint_set::DisjointSet).
 pub mod map_of_groups {
-    use std::cell::RefCell;
     use std::fmt::{Display, Debug};
     use std::hash::Hash;
-    use std::rc::Rc;
-    use std::collections::{HashSet, HashMap};
+
+    use crate::utils::disjoint_set::DisjointSet;
 
     #[derive(PartialEq, Eq, Debug)]
-    struct Group<T>
+    pub struct MapOfGroups<T> 
     where
         T: Display + Debug + PartialEq + Eq + Hash + Clone + Copy
     {
-        members: HashSet<T>,
+        groups: DisjointSet<T>,
     }
 
-    #[derive(PartialEq, Eq, Debug, Default)]
-    pub struct MapOfGroups<T> 
+    impl<T> Default for MapOfGroups<T>
     where
         T: Display + Debug + PartialEq + Eq + Hash + Clone + Copy
     {
-        groups: HashMap<T, Rc<RefCell<Group<T>>>>,
+        fn default() -> Self {
+            Self { groups: DisjointSet::default() }
+        }
     }
 
     impl<T> MapOfGroups<T> 
@@ -327,69 +327,30 @@ pub mod map_of_groups {
         /// Adds `member` to the map, merging it with the other groups specified
         /// by `mergees` (a vector of other existing or future keys in the map).
         pub fn add_member(&mut self, member: T, mergees: Vec<T>) {
-            self.groups.insert(
-                member,
-                Rc::new(RefCell::new(Group { members: HashSet::from([member]) })),
-            );
-            // TODO: decide which group to merge into the other?
-            mergees.iter().for_each(|mergee| {
-                if *mergee == member {
-                    self.groups.get(&member).unwrap().borrow_mut().members.insert(*mergee);
-                } else {
-                    match self.groups.remove(mergee) {
-                        None => {
-                            self.groups.get(&member).unwrap().borrow_mut().members.insert(*mergee);
-                        },
-                        Some(mergee_group) => {        
-                            let member_group = self.groups.remove(&member).unwrap();
-                            self.groups.insert(member, Rc::clone(&mergee_group));
-                            if member_group != mergee_group {
-                                mergee_group.borrow_mut().members.insert(member);
-                                mergee_group.borrow_mut().members.insert(*mergee);
-                                mergee_group.borrow_mut().members.extend(member_group.borrow().members.iter());
-                                for mem in member_group.borrow().members.iter() {
-                                    self.groups.insert(*mem, Rc::clone(&mergee_group));
-                                }
-                            }
-                            self.groups.insert(*mergee, mergee_group);
-                        },
-                    }    
-                }
+            self.groups.make_set(member);
+            mergees.into_iter().for_each(|mergee| {
+                self.groups.union(member, mergee);
             });
         }
 
         /// Returns the length of the specified `member`'s group.
         pub fn group_len(&self, member: T) -> usize {
             self.groups
-                .get(&member)
+                .component_size(&member)
                 .expect("Program should exist.")
-                .borrow()
-                .members
-                .len()
         }
 
         /// Returns the number of groups in the map.
         pub fn groups(&self) -> u32 {
-            let mut counted: HashSet<T> = HashSet::new();
-            self.groups.values()
-                .map(|group| {
-                    match counted.intersection(&group.borrow().members).count() {
-                        0 => {
-                            counted.extend(&group.borrow().members);
-                            1
-                        },
-                        x => {
-                            assert_eq!(group.borrow().members.len(), x);
-                            0
-                        },
-                    }
-                })
-                .sum()
+            self.groups
+                .num_components()
+                .try_into()
+                .expect("Number of groups should fit into `u32` datatype.")
         }        
 
         /// Returns whether the map contains the specified `member`
         pub fn contains(&self, member: &T) -> bool {
-            self.groups.contains_key(member)
+            self.groups.contains(member)
         }
 
     }
@@ -400,16 +361,10 @@ pub mod map_of_groups {
 
         #[test]
         fn group_len_is_correct_int() {
-            let group = Rc::new(RefCell::new(Group { members: HashSet::from([0, 2, 3, 4]) }));
-            let map_of_groups = MapOfGroups {
-                groups: HashMap::from([
-                    (1, Rc::new(RefCell::new(Group { members: HashSet::from([1]) }))),
-                    (2, Rc::clone(&group)),
-                    (3, Rc::clone(&group)),
-                    (4, Rc::clone(&group)),
-                    (0, group),
-                ])
-            };
+            let mut map_of_groups = MapOfGroups::default();
+            map_of_groups.add_member(0, vec![2, 3]);
+            map_of_groups.add_member(1, vec![]);
+            map_of_groups.add_member(4, vec![3]);
             assert_eq!(map_of_groups.group_len(0), 4);
             assert_eq!(map_of_groups.group_len(1), 1);
             assert_eq!(map_of_groups.group_len(2), 4);
@@ -428,5 +383,15 @@ pub mod map_of_groups {
             map_of_groups.add_member(2, vec![0, 3, 4]);
             assert_eq!(map_of_groups.group_len(0), 4);
         }
+
+        #[test]
+        fn groups_is_correct_int() {
+            let mut map_of_groups = MapOfGroups::default();
+            map_of_groups.add_member(0, vec![2]);
+            map_of_groups.add_member(1, vec![1]);
+            assert_eq!(map_of_groups.groups(), 2);
+            map_of_groups.add_member(3, vec![1, 2]);
+            assert_eq!(map_of_groups.groups(), 1);
+        }
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 //! Solutions to the [2018](https://adventofcode.com/2018) puzzles.
 
+pub mod day_01;
 pub mod day_02;
 pub mod day_03;
 pub mod day_04;
@@ -0,0 +1,101 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2018, day: 1 };
+
+mod utils {
+    use crate::utils::io_utils;
+
+    pub fn frequency_changes(filename: &str) -> Vec<i32> {
+        io_utils::file_to_lines(filename)
+            .map(|line| line.parse().expect("Line should be a signed frequency change."))
+            .collect()
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::I32(utils::frequency_changes(filename).iter().sum())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::I32(3); "example_1")]
+        #[test_case(2, Answer::I32(3); "example_2")]
+        #[test_case(3, Answer::I32(0); "example_3")]
+        #[test_case(4, Answer::I32(-6); "example_4")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use std::collections::HashSet;
+
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+    }
+
+    impl Solution for Soln {
+        /// Repeats the changes until a frequency comes up a second time.
+        fn solve(&mut self, filename: &str) -> Answer {
+            let changes = utils::frequency_changes(filename);
+            let mut frequency = 0;
+            let mut seen: HashSet<i32> = HashSet::from([frequency]);
+            for change in changes.iter().cycle() {
+                frequency += change;
+                if !seen.insert(frequency) {
+                    return Answer::I32(frequency);
+                }
+            }
+            panic!("Changes should not be empty.")
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::I32(2); "example_1")]
+        #[test_case(5, Answer::I32(0); "example_5")]
+        #[test_case(6, Answer::I32(10); "example_6")]
+        #[test_case(7, Answer::I32(5); "example_7")]
+        #[test_case(8, Answer::I32(14); "example_8")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
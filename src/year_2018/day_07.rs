@@ -138,28 +138,33 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::SleighBuilder;
 
-    #[derive(Debug, Default)]
+    #[derive(Debug)]
     pub struct Soln {
-        sleigh_builder: SleighBuilder,    
+        workers: usize,
+        fixed_time: usize,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { workers: 5, fixed_time: 60 }
+        }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.sleigh_builder.parse_input_file(filename);
-            Answer::Usize(self.sleigh_builder.time())
+            let mut sleigh_builder = SleighBuilder::new(self.workers, self.fixed_time);
+            sleigh_builder.parse_input_file(filename);
+            Answer::Usize(sleigh_builder.time())
         }
-    }
 
-    impl Soln {
-        #[cfg(test)]
-        fn with_workers_and_fixed_time(workers: usize, fixed_time: usize) -> Self {
-            Self {
-                sleigh_builder: SleighBuilder::new(workers, fixed_time),
-            }
+        solution_params! {
+            workers: "Number of workers, including you",
+            fixed_time: "Seconds every step takes on top of its letter's position in the alphabet",
         }
     }
 
@@ -172,8 +177,9 @@ pub mod part_two {
 
         #[test_case(1, Answer::Usize(15); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_workers_and_fixed_time(2, 0),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("workers", "2"), ("fixed_time", "0")],
                 example_key,
                 answer,
                 &DAY,
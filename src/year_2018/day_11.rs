@@ -17,7 +17,7 @@ mod utils {
 
     use crate::utils::io_utils;
 
-    const GRID_DIMENSIONS: usize = 300;
+    pub const GRID_DIMENSIONS: usize = 300;
 
     #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
     pub struct Point {
@@ -53,6 +53,18 @@ mod utils {
             self.serial_number = io_utils::file_to_string(filename).parse().unwrap();
         }
 
+        pub fn dimensions(&self) -> usize {
+            self.dimensions
+        }
+
+        /// Changes the width and height of the grid, forgetting any power
+        /// levels already computed.
+        pub fn set_dimensions(&mut self, dimensions: usize) {
+            self.dimensions = dimensions;
+            self.power_levels.clear();
+            self.summed_area_table.clear();
+        }
+
         pub fn power_level(&mut self, point: &Point) -> i64 {
             *self.power_levels.entry(*point).or_insert({
                 let rack_id = point.x + 10;
@@ -173,6 +185,7 @@ mod utils {
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::{self, Grid};
@@ -180,20 +193,30 @@ pub mod part_one {
     #[derive(Debug)]
     pub struct Soln {
         grid: Grid,    
+        grid_size: usize,
+        window_size: usize,
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
+            self.grid.set_dimensions(self.grid_size);
             self.grid.parse_input_file(filename);
-            let (point, _total_power) = utils::search_grid(300, 3, &mut self.grid);
+            let (point, _total_power) = utils::search_grid(self.grid.dimensions(), self.window_size, &mut self.grid);
             Answer::String(format!("{}", point))
         }
+
+        solution_params! {
+            grid_size: "Width and height of the grid of fuel cells",
+            window_size: "Width and height of the squares searched",
+        }
     }
 
     impl Default for Soln {
         fn default() -> Self {
             Self {
                 grid: Grid::new(0),
+                grid_size: utils::GRID_DIMENSIONS,
+                window_size: 3,
             }
         }
     }
@@ -223,28 +246,36 @@ pub mod part_one {
  * thread of solutions.
  */
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
-    use super::utils::Grid;
+    use super::utils::{self, Grid};
 
     #[derive(Debug)]
     pub struct Soln {
         grid: Grid,    
+        grid_size: usize,
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
+            self.grid.set_dimensions(self.grid_size);
             self.grid.parse_input_file(filename);
             self.grid.build_summed_area_table();
             let (top_left, window_size) = self.grid.max_total_power_locn();
             Answer::String(format!("{},{}", top_left, window_size))
         }
+
+        solution_params! {
+            grid_size: "Width and height of the grid of fuel cells",
+        }
     }
 
     impl Default for Soln {
         fn default() -> Self {
             Self {
                 grid: Grid::new(0),
+                grid_size: utils::GRID_DIMENSIONS,
             }
         }
     }
@@ -3,10 +3,12 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2018, day: 13 };
 
+use crate::utils::visualize::Visualize;
+
 mod utils {
     use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet}, fmt::Display};
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, visualize::{Colour, Frame, Visualize}};
 
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
     enum Direction {
@@ -27,6 +29,15 @@ mod utils {
             }
         }
 
+        fn to_char(self) -> char {
+            match self {
+                Self::N => '^',
+                Self::E => '>',
+                Self::S => 'v',
+                Self::W => '<',
+            }
+        }
+
         fn turn(&mut self, turn_direction: TurnDirection) {
             *self = match (&self, turn_direction) {
                 (Direction::N, TurnDirection::Left) | (Direction::W, TurnDirection::Straight) | (Direction::S, TurnDirection::Right) => Direction::W,
@@ -120,12 +131,24 @@ mod utils {
                 _ => panic!("Unrecognized track input"),
             }
         }
+
+        fn to_char(self) -> char {
+            match self {
+                Self::NS => '|',
+                Self::EW => '-',
+                Self::NESW => '/',
+                Self::NWSE => '\\',
+                Self::Intersection => '+',
+            }
+        }
     }
 
     #[derive(Debug, Default)]
     pub struct Tracks {
         tracks: HashMap<Point, Track>,
         carts: BinaryHeap<Reverse<Cart>>,
+        ticks: usize,
+        last_collisions: HashSet<Point>,
     }
 
     impl Tracks {
@@ -171,6 +194,8 @@ mod utils {
                 !collisions.contains(&cart.0.location)
             });
             self.carts = new_carts;
+            self.ticks += 1;
+            self.last_collisions = collisions.clone();
             collisions
         }
 
@@ -222,6 +247,81 @@ mod utils {
             self.carts.pop().unwrap().0.location
         }
     }
+
+    impl Visualize for Tracks {
+        /// Draws the tracks and carts as in the puzzle's examples, with an `X`
+        /// wherever carts collided in the last tick.
+        fn frame(&self) -> Frame {
+            let max_x = self.tracks.keys().map(|pt| pt.x).max().unwrap_or(0);
+            let max_y = self.tracks.keys().map(|pt| pt.y).max().unwrap_or(0);
+            let carts: HashMap<Point, Direction> = self.carts.iter()
+                .map(|cart| (cart.0.location, cart.0.direction))
+                .collect();
+            let mut frame = Frame::new(&format!("After {} ticks, {} carts left:", self.ticks, self.carts.len()));
+            for y in 0..=max_y {
+                frame.push_row((0..=max_x).map(|x| {
+                    let pt = Point { x, y };
+                    if self.last_collisions.contains(&pt) {
+                        'X'
+                    } else if let Some(direction) = carts.get(&pt) {
+                        direction.to_char()
+                    } else {
+                        self.tracks.get(&pt).map_or(' ', |track| track.to_char())
+                    }
+                }));
+            }
+            frame
+        }
+
+        /// Ticks until at most one cart is left.
+        fn step(&mut self) -> bool {
+            if self.carts.len() <= 1 { return false; }
+            self.tick();
+            true
+        }
+
+        fn colour(&self, ch: char) -> Colour {
+            match ch {
+                '^' | '>' | 'v' | '<' => Colour::Green,
+                'X' => Colour::Red,
+                _ => Colour::Grey,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{self, InputFileType};
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn frames_match_example() {
+            let filename = io_utils::input_filename(&DAY, InputFileType::Example(1));
+            let mut tracks = Tracks::default();
+            tracks.parse_input_file(&filename);
+            let rows = |frame: &Frame| -> Vec<String> {
+                frame.rows().iter()
+                    .map(|row| row.iter().collect::<String>().trim_end().to_string())
+                    .collect()
+            };
+            let input: Vec<String> = io_utils::file_to_lines(&filename)
+                .map(|line| line.trim_end().to_string())
+                .collect();
+            assert_eq!(rows(&tracks.frame()), input);
+            tracks.step();
+            assert_eq!(rows(&tracks.frame())[0], "/-->\\");
+            tracks.first_collision_point();
+            assert_eq!(tracks.frame().rows()[3][7], 'X');
+        }
+    }
+}
+
+/// Returns the carts' simulation, for animating.
+pub fn simulation(filename: &str) -> Box<dyn Visualize> {
+    let mut tracks = utils::Tracks::default();
+    tracks.parse_input_file(filename);
+    Box::new(tracks)
 }
 
 pub mod part_one {
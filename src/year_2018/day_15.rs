@@ -185,6 +185,8 @@ mod utils {
     pub struct CombatSimulator {
         walls: HashSet<Point>,
         units: BinaryHeap<Reverse<CombatUnit>>,
+        completed_rounds: usize,
+        elf_deaths: Vec<usize>, // the round (1-indexed) in which each elf that has died was killed
     }
 
     impl CombatSimulator {
@@ -214,14 +216,34 @@ mod utils {
             });
         }
 
+        pub fn completed_rounds(&self) -> usize {
+            self.completed_rounds
+        }
+
+        /// Returns the round (1-indexed) in which the first elf died, if any
+        /// have.
+        pub fn first_elf_death(&self) -> Option<usize> {
+            self.elf_deaths.first().copied()
+        }
+
         /// Returns whether zero elves have died in the combat. Depending on argument,
-        /// short-circuits if elf dies
+        /// short-circuits (at the end of the round) if an elf dies.
         pub fn simulate_combat(&mut self, short_circuit_if_elf_dies: bool) -> bool {
-            loop {
+            while self.simulate_round() {
+                if short_circuit_if_elf_dies && !self.elf_deaths.is_empty() { break; }
+            }
+            self.elf_deaths.is_empty()
+        }
+
+        /// Gives every living unit its turn, in reading order. Returns whether
+        /// the round was completed: `false` if combat ended because a unit
+        /// found no targets, in which case the round doesn't count.
+        pub fn simulate_round(&mut self) -> bool {
+            let round = self.completed_rounds;
+            while self.units.peek().is_some_and(|unit| unit.0.round == round) {
                 let mut unit = self.units.pop().unwrap().0;
                 // Remove from units collection if the unit is dead.
                 if *unit.hit_points.borrow() == 0 {
-                    if short_circuit_if_elf_dies && unit.unit_type == CombatUnitType::Elf { return false; }
                     continue;
                 }
                 let targets = self.units.iter().filter(|u| {
@@ -229,100 +251,192 @@ mod utils {
                 });
                 // Combat ends if there are no targets
                 if targets.clone().count() == 0 {
-                    let result = unit.unit_type == CombatUnitType::Elf;
                     self.units.push(Reverse(unit));
-                    return result;
-                }
-                // If in attacking range, attack.
-                if attack(&unit, targets.clone()) {
-                    unit.round += 1;
-                    self.units.push(Reverse(unit));
-                    continue;
+                    return false;
                 }
                 // If not in attacking range, move
-                let mut obstacles = self.walls.clone();
-                self.units.iter()
-                    .filter(|u| *u.0.hit_points.borrow() > 0)
-                    .map(|u| u.0.point)
-                    .for_each(|pt| { obstacles.insert(pt); });
-                let mut destinations = HashSet::new();
-                targets.clone().for_each(|u| {
-                    adjacent_points(u.0.point, &obstacles).iter().for_each(|adj| {
-                        destinations.insert(*adj);
+                if !targets.clone().any(|u| unit.is_adjacent(&u.0)) {
+                    let mut obstacles = self.walls.clone();
+                    self.units.iter()
+                        .filter(|u| *u.0.hit_points.borrow() > 0)
+                        .map(|u| u.0.point)
+                        .for_each(|pt| { obstacles.insert(pt); });
+                    let mut destinations = HashSet::new();
+                    targets.clone().for_each(|u| {
+                        adjacent_points(u.0.point, &obstacles).iter().for_each(|adj| {
+                            destinations.insert(*adj);
+                        });
                     });
-                });
-                let mut paths_finder = PathsFinder::new(
-                    unit.point,
-                    obstacles,
-                    destinations,
-                );
-                if let Some(pt) = paths_finder.take_step() {
-                    // Attempt attack
-                    unit.point = pt;
-                    attack(&unit, self.units.iter().filter(|u| {
-                        unit.is_enemy(&u.0) && *u.0.hit_points.borrow() > 0
-                    }));
+                    let mut paths_finder = PathsFinder::new(
+                        unit.point,
+                        obstacles,
+                        destinations,
+                    );
+                    if let Some(pt) = paths_finder.take_step() {
+                        unit.point = pt;
+                    }
+                }
+                // If in attacking range, attack.
+                if let Some(target) = attack(&unit, self.units.iter().filter(|u| {
+                    unit.is_enemy(&u.0) && *u.0.hit_points.borrow() > 0
+                })) {
+                    if *target.hit_points.borrow() == 0 && target.unit_type == CombatUnitType::Elf {
+                        self.elf_deaths.push(round + 1);
+                    }
                 }
                 unit.round += 1;
                 self.units.push(Reverse(unit));
             }
+            self.completed_rounds += 1;
+            true
         }
 
         pub fn outcome(&self) -> usize {
-            self.units.iter().map(|unit| *unit.0.hit_points.borrow()).sum::<usize>()
-            * self.units.iter().filter(|unit| *unit.0.hit_points.borrow() > 0).map(|unit| unit.0.round).min().unwrap()
+            self.units.iter().map(|unit| *unit.0.hit_points.borrow()).sum::<usize>() * self.completed_rounds
         }
     }
 
     impl Display for CombatSimulator {
+        /// Renders the map with the hit points of the units on each row, in
+        /// the same format as the puzzle's examples.
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             let min_row = self.walls.iter().map(|pt| pt.row).min().unwrap();
             let max_row = self.walls.iter().map(|pt| pt.row).max().unwrap();
             let min_col = self.walls.iter().map(|pt| pt.col).min().unwrap();
             let max_col = self.walls.iter().map(|pt| pt.col).max().unwrap();
-            let units: HashMap<Point, CombatUnitType> = self.units.iter().map(|unit| {
-                (unit.0.point, unit.0.unit_type)
-            }).collect();
+            let units: HashMap<Point, &CombatUnit> = self.units.iter()
+                .filter(|unit| *unit.0.hit_points.borrow() > 0)
+                .map(|unit| (unit.0.point, &unit.0))
+                .collect();
             for row in min_row..=max_row {
+                let mut row_units = Vec::new();
                 for col in min_col..=max_col {
                     let pt = Point { row, col };
                     if self.walls.contains(&pt) {
                         write!(f, "#")?;
-                    } else if units.contains_key(&pt) {
-                        write!(f, "{}", units.get(&pt).unwrap().to_str())?;
+                    } else if let Some(unit) = units.get(&pt) {
+                        write!(f, "{}", unit.unit_type.to_str())?;
+                        row_units.push(format!("{}({})", unit.unit_type.to_str(), *unit.hit_points.borrow()));
                     } else {
                         write!(f, ".")?;
                     }
                 }
-                write!(f, "\n")?;
-            }
-            write!(f, "\n")?;
-            for unit in self.units.iter().sorted_by_key(|u| u.0.id) {
-                write!(
-                    f, 
-                    "{:02} {} at {:?}: {} hit points\n",
-                    unit.0.id,
-                    unit.0.unit_type.to_str(),
-                    unit.0.point,
-                    *unit.0.hit_points.borrow(),
-                )?;
+                if !row_units.is_empty() {
+                    write!(f, "   {}", row_units.join(", "))?;
+                }
+                writeln!(f)?;
             }
             Ok(())
         }
     }
 
-    fn attack<'a>(attacker: &CombatUnit, targets: impl Iterator<Item = &'a Reverse<CombatUnit>>) -> bool {
-        if let Some(target) = targets.filter(|u| {
+    /// Attacks the adjacent target with the fewest hit points (ties broken in
+    /// reading order), returning the target if there was one.
+    fn attack<'a>(attacker: &CombatUnit, targets: impl Iterator<Item = &'a Reverse<CombatUnit>>) -> Option<&'a CombatUnit> {
+        let target = targets.filter(|u| {
             attacker.is_adjacent(&u.0)
         }).sorted_by(|l, r| {
             l.0.hit_points.borrow().cmp(&r.0.hit_points.borrow())
                 .then_with(|| l.0.point.cmp(&r.0.point))
-        }).next() {
-            target.0.absorb_attack(attacker.attack_power);
-            return true;
+        }).next()?;
+        target.0.absorb_attack(attacker.attack_power);
+        Some(&target.0)
+    }
+
+    /// Renders the map initially and after every round until combat ends, in
+    /// the format of the puzzle's examples (frames separated by blank lines).
+    pub fn replay(filename: &str, elf_attack_power: usize) -> String {
+        let mut combat_simulator = CombatSimulator::default();
+        combat_simulator.parse_input_file(filename, elf_attack_power);
+        let mut frames = vec![format!("Initially:\n{combat_simulator}")];
+        while combat_simulator.simulate_round() {
+            let rounds = combat_simulator.completed_rounds();
+            let plural = if rounds == 1 { "" } else { "s" };
+            frames.push(format!("After {rounds} round{plural}:\n{combat_simulator}"));
         }
-        false
+        frames.push(format!(
+            "Combat ends after {} full rounds:\n{combat_simulator}",
+            combat_simulator.completed_rounds(),
+        ));
+        frames.join("\n")
+    }
+
+    /// One attempt at an elf attack power in the search for part two.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Trial {
+        pub elf_attack_power: usize,
+        pub first_elf_death: Option<usize>,
+        pub outcome: usize,
     }
+
+    /// Tries increasing elf attack powers (from 4) until no elf dies,
+    /// returning every trial. Each trial stops at the end of the round in
+    /// which the first elf died.
+    pub fn attack_power_search(filename: &str) -> Vec<Trial> {
+        let mut trials: Vec<Trial> = Vec::new();
+        for elf_attack_power in 4.. {
+            let mut combat_simulator = CombatSimulator::default();
+            combat_simulator.parse_input_file(filename, elf_attack_power);
+            combat_simulator.simulate_combat(true);
+            trials.push(Trial {
+                elf_attack_power,
+                first_elf_death: combat_simulator.first_elf_death(),
+                outcome: combat_simulator.outcome(),
+            });
+            if combat_simulator.first_elf_death().is_none() { break; }
+        }
+        trials
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{InputFileType, Sections, filename, input_filename};
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn replay_matches_example_trace() {
+            let replay = replay(&input_filename(&DAY, InputFileType::Example(1)), 3);
+            let frames: Vec<Vec<&str>> = replay.split("\n\n")
+                .map(|frame| frame.lines().collect())
+                .collect();
+            let expected = Sections::from_file_any(&filename(&DAY, "test_examples/example_1_trace.txt"));
+            for expected_frame in expected.iter() {
+                let expected_frame: Vec<&str> = expected_frame.lines().collect();
+                let frame = frames.iter()
+                    .find(|frame| frame[0] == expected_frame[0])
+                    .unwrap_or_else(|| panic!("Replay should have a frame `{}`", expected_frame[0]));
+                assert_eq!(*frame, expected_frame);
+            }
+        }
+
+        #[test]
+        fn attack_power_search_reports_trials() {
+            let trials = attack_power_search(&input_filename(&DAY, InputFileType::Example(1)));
+            assert_eq!(trials.len(), 12);
+            assert!(trials[..11].iter().all(|trial| trial.first_elf_death.is_some()));
+            assert_eq!(trials[11], Trial { elf_attack_power: 15, first_elf_death: None, outcome: 4_988 });
+        }
+    }
+}
+
+/// Renders the combat initially and after every round (see
+/// `utils::replay`), for comparing against the puzzle's example traces.
+pub fn replay(filename: &str, elf_attack_power: usize) -> String {
+    utils::replay(filename, elf_attack_power)
+}
+
+/// Describes each elf attack power tried for part two and the round in
+/// which an elf died.
+pub fn attack_power_report(filename: &str) -> String {
+    utils::attack_power_search(filename)
+        .iter()
+        .map(|trial| match trial.first_elf_death {
+            Some(round) => format!("Attack power {}: an elf died in round {round}", trial.elf_attack_power),
+            None => format!("Attack power {}: no elves died, outcome {}", trial.elf_attack_power, trial.outcome),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 pub mod part_one {
@@ -370,31 +484,24 @@ pub mod part_one {
 pub mod part_two {
     use crate::utils::solution::{Answer, Solution};
 
-    use super::utils::CombatSimulator;
+    use super::utils;
 
     #[derive(Debug, Default)]
     pub struct Soln {}
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            let mut elf_attack_power = 4;
-            loop {
-                let mut combat_simulator = CombatSimulator::default();
-                combat_simulator.parse_input_file(filename, elf_attack_power);
-                if combat_simulator.simulate_combat(true) {
-                    return Answer::Usize(combat_simulator.outcome());        
-                }
-                elf_attack_power += 1;
-            }
+            let trials = utils::attack_power_search(filename);
+            Answer::Usize(trials.last().unwrap().outcome)
         }
     }
 
     #[cfg(test)]
     mod tests {
         use test_case::test_case;
-        use crate::utils::{test_utils, solution::Answer};
+        use crate::utils::{io_utils::{self, InputFileType}, test_utils, solution::Answer};
         use super::*;
-        use super::super::DAY;
+        use super::super::{attack_power_report, DAY};
 
         #[test_case(1, Answer::Usize(4_988); "example_1")]
         #[test_case(3, Answer::Usize(31_284); "example_3")]
@@ -409,5 +516,16 @@ pub mod part_two {
                 &DAY,
             );
         }
+
+        #[test]
+        fn attack_power_report_finds_minimum_power() {
+            let report = attack_power_report(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            let lines: Vec<&str> = report.lines().collect();
+            assert_eq!(lines.len(), 12);
+            for (line, elf_attack_power) in lines[..11].iter().zip(4..) {
+                assert!(line.starts_with(&format!("Attack power {elf_attack_power}: an elf died in round ")), "{line}");
+            }
+            assert_eq!(lines[11], "Attack power 15: no elves died, outcome 4988");
+        }
     }
 }
\ No newline at end of file
@@ -3,12 +3,14 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2018, day: 17 };
 
+use crate::utils::visualize::Visualize;
+
 mod utils {
     use std::{cmp, collections::{BTreeSet, HashMap, HashSet}};
 
     use regex::Regex;
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, visualize::{Colour, Frame, Visualize}};
 
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
     struct Point {
@@ -37,6 +39,8 @@ mod utils {
         min_y: usize,
         max_y: usize,
         squares: HashMap<Point, SquareType>,
+        to_process: BTreeSet<Point>,
+        steps: usize,
     }
 
     impl Default for Reservoir {
@@ -47,6 +51,8 @@ mod utils {
                 min_y: usize::MAX,
                 max_y: usize::MIN,
                 squares: HashMap::from([(spring, SquareType::Spring)]),
+                to_process: BTreeSet::from([spring]),
+                steps: 0,
             }
         }
     }
@@ -90,64 +96,69 @@ mod utils {
         }
 
         pub fn flow_water(&mut self) {
-            let mut to_process = BTreeSet::from([self.spring]);
-            while !to_process.is_empty() {
-                let pt = to_process.pop_first().unwrap();
-                match self.squares.get(&pt) {
-                    None | Some(SquareType::FlowingWater) | Some(SquareType::Spring) => (),
-                    Some(SquareType::RestingWater) => {
-                        to_process.insert(Point { x: pt.x, y: pt.y - 1 });
-                        continue;
-                    },
-                    Some(SquareType::Clay) => {
-                        panic!("Should not be processing a clay square");
-                    },
-                }
-                let down_pt = Point { x: pt.x, y: pt.y + 1 };
-                match self.squares.get(&down_pt) {
-                    None | Some(SquareType::FlowingWater) => {
-                        self.squares.insert(down_pt, SquareType::FlowingWater);
-                        if pt.y + 1 <= self.max_y {
-                            to_process.insert(down_pt);
-                        }
-                    },
-                    Some(SquareType::Clay) | Some(SquareType::RestingWater) => {
-                        let (left_res, left_pts) = self.flow_water_horizontally(&pt, &Direction::Left);
-                        let (right_res, right_pts) = self.flow_water_horizontally(&pt, &Direction::Right);
-                        match (left_res, right_res) {
-                            (None, None) => {
-                                // Water has come to rest on both sides
-                                for pt in left_pts.into_iter() {
-                                    self.squares.insert(pt, SquareType::RestingWater);
-                                }
-                                for pt in right_pts.into_iter() {
-                                    self.squares.insert(pt, SquareType::RestingWater);
-                                }
-                                // Go back up a level if this has resulted in a new layer.
-                                to_process.insert(Point { x: pt.x, y: pt.y - 1 });
-                            },
-                            (_, _) => {
-                                for pt in left_pts.into_iter() {
-                                    self.squares.insert(pt, SquareType::FlowingWater);
-                                }
-                                for pt in right_pts.into_iter() {
-                                    self.squares.insert(pt, SquareType::FlowingWater);
-                                }
-                                // Add the overflow points for processing
-                                if let Some(left_next_pt) = left_res {
-                                    to_process.insert(left_next_pt);
-                                }
-                                if let Some(right_next_pt) = right_res {
-                                    to_process.insert(right_next_pt);
-                                }
-                            },
-                        }
-                    },
-                    Some(SquareType::Spring) => {
-                        panic!("Water should not be above spring");
+            while self.flow_water_step() {}
+        }
+
+        /// Processes the next square water has reached. Returns `false` once
+        /// there are none left.
+        fn flow_water_step(&mut self) -> bool {
+            let Some(pt) = self.to_process.pop_first() else { return false; };
+            self.steps += 1;
+            match self.squares.get(&pt) {
+                None | Some(SquareType::FlowingWater) | Some(SquareType::Spring) => (),
+                Some(SquareType::RestingWater) => {
+                    self.to_process.insert(Point { x: pt.x, y: pt.y - 1 });
+                    return true;
+                },
+                Some(SquareType::Clay) => {
+                    panic!("Should not be processing a clay square");
+                },
+            }
+            let down_pt = Point { x: pt.x, y: pt.y + 1 };
+            match self.squares.get(&down_pt) {
+                None | Some(SquareType::FlowingWater) => {
+                    self.squares.insert(down_pt, SquareType::FlowingWater);
+                    if pt.y < self.max_y {
+                        self.to_process.insert(down_pt);
+                    }
+                },
+                Some(SquareType::Clay) | Some(SquareType::RestingWater) => {
+                    let (left_res, left_pts) = self.flow_water_horizontally(&pt, &Direction::Left);
+                    let (right_res, right_pts) = self.flow_water_horizontally(&pt, &Direction::Right);
+                    match (left_res, right_res) {
+                        (None, None) => {
+                            // Water has come to rest on both sides
+                            for pt in left_pts.into_iter() {
+                                self.squares.insert(pt, SquareType::RestingWater);
+                            }
+                            for pt in right_pts.into_iter() {
+                                self.squares.insert(pt, SquareType::RestingWater);
+                            }
+                            // Go back up a level if this has resulted in a new layer.
+                            self.to_process.insert(Point { x: pt.x, y: pt.y - 1 });
+                        },
+                        (_, _) => {
+                            for pt in left_pts.into_iter() {
+                                self.squares.insert(pt, SquareType::FlowingWater);
+                            }
+                            for pt in right_pts.into_iter() {
+                                self.squares.insert(pt, SquareType::FlowingWater);
+                            }
+                            // Add the overflow points for processing
+                            if let Some(left_next_pt) = left_res {
+                                self.to_process.insert(left_next_pt);
+                            }
+                            if let Some(right_next_pt) = right_res {
+                                self.to_process.insert(right_next_pt);
+                            }
+                        },
                     }
+                },
+                Some(SquareType::Spring) => {
+                    panic!("Water should not be above spring");
                 }
             }
+            true
         }
 
         /// Returns a tuple of:
@@ -212,6 +223,45 @@ mod utils {
         }
     }
 
+    impl Visualize for Reservoir {
+        /// Draws the whole scan in the puzzle's notation, one column either
+        /// side of the clay.
+        fn frame(&self) -> Frame {
+            let min_x = self.squares.keys().map(|pt| pt.x).min().unwrap().min(self.spring.x) - 1;
+            let max_x = self.squares.keys().map(|pt| pt.x).max().unwrap().max(self.spring.x) + 1;
+            let mut frame = Frame::new(&format!(
+                "After {} steps, {} squares reached by water:",
+                self.steps,
+                self.squares_reached_by_water(),
+            ));
+            for y in 0..=self.max_y {
+                frame.push_row((min_x..=max_x).map(|x| {
+                    match self.squares.get(&Point { x, y }) {
+                        Some(SquareType::Spring) => '+',
+                        None => '.',
+                        Some(SquareType::Clay) => '#',
+                        Some(SquareType::RestingWater) => '~',
+                        Some(SquareType::FlowingWater) => '|',
+                    }
+                }));
+            }
+            frame
+        }
+
+        fn step(&mut self) -> bool {
+            self.flow_water_step()
+        }
+
+        fn colour(&self, ch: char) -> Colour {
+            match ch {
+                '#' => Colour::Yellow,
+                '~' => Colour::Blue,
+                '|' | '+' => Colour::Cyan,
+                _ => Colour::Background,
+            }
+        }
+    }
+
     impl std::fmt::Display for Reservoir {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             for y in 0..=250 {
@@ -230,6 +280,70 @@ mod utils {
             Ok(())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{self, InputFileType};
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn frames_match_example() {
+            let mut reservoir = Reservoir::default();
+            reservoir.parse_input_file(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            let rows = |frame: Frame| -> Vec<String> {
+                frame.rows().iter().map(|row| row.iter().collect()).collect()
+            };
+            assert_eq!(
+                rows(reservoir.frame()),
+                vec![
+                    "......+.......",
+                    "............#.",
+                    ".#..#.......#.",
+                    ".#..#..#......",
+                    ".#..#..#......",
+                    ".#.....#......",
+                    ".#.....#......",
+                    ".#######......",
+                    "..............",
+                    "..............",
+                    "....#.....#...",
+                    "....#.....#...",
+                    "....#.....#...",
+                    "....#######...",
+                ],
+            );
+            while reservoir.step() {}
+            let last = reservoir.frame();
+            assert!(last.caption().ends_with(", 57 squares reached by water:"), "{}", last.caption());
+            assert_eq!(
+                rows(last),
+                vec![
+                    "......+.......",
+                    "......|.....#.",
+                    ".#..#||||...#.",
+                    ".#..#~~#|.....",
+                    ".#..#~~#|.....",
+                    ".#~~~~~#|.....",
+                    ".#~~~~~#|.....",
+                    ".#######|.....",
+                    "........|.....",
+                    "...|||||||||..",
+                    "...|#~~~~~#|..",
+                    "...|#~~~~~#|..",
+                    "...|#~~~~~#|..",
+                    "...|#######|..",
+                ],
+            );
+        }
+    }
+}
+
+/// Returns the water's simulation, for animating.
+pub fn simulation(filename: &str) -> Box<dyn Visualize> {
+    let mut reservoir = utils::Reservoir::default();
+    reservoir.parse_input_file(filename);
+    Box::new(reservoir)
 }
 
 pub mod part_one {
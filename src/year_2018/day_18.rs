@@ -4,14 +4,11 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2018, day: 18 };
 
 mod utils {
-    use std::collections::{BTreeMap, HashMap};
-    
-    use itertools::iproduct;
+    use crate::utils::{automaton::{Automaton, Bounds, Neighborhood, Position}, io_utils};
 
-    use crate::utils::io_utils;
-
-    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
     pub enum Acre {
+        #[default]
         OPEN,
         TREES,
         LUMBERYARD,
@@ -26,109 +23,44 @@ mod utils {
                 _ => panic!("Unrecognized character."),
             }
         }
-    }
 
-    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-    struct Point {
-        x: isize,
-        y: isize,
+        /// Returns the acre's type after one minute, given its adjacent acres.
+        fn next(self, adjacent: &[Acre]) -> Self {
+            let adjacent_count = |acre_type: Acre| adjacent.iter().filter(|acre| **acre == acre_type).count();
+            match self {
+                Acre::OPEN if adjacent_count(Acre::TREES) >= 3 => Acre::TREES,
+                Acre::TREES if adjacent_count(Acre::LUMBERYARD) >= 3 => Acre::LUMBERYARD,
+                Acre::LUMBERYARD if adjacent_count(Acre::LUMBERYARD) == 0 || adjacent_count(Acre::TREES) == 0 => Acre::OPEN,
+                acre => acre,
+            }
+        }
     }
 
     #[derive(Debug, Default)]
     pub struct LumberCollectionArea {
-        acres: BTreeMap<Point, Acre>,
-        minutes: usize,
-        rows: usize,
-        cols: usize,
-        history: HashMap<BTreeMap<Point, Acre>, usize>,
+        acres: Option<Automaton<Acre>>,
     }
 
     impl LumberCollectionArea {
         pub fn parse_input_file(&mut self, filename: &str) {
-            let mut row = 0;
-            io_utils::file_to_lines(filename).for_each(|line| {
-                self.cols = line.len();
+            let lines: Vec<String> = io_utils::file_to_lines(filename).collect();
+            let mut acres = Automaton::new(
+                Neighborhood::Moore,
+                Bounds::Bounded { width: lines[0].len(), height: lines.len() },
+                Acre::next,
+            );
+            lines.iter().enumerate().for_each(|(row, line)| {
                 line.char_indices().for_each(|(col, ch)| {
-                    self.acres.insert(Point{ x: col as isize, y: row}, Acre::from_char(ch));
+                    acres.set(Position::new(col as isize, row as isize), Acre::from_char(ch));
                 });
-                row += 1;
             });
-            self.rows = row as usize;
-            self.history.insert(self.acres.clone(), self.minutes);
-        }
-
-        /// Returns the count of types of adjacent acres
-        fn adjacent_acre_count(&self, point: &Point) -> HashMap<Acre, usize> {
-            let mut res = HashMap::new();
-            for (x, y) in iproduct!(
-                point.x - 1 ..= point.x + 1,
-                point.y - 1 ..= point.y + 1
-            ) {
-                let adj_pt = Point { x, y };
-                if *point == adj_pt { continue; }
-                if let Some(acre) = self.acres.get(&adj_pt) {
-                    *res.entry(*acre).or_insert(0) += 1;
-                }
-            }
-            res
-        }
-
-        /// Returns the period of repeating, if any
-        fn progress_one_minute(&mut self) -> Option<usize> {
-            let mut new_acres = BTreeMap::new();
-            for (x, y) in iproduct!(0..self.cols as isize, 0..self.rows as isize) {
-                let pt = Point { x, y };
-                let adj_acre_count = self.adjacent_acre_count(&pt);
-                match self.acres.get(&pt).unwrap() {
-                    Acre::OPEN => {
-                        new_acres.insert(pt, Acre::OPEN);
-                        if let Some(adj_trees) = adj_acre_count.get(&Acre::TREES) {
-                            if *adj_trees >= 3 {
-                                new_acres.insert(pt, Acre::TREES);
-                            }
-                        }
-                    },
-                    Acre::TREES => {
-                        new_acres.insert(pt, Acre::TREES);
-                        if let Some(adj_lumberyards) = adj_acre_count.get(&Acre::LUMBERYARD) {
-                            if *adj_lumberyards >= 3 {
-                                new_acres.insert(pt, Acre::LUMBERYARD);
-                            }
-                        }
-                    },
-                    Acre::LUMBERYARD => {
-                        new_acres.insert(pt, Acre::OPEN);
-                        if let Some(adj_lumberyards) = adj_acre_count.get(&Acre::LUMBERYARD) {
-                            if *adj_lumberyards >= 1 {
-                                if let Some(adj_trees) = adj_acre_count.get(&Acre::TREES) {
-                                    if *adj_trees >= 1 {
-                                        new_acres.insert(pt, Acre::LUMBERYARD);
-                                    }
-                                }
-                            }
-                        }
-                    },
-                }
-            }
-            self.acres = new_acres;
-            self.minutes += 1;
-            if let Some(first_instance) = self.history.get(&self.acres) {
-                return Some(self.minutes - *first_instance);
-            }
-            self.history.insert(self.acres.clone(), self.minutes);            
-            None
+            self.acres = Some(acres);
         }
 
+        /// Progresses to the specified minute, skipping ahead once the
+        /// area's state starts repeating.
         pub fn progress_to(&mut self, minutes: usize) {
-            while self.minutes < minutes {
-                if let Some(period) = self.progress_one_minute() {
-                    let remaining = (minutes - self.minutes) % period;
-                    for _ in 0..remaining {
-                        self.progress_one_minute();
-                    }
-                    return;
-                }
-            }
+            self.acres.as_mut().unwrap().advance_to(minutes);
         }
 
         pub fn resource_value(&self) -> usize {
@@ -136,14 +68,11 @@ mod utils {
         }
 
         fn acre_value(&self, acre_type: Acre) -> usize {
-            self.acres.values()
-                .filter(|acre| **acre == acre_type)
-                .count()
+            self.acres.as_ref().unwrap().count(acre_type)
         }
     }
 }
 
-
 pub mod part_one {
     use crate::utils::solution::{Answer, Solution};
 
@@ -182,9 +111,6 @@ pub mod part_one {
 }
 
 pub mod part_two {
-    // TODO: need to find a circularity. so the lumber collection area needs to be hashable, it needs to use a BTreeMap instead
-    // of a HashMap. Need to track the state of the LCA with the minutes, find the period of the repeating, and calculate how many more
-    // minutes need to be progressed.
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::LumberCollectionArea;
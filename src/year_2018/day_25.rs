@@ -4,11 +4,11 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2018, day: 25 };
 
 mod utils {
-    use std::collections::{HashMap, HashSet};
-
     use lazy_static::lazy_static;
     use regex::Regex;
 
+    use crate::utils::disjoint_set::DisjointSet;
+
     lazy_static! {
         static ref POINT_RE: Regex = Regex::new(r"(?<x>\-?\d+),(?<y>\-?\d+),(?<z>\-?\d+),(?<t>\-?\d+)").unwrap();
     }
@@ -39,64 +39,29 @@ mod utils {
         }
     }
 
-    #[derive(Debug)]
-    pub struct Constellation {
-        points: HashSet<Point>,
-    }
-
-    impl Constellation {
-        pub fn add_point(&mut self, point: Point) {
-            self.points.insert(point);
-        }
-
-        pub fn merge(&mut self, other: Constellation) {
-            self.points.extend(other.points.into_iter());
-        }
-    }
-
+    /// Points are tracked by their index in `points`, with points within a
+    /// Manhattan distance of 3 of each other joined into the same constellation.
     #[derive(Debug, Default)]
     pub struct SolarSystem {
-        constellations: HashMap<usize, Constellation>,
-        next_id: usize,
+        points: Vec<Point>,
+        constellations: DisjointSet<usize>,
     }
 
     impl SolarSystem {
         pub fn add_point(&mut self, line: &str) {
             let point = Point::from_str(line);
-            let mut constellation_ids = HashSet::new();
-            for (id, constellation) in self.constellations.iter() {
-                for pt in constellation.points.iter() {
-                    if point.manhattan_distance(pt) <= 3 {
-                        constellation_ids.insert(*id);
-                        break;
-                    }
-                }
-            }
-            if constellation_ids.is_empty() {
-                self.constellations.insert(
-                    self.next_id,
-                    Constellation { points: HashSet::from([point]) },
-                );
-                self.next_id += 1;
-            } else if constellation_ids.len() == 1 {
-                let constellation_id = constellation_ids.iter().next().unwrap();
-                self.constellations.get_mut(constellation_id).unwrap().add_point(point);
-            } else {
-                // merge
-                let mut constellation_ids_iter = constellation_ids.into_iter();
-                let mergee_id = constellation_ids_iter.next().unwrap();
-                let mut mergee = self.constellations.remove(&mergee_id).unwrap();
-                mergee.add_point(point);
-                for merger_id in constellation_ids_iter {
-                    let merger = self.constellations.remove(&merger_id).unwrap();
-                    mergee.merge(merger);
+            let id = self.points.len();
+            self.constellations.make_set(id);
+            for (other_id, pt) in self.points.iter().enumerate() {
+                if point.manhattan_distance(pt) <= 3 {
+                    self.constellations.union(id, other_id);
                 }
-                self.constellations.insert(mergee_id, mergee);
             }
+            self.points.push(point);
         }
 
         pub fn num_constellations(&self) -> usize {
-            self.constellations.len()
+            self.constellations.num_components()
         }
     }
 }
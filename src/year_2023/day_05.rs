@@ -8,6 +8,8 @@ mod utils {
 
     use regex::Regex;
 
+    use crate::utils::io_utils::{Section, Sections};
+
     #[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
     pub enum Category {
         Seed,
@@ -237,48 +239,54 @@ mod utils {
         }
     }
 
-    /// Returns a mapping of source category to the map
-    pub fn parse_maps(lines: impl Iterator<Item = String>) -> HashMap<Category, Map> {
-        let mut maps = HashMap::new();
-        let seeds_re = Regex::new(r"seeds: (?<seeds>[ \d]+)").unwrap();
+    /// Returns the seeds line's numbers and a mapping of source category to
+    /// the map. The almanac is the seeds section followed by one section per
+    /// map.
+    pub fn parse_almanac(filename: &str) -> (Vec<u64>, HashMap<Category, Map>) {
+        let sections = Sections::from_file(filename, 8);
+        let seeds_re = Regex::new(r"^seeds: (?<seeds>[ \d]+)$").unwrap();
+        let seeds = seeds_re.captures(sections.nth(0).lines().next().unwrap())
+            .expect("First section should be the seeds line.")
+            .name("seeds")
+            .unwrap()
+            .as_str()
+            .split(" ")
+            .map(|part| part.parse().unwrap())
+            .collect();
+        let maps = sections.iter()
+            .skip(1)
+            .map(|section| {
+                let map = parse_map(section);
+                (map.source_category(), map)
+            })
+            .collect();
+        (seeds, maps)
+    }
+
+    fn parse_map(section: &Section) -> Map {
         let map_start_re = Regex::new(r"(?<source>[a-z]+)\-to\-(?<dest>[a-z]+) map:").unwrap();
         let map_entry_re = Regex::new(r"(?<dest_range_start>\d+) (?<source_range_start>\d+) (?<range_len>\d+)").unwrap();
-        let mut cur_map: Option<Map> = None;
+        let mut lines = section.lines();
+        let captures = map_start_re.captures(lines.next().unwrap())
+            .expect("Each map section should start with its header.");
+        let mut map = Map::with_categories(
+            Category::from_str(captures.name("source").unwrap().as_str()),
+            Category::from_str(captures.name("dest").unwrap().as_str()),
+        );
         lines.for_each(|line| {
-            if seeds_re.is_match(&line) {
-                panic!("Should not still have the seeds line in the iterator.");
-            } else if map_start_re.is_match(&line) {
-                let captures = map_start_re.captures(&line).unwrap();
-                let source_category = Category::from_str(
-                    captures.name("source").unwrap().as_str()
-                );
-                let dest_category = Category::from_str(
-                    captures.name("dest").unwrap().as_str()
-                );
-                cur_map = Some(Map::with_categories(source_category, dest_category));
-            } else if line.len() == 0 {
-                if cur_map != None {
-                    let c_m = cur_map.clone().unwrap();
-                    maps.insert(c_m.source_category(), c_m);
-                    cur_map = None;
-                }
-            } else {
-                let captures = map_entry_re.captures(&line).unwrap();
-                let dest_range_start = captures.name("dest_range_start").unwrap().as_str().parse().unwrap();
-                let source_range_start = captures.name("source_range_start").unwrap().as_str().parse().unwrap();
-                let range_len = captures.name("range_len").unwrap().as_str().parse().unwrap();
-                cur_map.as_mut().unwrap().add_map_entry(
-                    MapEntry::new(
-                        dest_range_start,
-                        source_range_start,
-                        range_len,
-                    )
+            let captures = map_entry_re.captures(line).unwrap();
+            let dest_range_start = captures.name("dest_range_start").unwrap().as_str().parse().unwrap();
+            let source_range_start = captures.name("source_range_start").unwrap().as_str().parse().unwrap();
+            let range_len = captures.name("range_len").unwrap().as_str().parse().unwrap();
+            map.add_map_entry(
+                MapEntry::new(
+                    dest_range_start,
+                    source_range_start,
+                    range_len,
                 )
-            }
+            )
         });
-        let c_m = cur_map.unwrap();
-        maps.insert(c_m.source_category(), c_m);
-        maps
+        map
     }
 
 
@@ -403,9 +411,7 @@ mod utils {
 pub mod part_one {
     use std::collections::HashMap;
 
-    use regex::Regex;
-
-    use crate::utils::{solution::{Solution, Answer}, io_utils};
+    use crate::utils::solution::{Solution, Answer};
 
     use super::utils::{self, Category, Map};
 
@@ -424,18 +430,7 @@ pub mod part_one {
 
     impl Soln {
         fn parse_input_file(&mut self, filename: &str) {
-            let seeds_re = Regex::new(r"seeds: (?<seeds>[ \d]+)").unwrap();
-            let mut lines = io_utils::file_to_lines(filename);
-            let line = lines.next().unwrap();
-            self.seeds = seeds_re.captures(&line)
-                .unwrap()
-                .name("seeds")
-                .unwrap()
-                .as_str()
-                .split(" ")
-                .map(|part| part.parse().unwrap())
-                .collect();
-            self.maps = utils::parse_maps(lines);
+            (self.seeds, self.maps) = utils::parse_almanac(filename);
         }
 
         fn seed_to_location(&self, seed: u64) -> u64 {
@@ -480,9 +475,8 @@ pub mod part_one {
 pub mod part_two {
     use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}};
     use itertools::Itertools;
-    use regex::Regex;
 
-    use crate::utils::{solution::{Solution, Answer}, io_utils};
+    use crate::utils::solution::{Solution, Answer};
 
     use super::utils::{self, Category, CategoryRange, Map, Range};
 
@@ -504,16 +498,8 @@ pub mod part_two {
 
     impl Soln {
         fn parse_input_file(&mut self, filename: &str) {
-            let seeds_re = Regex::new(r"seeds: (?<seeds>[ \d]+)").unwrap();
-            let mut lines = io_utils::file_to_lines(filename);
-            let line = lines.next().unwrap();
-            seeds_re.captures(&line)
-                .unwrap()
-                .name("seeds")
-                .unwrap()
-                .as_str()
-                .split(" ")
-                .map(|part| part.parse::<u64>().unwrap())
+            let (seeds, maps) = utils::parse_almanac(filename);
+            seeds.into_iter()
                 .chunks(2)
                 .into_iter()
                 .for_each(|mut chunk| {
@@ -525,7 +511,7 @@ pub mod part_two {
                         },
                     }));
                 });
-            self.maps = utils::parse_maps(lines);
+            self.maps = maps;
         }
 
         fn minimum_location_number(&mut self) -> u64 {
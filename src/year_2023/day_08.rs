@@ -3,7 +3,21 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2023, day: 8 };
 
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::utils::{dot::Graph, io_utils::Sections};
+use utils::{Instruction, Node};
+
 mod utils {
+    use lazy_static::lazy_static;
+    use regex::Regex;
+
+    lazy_static! {
+        static ref NODE_RE: Regex = Regex::new(r"(?<val>[A-Z0-9]{3}) = \((?<l>[A-Z0-9]{3}), (?<r>[A-Z0-9]{3})\)").unwrap();
+    }
+
     #[derive(Debug, PartialEq, Eq)]
     pub enum Instruction {
         L,
@@ -36,6 +50,19 @@ mod utils {
             }
         }
 
+        pub fn from_str(line: &str) -> Self {
+            let captures = NODE_RE.captures(line).expect("Should match node form.");
+            Self::new(
+                captures.name("val").unwrap().as_str(),
+                captures.name("l").unwrap().as_str(),
+                captures.name("r").unwrap().as_str(),
+            )
+        }
+
+        pub fn val(&self) -> &str {
+            &self.val
+        }
+
         pub fn next(&self, instruction: &Instruction) -> &str {
             match instruction {
                 Instruction::L => &self.l,
@@ -45,9 +72,74 @@ mod utils {
     }
 }
 
+/// Returns the network as a graph, with start (`..A`) and end (`..Z`) nodes
+/// marked by a `role` attribute and each edge labelled with its instruction.
+/// Following the instructions from each start, the nodes it passes get their
+/// fewest steps from a start as a `distance` attribute, and the nodes in the
+/// loop it ends up in get the ends in that loop as a `cycle` attribute.
+pub fn graph(filename: &str) -> Graph {
+    let sections = Sections::from_file(filename, 2);
+    let instructions: Vec<Instruction> = sections.nth(0)
+        .lines()
+        .flat_map(|line| line.chars().map(Instruction::from_char).collect::<Vec<Instruction>>())
+        .collect();
+    let nodes: Vec<Node> = sections.nth(1)
+        .lines()
+        .map(Node::from_str)
+        .collect();
+    let mut graph = Graph::directed("network");
+    nodes.iter()
+        .for_each(|node| {
+            let val = node.val();
+            if val.ends_with('A') {
+                graph.node(val).attr("role", "start").attr("shape", "box");
+            } else if val.ends_with('Z') {
+                graph.node(val).attr("role", "end").attr("shape", "doublecircle");
+            } else {
+                graph.node(val);
+            }
+            graph.edge(val, node.next(&Instruction::L)).attr("label", "L");
+            graph.edge(val, node.next(&Instruction::R)).attr("label", "R");
+        });
+    let nodes: HashMap<&str, &Node> = nodes.iter().map(|node| (node.val(), node)).collect();
+    let mut distances: HashMap<&str, usize> = HashMap::new();
+    let mut cycles: HashMap<&str, Vec<&str>> = HashMap::new();
+    nodes.keys()
+        .filter(|val| val.ends_with('A'))
+        .for_each(|start| {
+            // The walk repeats once it is back at a node at the same point in
+            // the instructions.
+            let mut first_steps: HashMap<(&str, usize), usize> = HashMap::new();
+            let mut walk: Vec<&str> = Vec::new();
+            let mut val: &str = start;
+            while !first_steps.contains_key(&(val, walk.len() % instructions.len())) {
+                first_steps.insert((val, walk.len() % instructions.len()), walk.len());
+                let distance = distances.entry(val).or_insert(walk.len());
+                *distance = (*distance).min(walk.len());
+                walk.push(val);
+                val = nodes[val].next(&instructions[(walk.len() - 1) % instructions.len()]);
+            }
+            let cycle = &walk[first_steps[&(val, walk.len() % instructions.len())]..];
+            let ends: Vec<&str> = cycle.iter()
+                .copied()
+                .filter(|val| val.ends_with('Z'))
+                .collect();
+            cycle.iter()
+                .for_each(|val| cycles.entry(val).or_default().extend(ends.iter().copied()));
+        });
+    distances.iter()
+        .for_each(|(val, distance)| {
+            graph.node(val).attr("distance", distance);
+        });
+    cycles.iter()
+        .for_each(|(val, ends)| {
+            graph.node(val).attr("cycle", ends.iter().sorted().dedup().join(","));
+        });
+    graph
+}
+
 pub mod part_one {
     use std::collections::HashMap;
-    use regex::Regex;
 
     use crate::utils::{solution::{Solution, Answer}, io_utils};
 
@@ -72,7 +164,6 @@ pub mod part_one {
 
     impl Soln {
         fn parse_input_file(&mut self, filename: &str) {
-            let node_re = Regex::new(r"(?<val>[A-Z]{3}) = \((?<l>[A-Z]{3}), (?<r>[A-Z]{3})\)").unwrap();
             let mut lines = io_utils::file_to_lines(filename);
             self.instructions = lines.next().unwrap()
                 .chars()
@@ -80,12 +171,8 @@ pub mod part_one {
                 .collect();
             assert!(lines.next().unwrap().len() == 0);
             lines.for_each(|line| {
-                let captures = node_re.captures(&line).unwrap();
-                let val = captures.name("val").unwrap().as_str();
-                let l = captures.name("l").unwrap().as_str();
-                let r = captures.name("r").unwrap().as_str();
-                let node = Node::new(val, l, r);
-                self.nodes.insert(String::from(val), node);
+                let node = Node::from_str(&line);
+                self.nodes.insert(String::from(node.val()), node);
             });
         }
 
@@ -111,9 +198,9 @@ pub mod part_one {
     #[cfg(test)]
     mod tests {
         use test_case::test_case;
-        use crate::utils::{test_utils, solution::Answer};
+        use crate::utils::{io_utils::{self, InputFileType}, test_utils, solution::Answer};
         use super::*;
-        use super::super::DAY;
+        use super::super::{graph, DAY};
 
         #[test_case(1, Answer::U32(6); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
@@ -124,6 +211,27 @@ pub mod part_one {
                 &DAY,
             );
         }
+
+        #[test]
+        fn graph_is_correct() {
+            let graph = graph(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            assert_eq!(
+                graph.to_string().lines().collect::<Vec<&str>>(),
+                vec![
+                    "digraph \"network\" {",
+                    "  \"AAA\" [role=\"start\", shape=\"box\", distance=\"0\"];",
+                    "  \"BBB\" [distance=\"1\"];",
+                    "  \"ZZZ\" [role=\"end\", shape=\"doublecircle\", distance=\"6\", cycle=\"ZZZ\"];",
+                    "  \"AAA\" -> \"BBB\" [label=\"L\"];",
+                    "  \"AAA\" -> \"BBB\" [label=\"R\"];",
+                    "  \"BBB\" -> \"AAA\" [label=\"L\"];",
+                    "  \"BBB\" -> \"ZZZ\" [label=\"R\"];",
+                    "  \"ZZZ\" -> \"ZZZ\" [label=\"L\"];",
+                    "  \"ZZZ\" -> \"ZZZ\" [label=\"R\"];",
+                    "}",
+                ],
+            );
+        }
     }    
 }
 
@@ -194,7 +302,6 @@ pub mod part_two {
 
     impl Soln {
         fn parse_input_file(&mut self, filename: &str) {
-            let node_re = Regex::new(r"(?<val>[A-Z0-9]{3}) = \((?<l>[A-Z0-9]{3}), (?<r>[A-Z0-9]{3})\)").unwrap();
             let start_node_val_re = Regex::new(r"[A-Z0-9]{2}A").unwrap();
             let mut lines = io_utils::file_to_lines(filename);
             self.instructions = lines.next().unwrap()
@@ -203,15 +310,11 @@ pub mod part_two {
                 .collect();
             assert!(lines.next().unwrap().len() == 0);
             lines.for_each(|line| {
-                let captures = node_re.captures(&line).unwrap();
-                let val = captures.name("val").unwrap().as_str();
-                let l = captures.name("l").unwrap().as_str();
-                let r = captures.name("r").unwrap().as_str();
-                let node = Node::new(val, l, r);
-                self.nodes.insert(String::from(val), node);
-                if start_node_val_re.is_match(&val) {
-                    self.paths.push(Path::new(val));
+                let node = Node::from_str(&line);
+                if start_node_val_re.is_match(node.val()) {
+                    self.paths.push(Path::new(node.val()));
                 }
+                self.nodes.insert(String::from(node.val()), node);
             });
         }
 
@@ -257,10 +257,13 @@ pub mod part_one {
     }    
 }
 
+/// The pipe loop is a lattice polygon with every loop tile as a vertex, so
+/// the enclosed tiles are its interior points by Pick's theorem (see
+/// [`crate::utils::polygon`]).
 pub mod part_two {
-    use std::{cmp, collections::{HashMap, HashSet, VecDeque}};
+    use std::collections::{HashMap, HashSet};
 
-    use crate::utils::{solution::{Solution, Answer}, io_utils};
+    use crate::utils::{io_utils, polygon::{LatticePolygon, Vertex}, solution::{Solution, Answer}};
 
     use super::utils::{Direction, Pipe, Point};
 
@@ -270,174 +273,11 @@ pub mod part_two {
         dir: Direction,
     }
 
-    #[derive(Debug, Default, PartialEq, Eq)]
-    struct Range {
-        min: usize,
-        max: usize,
-    }
-
-    impl Range {
-        fn new(val: usize) -> Self {
-            Self {
-                min: val,
-                max: val,
-            }
-        }
-
-        fn update(&mut self, val: usize) {
-            self.min = cmp::min(self.min, val);
-            self.max = cmp::max(self.max, val);
-        }
-
-        fn contains(&self, other: &Self) -> bool {
-            self.min <= other.min && self.max >= other.max
-        }
-
-        fn expand(&mut self, other: &Range) {
-            self.min = cmp::min(self.min, other.min);
-            self.max = cmp::max(self.max, other.max);
-        }
-    }
-
-    #[derive(Debug, Default, PartialEq, Eq)]
-    struct BoundingBox {
-        rows: Range,
-        cols: Range,
-    }
-
-    impl BoundingBox {
-        fn new(point: Point) -> Self {
-            Self {
-                rows: Range::new(point.row),
-                cols: Range::new(point.col),
-            }
-        }
-
-        fn update(&mut self, point: Point) {
-            self.rows.update(point.row);
-            self.cols.update(point.col);
-        }
-
-        fn contains(&self, other: &Self) -> bool {
-            self.rows.contains(&other.rows) && self.cols.contains(&other.cols)
-        }
-
-        fn expand(&mut self, other: &Self) {
-            self.rows.expand(&other.rows);
-            self.cols.expand(&other.cols);
-        }
-    }
-
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    enum EnclosureStatus {
-        Enclosed,
-        FreeRange,
-    }
-
-    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-    enum Location {
-        Tile(Point),
-        Intersection(Point), // Intersection at the top left of the `Point`
-    }
-
-    #[derive(Debug, Default)]
-    struct ConnectedTiles {
-        enclosure_status: Option<EnclosureStatus>,       
-        tiles: HashSet<Point>,
-        bounding_box: BoundingBox,
-        frontier: VecDeque<Location>,
-        explored: HashSet<Location>,        
-    }
-
-    impl ConnectedTiles {
-        fn num_tiles(&self) -> usize {
-            self.tiles.len()
-        }
-    }
-
-    impl ConnectedTiles {
-        fn new(location: Location) -> Self {
-            let bounding_box = match location {
-                Location::Tile(point) => BoundingBox::new(point),
-                Location::Intersection(point) => {
-                    let mut bb = BoundingBox::new(point);
-                    bb.update(Point { 
-                        row: point.row - if point.row != 0 { 1 } else { 0 }, 
-                        col: point.col - if point.col != 0 { 1 } else { 0 },
-                    });                    
-                    bb.update(Point { 
-                        row: point.row - if point.row != 0 { 1 } else { 0 }, 
-                        col: point.col,
-                    });                    
-                    bb.update(Point { 
-                        row: point.row,
-                        col: point.col - if point.col != 0 { 1 } else { 0 },
-                    });                    
-                    bb
-                },
-            };
-            Self {
-                enclosure_status: None,
-                tiles: match location {
-                    Location::Tile(pt) => HashSet::from([pt]),
-                    Location::Intersection(_) => HashSet::new(),
-                },
-                bounding_box,
-                frontier: VecDeque::from([location]),
-                explored: HashSet::new(),               
-            }
-        }
-
-        fn absorb(&mut self, other: &mut Self) {
-            self.enclosure_status = match (self.enclosure_status, other.enclosure_status) {
-                (Some(EnclosureStatus::FreeRange), _) | (_, Some(EnclosureStatus::FreeRange)) => Some(EnclosureStatus::FreeRange),
-                (None, o) => o,
-                (s, None) => s,
-                (Some(EnclosureStatus::Enclosed), Some(EnclosureStatus::Enclosed)) => Some(EnclosureStatus::Enclosed),
-            };
-            self.tiles.extend(other.tiles.iter());
-            self.explored.extend(other.explored.iter());
-            self.frontier.append(&mut other.frontier);
-            self.frontier.retain(|location| {
-                if self.explored.contains(location) { return false; }
-                if let Location::Tile(point) = location {
-                    if self.tiles.contains(point) { return false; } 
-                }
-                true
-            });
-            self.bounding_box.expand(&other.bounding_box);
-        }
-
-        fn set_enclosure_status(&mut self, enclosure_status: EnclosureStatus) {
-            self.enclosure_status = Some(enclosure_status);
-        }
-    }
-
-    #[derive(Debug, Default, PartialEq, Eq)]
-    struct PipeLoop {
-        pipes: HashSet<Point>,
-        bounding_box: BoundingBox,
-    }
-
-    impl PipeLoop {
-        fn new(point: Point) -> Self {
-            Self {
-                pipes: HashSet::from([point]),
-                bounding_box: BoundingBox::new(point),
-            }
-        }
-
-        fn add_point(&mut self, point: Point) {
-            self.pipes.insert(point);
-            self.bounding_box.update(point);
-        }
-    }
-
     #[derive(Debug, PartialEq, Eq, Default)]
     pub struct Soln {
         start: Option<Point>,
         pipes: HashMap<Point, Pipe>,
-        pipe_loop: PipeLoop,
+        pipe_loop: LatticePolygon,
     }
 
     impl Solution for Soln {
@@ -521,171 +361,23 @@ pub mod part_two {
         }
 
         fn calculate_pipe_loop(&mut self) {
-            self.pipe_loop = PipeLoop::new(self.start.unwrap());
+            let start = self.start.unwrap();
+            let mut pipe_loop = vec![start];
             let mut path = self.starting_path();
-            while path.point != self.start.unwrap() {
-                self.pipe_loop.add_point(path.point);
+            while path.point != start {
+                pipe_loop.push(path.point);
                 path = self.step(&path);
             }
+            self.pipe_loop = pipe_loop.into_iter()
+                .map(|point| Vertex::new(point.col as i64, point.row as i64))
+                .collect();
         }
 
         fn enclosed_tiles(&self) -> usize {
-            let start = self.start.unwrap();
-            let mut all_connected_tiles = VecDeque::from([
-                ConnectedTiles::new(Location::Intersection(start)),
-                ConnectedTiles::new(Location::Intersection(Point { row: start.row + 1, col: start.col })),
-                ConnectedTiles::new(Location::Intersection(Point { row: start.row + 1, col: start.col + 1 })),
-                ConnectedTiles::new(Location::Intersection(Point { row: start.row, col: start.col + 1 })),
-            ]);
-            loop {
-                let mut connected_tiles = all_connected_tiles.pop_front().unwrap();
-                self.update_enclosure_status(&mut connected_tiles);
-                if connected_tiles.enclosure_status == Some(EnclosureStatus::Enclosed) {
-                    return connected_tiles.num_tiles();
-                }
-                self.explore_frontier(&mut connected_tiles);
-                let mut absorbed = false;
-                for other_cts in all_connected_tiles.iter_mut() {
-                    if !connected_tiles.explored.is_disjoint(&other_cts.explored) {
-                        other_cts.absorb(&mut connected_tiles);
-                        absorbed = true;
-                        break;
-                    }
-                }
-                if !absorbed {
-                    all_connected_tiles.push_back(connected_tiles);
-                }
-            }
-        }
-
-        fn update_enclosure_status(&self, connected_tiles: &mut ConnectedTiles) {
-            if connected_tiles.enclosure_status != None { return; }
-            if !self.pipe_loop.bounding_box.contains(&connected_tiles.bounding_box) {
-                connected_tiles.set_enclosure_status(EnclosureStatus::FreeRange);
-                connected_tiles.frontier.drain(..);
-            } else if connected_tiles.frontier.is_empty() {
-                connected_tiles.set_enclosure_status(EnclosureStatus::Enclosed);
-            }
-        }
-
-        /* 
-        If you're at an intersection you can go on to any of the 4 tiles (as long
-        as they're not in the pipe loop) or to any of 4 intersections (as long
-        as you can cross to them).
-
-        If you're on a tile, you can go to any of your 4 intersections
-        or to any of 4 tiles (as long as they're not in the pipe loop).
-        */
-        fn explore_frontier(&self, connected_tiles: &mut ConnectedTiles) {
-            let mut new_frontier = VecDeque::new();
-            while !connected_tiles.frontier.is_empty() {
-                let location = connected_tiles.frontier.pop_front().unwrap();
-                if connected_tiles.explored.contains(&location) { continue; }
-                connected_tiles.explored.insert(location);
-                match location {
-                    Location::Tile(point) => {
-                        connected_tiles.tiles.insert(point);
-                        connected_tiles.bounding_box.update(point);
-                        if point.row != 0 {
-                            let new_pt = Point { row: point.row - 1, col: point.col };
-                            if !self.pipe_loop.pipes.contains(&new_pt) && !connected_tiles.explored.contains(&Location::Tile(new_pt)) {
-                                new_frontier.push_back(Location::Tile(new_pt));
-                            }
-                        }
-                        if point.col != 0 {
-                            let new_pt = Point { row: point.row, col: point.col - 1 };
-                            if !self.pipe_loop.pipes.contains(&new_pt) && !connected_tiles.explored.contains(&Location::Tile(new_pt)) {
-                                new_frontier.push_back(Location::Tile(new_pt));
-                            }
-                        }
-                        for (row_offset, col_offset) in [(1, 0), (0, 1)] {
-                            let new_pt = Point { row: point.row + row_offset, col: point.col + col_offset };
-                            if !self.pipe_loop.pipes.contains(&new_pt) && !connected_tiles.explored.contains(&Location::Tile(new_pt)) {
-                                new_frontier.push_back(Location::Tile(new_pt));
-                            }
-                        }
-                        for (row_offset, col_offset) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
-                            let new_pt = Point { row: point.row + row_offset, col: point.col + col_offset };
-                            if !connected_tiles.explored.contains(&Location::Intersection(new_pt)) {
-                                new_frontier.push_back(Location::Intersection(new_pt));
-                            }
-                        }
-                    },
-                    Location::Intersection(point) => {
-                        if point.row != 0 {
-                            let new_pt = Point { row: point.row - 1, col: point.col };
-                            if !self.pipe_loop.pipes.contains(&new_pt) && !connected_tiles.explored.contains(&Location::Tile(new_pt)) {
-                                new_frontier.push_back(Location::Tile(new_pt));
-                            }
-                        }
-                        if point.col != 0 {
-                            let new_pt = Point { row: point.row, col: point.col - 1 };
-                            if !self.pipe_loop.pipes.contains(&new_pt) && !connected_tiles.explored.contains(&Location::Tile(new_pt)) {
-                                new_frontier.push_back(Location::Tile(new_pt));
-                            }
-                        }
-                        if point.row != 0 && point.col != 0 {
-                            let new_pt = Point { row: point.row - 1, col: point.col - 1 };
-                            if !self.pipe_loop.pipes.contains(&new_pt) && !connected_tiles.explored.contains(&Location::Tile(new_pt)) {
-                                new_frontier.push_back(Location::Tile(new_pt));
-                            }
-                        }
-                        if !self.pipe_loop.pipes.contains(&point) && !connected_tiles.explored.contains(&Location::Tile(point)) {
-                            new_frontier.push_back(Location::Tile(point));
-                        }
-                        // Intersections
-                        // check North
-                        if point.row != 0 {
-                            let new_pt = Point { row: point.row - 1, col: point.col };
-                            if !connected_tiles.explored.contains(&Location::Intersection(new_pt)) && (
-                                point.col == 0
-                                || !self.pipe_loop.pipes.contains(&new_pt)
-                                || !self.pipes.get(&new_pt).unwrap().connects(&Direction::W)
-                                || !self.pipe_loop.pipes.contains(&Point { row: point.row - 1, col: point.col - 1 })
-                                || !self.pipes.get(&Point { row: point.row - 1, col: point.col - 1 }).unwrap().connects(&Direction::E)
-                            ) {
-                                new_frontier.push_back(Location::Intersection(new_pt));
-                            }
-                        }
-                        // check West
-                        if point.col != 0 {
-                            let new_pt = Point { row: point.row, col: point.col - 1 };
-                            if !connected_tiles.explored.contains(&Location::Intersection(new_pt)) && (
-                                point.row == 0
-                                || !self.pipe_loop.pipes.contains(&new_pt)
-                                || !self.pipes.get(&new_pt).unwrap().connects(&Direction::N)
-                                || !self.pipe_loop.pipes.contains(&Point { row: point.row - 1, col: point.col - 1 })
-                                || !self.pipes.get(&Point { row: point.row - 1, col: point.col - 1 }).unwrap().connects(&Direction::S)
-                            ) {
-                                new_frontier.push_back(Location::Intersection(new_pt));
-                            }
-                        }
-                        // check South
-                        let new_pt = Point { row: point.row + 1, col: point.col };
-                        if !connected_tiles.explored.contains(&Location::Intersection(new_pt)) && (
-                            point.col == 0
-                            || !self.pipe_loop.pipes.contains(&point)
-                            || !self.pipes.get(&point).unwrap().connects(&Direction::W)
-                            || !self.pipe_loop.pipes.contains(&Point { row: point.row, col: point.col - 1 })
-                            || !self.pipes.get(&Point { row: point.row, col: point.col - 1 }).unwrap().connects(&Direction::E)
-                        ) {
-                            new_frontier.push_back(Location::Intersection(new_pt));
-                        }
-                        // check East
-                        let new_pt = Point { row: point.row, col: point.col + 1 };
-                        if !connected_tiles.explored.contains(&Location::Intersection(new_pt)) && (
-                            point.row == 0
-                            || !self.pipe_loop.pipes.contains(&point)
-                            || !self.pipes.get(&point).unwrap().connects(&Direction::N)
-                            || !self.pipe_loop.pipes.contains(&Point { row: point.row - 1, col: point.col })
-                            || !self.pipes.get(&Point { row: point.row - 1, col: point.col }).unwrap().connects(&Direction::S)
-                        ) {
-                            new_frontier.push_back(Location::Intersection(new_pt));
-                        }
-                    },
-                }
-            }
-            connected_tiles.frontier = new_frontier;
+            self.pipe_loop
+                .interior_points()
+                .try_into()
+                .expect("Number of enclosed tiles should fit into `usize` datatype.")
         }
     }
 
@@ -115,6 +115,7 @@ mod utils {
 pub mod part_one {
     use std::{collections::{HashSet, HashMap}, cell::{RefCell, Ref, RefMut}};
 
+    use crate::solution_params;
     use crate::utils::solution::{Solution, Answer};
 
     use super::utils::{Year2023Day11Solution, Point};
@@ -178,6 +179,10 @@ pub mod part_one {
             self.parse_input_file(filename);
             Answer::U32(self.sum_of_min_distances().try_into().unwrap())
         }
+
+        solution_params! {
+            expansion_factor: "How many times larger every empty row and column becomes",
+        }
     }
 
     impl Soln {
@@ -215,6 +220,7 @@ pub mod part_one {
 pub mod part_two {
     use std::{collections::{HashSet, HashMap}, cell::{RefCell, Ref, RefMut}};
 
+    use crate::solution_params;
     use crate::utils::solution::{Solution, Answer};
 
     use super::utils::{Year2023Day11Solution, Point};
@@ -278,6 +284,10 @@ pub mod part_two {
             self.parse_input_file(filename);
             Answer::U64(self.sum_of_min_distances().try_into().unwrap())
         }
+
+        solution_params! {
+            expansion_factor: "How many times larger every empty row and column becomes",
+        }
     }
 
     impl Soln {
@@ -304,8 +314,9 @@ pub mod part_two {
         #[test_case(1, 10, Answer::U64(1_030); "example_1_10")]
         #[test_case(1, 100, Answer::U64(8_410); "example_1_100")]
         fn examples_are_correct(example_key: u8, expansion_factor: usize, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_expansion_factor(expansion_factor),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("expansion_factor", expansion_factor.to_string())],
                 example_key,
                 answer,
                 &DAY,
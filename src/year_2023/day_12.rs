@@ -4,9 +4,9 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2023, day: 12 };
 
 mod utils {
-    use std::{collections::{HashMap, VecDeque}, iter};
+    use std::{collections::VecDeque, iter};
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, memo::Memo};
 
     #[derive(Debug, PartialEq, Eq, Hash, Clone)]
     enum Condition {
@@ -73,7 +73,7 @@ mod utils {
     pub struct ArrangementsCounter {
         num_repeats: usize,
         total_arrangements: u64,
-        cache: HashMap<ConditionRecord, u64>,
+        memo: Memo<ConditionRecord, u64>,
     }
 
     impl ArrangementsCounter {
@@ -81,18 +81,18 @@ mod utils {
             Self {
                 num_repeats,
                 total_arrangements: 0,
-                cache: HashMap::new(),
+                memo: Memo::default().named("arrangements"),
             }
         }
 
         fn arrangements_from_str(&mut self, line: &str) -> u64 {
             let condition_record = ConditionRecord::from_str(line, self.num_repeats);
-            self.arrangements(condition_record)
+            Self::arrangements(&mut self.memo, condition_record)
         }
 
         /// Recursively calculates the number of arrangements for the given condition record
         /// (set of not-known-to-be operational condition groups and needed contiguous damaged blocks).
-        fn arrangements(&mut self, cr: ConditionRecord) -> u64 {
+        fn arrangements(memo: &mut Memo<ConditionRecord, u64>, cr: ConditionRecord) -> u64 {
             // base case: if no more damaged are needed
             if cr.needed.is_empty() {
                 if cr.groups.iter().any(|group| {
@@ -103,76 +103,72 @@ mod utils {
                     return 1;
                 }
             }
-            match self.cache.get(&cr) {
-                Some(arrangements) => return *arrangements,
-                None => {
-                    // base case: if exactly one group
-                    if cr.groups.len() == 1 {
-                        let mut groups = cr.groups.clone();
-                        let group = groups.pop_back().expect("Should be exactly one group.");
-                        if group.conditions.len() < (cr.needed.iter().sum::<u64>() as usize + cr.needed.len() - 1) {
-                            // not enough room in the group for the needed damaged groups
-                            return 0;
-                        } else {
-                            let mut arr: u64 = 0;
-                            // try making the entire first needed block at the start of the group
-                            let mut new_needed = cr.needed.clone();
-                            let first_needed: usize = new_needed.pop_front().expect("Should be at least one needed.").try_into().unwrap();
-                            if group.conditions.len() == first_needed {
-                                if !new_needed.is_empty() {
-                                    return 0;
-                                } else {
-                                    return 1;
-                                }
-                            }
-                            if *group.conditions.get(first_needed).expect("Conditions should be long enough.") == Condition::Unknown {
-                                let mut new_conditions = group.conditions.clone();
-                                let new_group = Group {
-                                    conditions: new_conditions.split_off(first_needed + 1),
-                                };
-                                let new_cr = ConditionRecord {
-                                    groups: VecDeque::from([new_group]),
-                                    needed: new_needed,
-                                };
-                                arr += self.arrangements(new_cr);
-                            }
-                            // try making the first condition operational
-                            if *group.conditions.get(0).expect("Should be at least one condition.") == Condition::Unknown {
-                                let mut new_conditions = group.conditions.clone();
-                                let new_group = Group {
-                                    conditions: new_conditions.split_off(1),
-                                };
-                                let new_cr = ConditionRecord {
-                                    groups: VecDeque::from([new_group]),
-                                    needed: cr.needed.clone(),
-                                };
-                                arr += self.arrangements(new_cr);
-                            }
-                            self.cache.insert(cr.clone(), arr);                      
-                            return arr;
-                        }                
+            memo.get_or_compute(cr.clone(), |memo| {
+                // base case: if exactly one group
+                if cr.groups.len() == 1 {
+                    let mut groups = cr.groups.clone();
+                    let group = groups.pop_back().expect("Should be exactly one group.");
+                    if group.conditions.len() < (cr.needed.iter().sum::<u64>() as usize + cr.needed.len() - 1) {
+                        // not enough room in the group for the needed damaged groups
+                        0
                     } else {
-                        // recursive case: more than one group. Take the first group, try to fit all combinations of the needed into it. then recurse.
-                        let mut arr = 0;
-                        for num_needed in 0..=cr.needed.len() {
-                            // first group, x needed. * rest of groups, other needed
-                            let mut groups = cr.groups.clone();
-                            let rem_groups = groups.split_off(1);
-                            let mut needed = cr.needed.clone();
-                            let rem_needed = needed.split_off(num_needed);
-                            arr += self.arrangements(ConditionRecord { groups, needed }) * self.arrangements(ConditionRecord { groups: rem_groups, needed: rem_needed});
+                        let mut arr: u64 = 0;
+                        // try making the entire first needed block at the start of the group
+                        let mut new_needed = cr.needed.clone();
+                        let first_needed: usize = new_needed.pop_front().expect("Should be at least one needed.").try_into().unwrap();
+                        if group.conditions.len() == first_needed {
+                            if !new_needed.is_empty() {
+                                return 0;
+                            } else {
+                                return 1;
+                            }
+                        }
+                        if *group.conditions.get(first_needed).expect("Conditions should be long enough.") == Condition::Unknown {
+                            let mut new_conditions = group.conditions.clone();
+                            let new_group = Group {
+                                conditions: new_conditions.split_off(first_needed + 1),
+                            };
+                            let new_cr = ConditionRecord {
+                                groups: VecDeque::from([new_group]),
+                                needed: new_needed,
+                            };
+                            arr += Self::arrangements(memo, new_cr);
                         }
-                        self.cache.insert(cr.clone(), arr);
-                        return arr;
+                        // try making the first condition operational
+                        if *group.conditions.first().expect("Should be at least one condition.") == Condition::Unknown {
+                            let mut new_conditions = group.conditions.clone();
+                            let new_group = Group {
+                                conditions: new_conditions.split_off(1),
+                            };
+                            let new_cr = ConditionRecord {
+                                groups: VecDeque::from([new_group]),
+                                needed: cr.needed.clone(),
+                            };
+                            arr += Self::arrangements(memo, new_cr);
+                        }
+                        arr
                     }
+                } else {
+                    // recursive case: more than one group. Take the first group, try to fit all combinations of the needed into it. then recurse.
+                    let mut arr = 0;
+                    for num_needed in 0..=cr.needed.len() {
+                        // first group, x needed. * rest of groups, other needed
+                        let mut groups = cr.groups.clone();
+                        let rem_groups = groups.split_off(1);
+                        let mut needed = cr.needed.clone();
+                        let rem_needed = needed.split_off(num_needed);
+                        arr += Self::arrangements(memo, ConditionRecord { groups, needed }) * Self::arrangements(memo, ConditionRecord { groups: rem_groups, needed: rem_needed});
+                    }
+                    arr
                 }
-            }
+            })
         }
 
         pub fn parse_input_file(&mut self, filename: &str) {
             self.total_arrangements = io_utils::file_to_lines(filename)
                 .map(|line| self.arrangements_from_str(&line))
                 .sum();
+            self.memo.report();
         }
 
         pub fn total_arrangements(&self) -> u64 {
@@ -177,7 +177,7 @@ mod utils {
 /// in each pattern has at least one ash ('.') entry.
 pub mod part_one {
 
-    use crate::utils::{solution::{Solution, Answer}, io_utils};
+    use crate::utils::{solution::{Solution, Answer}, io_utils::Sections};
 
     use super::utils::Pattern;
 
@@ -196,17 +196,14 @@ pub mod part_one {
     impl Soln {
         fn parse_input_file(&mut self, filename: &str) {
             let smudges_necessary = 0;
-            let mut pattern = Pattern::with_smudges_necessary(smudges_necessary);
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    if line.is_empty() {
-                        self.summarized_notes += pattern.note();
-                        pattern = Pattern::with_smudges_necessary(smudges_necessary);
-                    } else {
-                        pattern.parse_line(&line);
-                    }
-                });
-            self.summarized_notes += pattern.note();
+            self.summarized_notes = Sections::from_file_any(filename)
+                .iter()
+                .map(|section| {
+                    let mut pattern = Pattern::with_smudges_necessary(smudges_necessary);
+                    section.lines().for_each(|line| pattern.parse_line(line));
+                    pattern.note()
+                })
+                .sum();
         }
 
         fn summarized_notes(&self) -> usize {
@@ -241,7 +238,7 @@ pub mod part_one {
 
 pub mod part_two {
 
-    use crate::utils::{solution::{Solution, Answer}, io_utils};
+    use crate::utils::{solution::{Solution, Answer}, io_utils::Sections};
 
     use super::utils::Pattern;
 
@@ -260,17 +257,14 @@ pub mod part_two {
     impl Soln {
         fn parse_input_file(&mut self, filename: &str) {
             let smudges_necessary = 1;
-            let mut pattern = Pattern::with_smudges_necessary(smudges_necessary);
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    if line.is_empty() {
-                        self.summarized_notes += pattern.note();
-                        pattern = Pattern::with_smudges_necessary(smudges_necessary);
-                    } else {
-                        pattern.parse_line(&line);
-                    }
-                });
-            self.summarized_notes += pattern.note();
+            self.summarized_notes = Sections::from_file_any(filename)
+                .iter()
+                .map(|section| {
+                    let mut pattern = Pattern::with_smudges_necessary(smudges_necessary);
+                    section.lines().for_each(|line| pattern.parse_line(line));
+                    pattern.note()
+                })
+                .sum();
         }
 
         fn summarized_notes(&self) -> usize {
@@ -3,10 +3,21 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2023, day: 19 };
 
+/// The workflows form a small decision-tree language. `Workflows` evaluates
+/// concrete parts (with a trace of the workflows visited) and analyzes the
+/// whole space of parts at once by splitting ranges of ratings at each rule.
+/// Both parts' answers come from that analysis.
 mod utils {
+    use std::{collections::{HashMap, HashSet}, fmt};
+
     use regex::Regex;
 
-    #[derive(Debug, PartialEq, Eq)]
+    use crate::utils::io_utils::Section;
+
+    pub const MIN_RATING: u64 = 1;
+    pub const MAX_RATING: u64 = 4_000;
+
+    #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
     pub enum Category {
         X,
         M,
@@ -24,6 +35,27 @@ mod utils {
                 _ => panic!("Unrecognized category"),
             }
         }
+
+        fn idx(&self) -> usize {
+            match self {
+                Self::X => 0,
+                Self::M => 1,
+                Self::A => 2,
+                Self::S => 3,
+            }
+        }
+    }
+
+    impl fmt::Display for Category {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let category = match self {
+                Self::X => "x",
+                Self::M => "m",
+                Self::A => "a",
+                Self::S => "s",
+            };
+            write!(f, "{category}")
+        }
     }
 
     #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -42,7 +74,7 @@ mod utils {
         }
     }
 
-    #[derive(Debug, PartialEq, Eq, Clone)]
+    #[derive(Debug, PartialEq, Eq, Hash, Clone)]
     pub enum Destination {
         A,
         R,
@@ -59,7 +91,17 @@ mod utils {
         }
     }
 
-    #[derive(Debug, PartialEq, Eq)]
+    impl fmt::Display for Destination {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::A => write!(f, "A"),
+                Self::R => write!(f, "R"),
+                Self::Workflow(name) => write!(f, "{name}"),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
     pub struct Rule {
         pub category: Category,
         pub operation: Operation,
@@ -82,9 +124,37 @@ mod utils {
                 destination,
             }
         }
+
+        fn matches(&self, part: &Part) -> bool {
+            let rating = part.rating(self.category);
+            match self.operation {
+                Operation::LessThan => rating < self.threshold,
+                Operation::GreaterThan => rating > self.threshold,
+            }
+        }
+
+        /// Splits `part_combination` into the parts that match the rule and the
+        /// parts that don't, either of which may be empty.
+        fn split(&self, part_combination: &PartCombination) -> (Option<PartCombination>, Option<PartCombination>) {
+            let (matching, rest) = part_combination.range(self.category).split(self.threshold, self.operation);
+            (
+                matching.map(|range| part_combination.with_range(self.category, range)),
+                rest.map(|range| part_combination.with_range(self.category, range)),
+            )
+        }
+    }
+
+    impl fmt::Display for Rule {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let operation = match self.operation {
+                Operation::LessThan => "<",
+                Operation::GreaterThan => ">",
+            };
+            write!(f, "{}{operation}{}:{}", self.category, self.threshold, self.destination)
+        }
     }
 
-    #[derive(Debug, PartialEq, Eq)]
+    #[derive(Debug, PartialEq, Eq, Clone)]
     pub struct Workflow {
         pub rules: Vec<Rule>,
         pub final_dest: Destination,
@@ -92,321 +162,517 @@ mod utils {
 
     impl Workflow {
         pub fn from_str(input: &str) -> Self {
-            let re = Regex::new(r"\{(?<rules>[ARa-z><\:\,\d]+)\,(?<final_dest>A|R|[a-z]+)\}").unwrap();
+            let re = Regex::new(r"\{((?<rules>[ARa-z><\:\,\d]+)\,)?(?<final_dest>A|R|[a-z]+)\}").unwrap();
             let captures = re.captures(input).unwrap();
-            let rules = captures.name("rules").unwrap().as_str();
+            let rules = match captures.name("rules") {
+                Some(rules) => rules.as_str().split(",").map(Rule::from_str).collect(),
+                None => Vec::new(),
+            };
             let final_dest = Destination::from_str(captures.name("final_dest").unwrap().as_str());
             Self {
-                rules: rules.split(",").map(|rule| Rule::from_str(rule)).collect(),
+                rules,
                 final_dest,
             }
         }
-    }
-}
-
-pub mod part_one {
-
-    use std::collections::{HashMap, VecDeque};
 
-    use regex::Regex;
+        /// Returns the index of the first rule matching `part` (or
+        /// `rules.len()` if none match) and where it sends the part.
+        fn process(&self, part: &Part) -> (usize, &Destination) {
+            self.rules.iter()
+                .enumerate()
+                .find(|(_idx, rule)| rule.matches(part))
+                .map(|(idx, rule)| (idx, &rule.destination))
+                .unwrap_or((self.rules.len(), &self.final_dest))
+        }
+    }
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
-    use super::utils::{Category, Operation, Destination, Rule, Workflow};
+    impl fmt::Display for Workflow {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{{")?;
+            for rule in self.rules.iter() {
+                write!(f, "{rule},")?;
+            }
+            write!(f, "{}}}", self.final_dest)
+        }
+    }
 
-    #[derive(Debug, PartialEq, Eq)]
-    struct Part {
-        x: u64,
-        m: u64,
-        a: u64,
-        s: u64,
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Part {
+        ratings: [u64; 4],
     }
 
     impl Part {
-        fn from_str(input: &str) -> Self {
+        pub fn from_str(input: &str) -> Self {
             let re = Regex::new(r"\{x\=(?<x>\d+)\,m\=(?<m>\d+)\,a\=(?<a>\d+)\,s\=(?<s>\d+)\}").unwrap();
-            let captures = re.captures(&input).unwrap();
-            let x = captures.name("x").unwrap().as_str().parse().unwrap();
-            let m = captures.name("m").unwrap().as_str().parse().unwrap();
-            let a = captures.name("a").unwrap().as_str().parse().unwrap();
-            let s = captures.name("s").unwrap().as_str().parse().unwrap();
+            let captures = re.captures(input).unwrap();
+            let rating = |name: &str| captures.name(name).unwrap().as_str().parse().unwrap();
             Self {
-                x,
-                m,
-                a,
-                s,
+                ratings: [rating("x"), rating("m"), rating("a"), rating("s")],
             }
         }
 
-        fn category(&self, category: &Category) -> u64 {
-            match category {
-                Category::X => self.x,
-                Category::M => self.m,
-                Category::A => self.a,
-                Category::S => self.s,
-            }
+        pub fn rating(&self, category: Category) -> u64 {
+            self.ratings[category.idx()]
         }
 
-        fn sum_of_categories(&self) -> u64 {
-            self.x + self.m + self.a + self.s
+        pub fn sum_of_ratings(&self) -> u64 {
+            self.ratings.iter().sum()
         }
     }
 
-    impl Rule {
-        fn test(&self, part: &Part) -> Option<Destination> {
-            match self.operation {
-                Operation::LessThan => if part.category(&self.category) < self.threshold { Some(self.destination.clone()) } else { None },
-                Operation::GreaterThan => if part.category(&self.category) > self.threshold { Some(self.destination.clone()) } else { None },
-            }
-        }
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct Range {
+        min: u64, // inclusive
+        max: u64, // inclusive
     }
 
-    impl Workflow {
-        fn process(&self, part: &Part) -> Destination {
-            for rule in &self.rules {
-                if let Some(dest) = rule.test(part) {
-                    return dest;
-                }
+    impl Default for Range {
+        fn default() -> Self {
+            Self {
+                min: MIN_RATING,
+                max: MAX_RATING,
             }
-            self.final_dest.clone()
         }
     }
 
-    #[derive(Debug, Default, PartialEq, Eq)]
-    pub struct Soln {
-        to_process: VecDeque<(Part, Destination)>,
-        sum_of_accepted: u64,
-        workflows: HashMap<String, Workflow>,
-    }
+    impl Range {
+        fn len(&self) -> u64 {
+            self.max - self.min + 1
+        }
 
-    impl Solution for Soln {
-        fn solve(&mut self, filename: &str) -> Answer {
-            self.parse_input_file(filename);
-            self.process_all_parts();
-            Answer::U64(self.sum_of_accepted())
-        }
-    }
-
-    impl Soln {
-        fn parse_input_file(&mut self, filename: &str) {
-            let workflow_re = Regex::new(r"(?<name>[a-z]+)(?<workflow>.+)").unwrap();
-            let mut parsing_workflows = true;
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    if line.is_empty() { 
-                        parsing_workflows = false;
-                    } else if parsing_workflows {
-                        let captures = workflow_re.captures(&line).unwrap();
-                        let name = String::from(captures.name("name").unwrap().as_str());
-                        let workflow = Workflow::from_str(captures.name("workflow").unwrap().as_str());
-                        self.workflows.insert(name, workflow);
-                    } else {
-                        self.to_process.push_back((Part::from_str(&line), Destination::Workflow(String::from("in"))));
-                    }
-                });
-        }
-
-        fn process_all_parts(&mut self) {
-            while !self.to_process.is_empty() {
-                let (part, destination) = self.to_process.pop_front().unwrap();
-                match destination {
-                    Destination::A => self.sum_of_accepted += part.sum_of_categories(),
-                    Destination::R => {},
-                    Destination::Workflow(name) => {
-                        let workflow = self.workflows.get(&name).expect("Should have a workflow matching this name.");
-                        let dest = workflow.process(&part);
-                        self.to_process.push_back((part, dest));
-                    },
-                }
-            }
+        fn contains(&self, val: u64) -> bool {
+            self.min <= val && val <= self.max
         }
 
-        fn sum_of_accepted(&self) -> u64 {
-            self.sum_of_accepted
+        /// Returns the (possibly empty) sub-ranges that do and don't satisfy
+        /// the comparison against `threshold`.
+        fn split(self, threshold: u64, operation: Operation) -> (Option<Range>, Option<Range>) {
+            let below = (self.min < threshold).then(|| Range { min: self.min, max: self.max.min(threshold - 1) });
+            let at = self.contains(threshold).then_some(Range { min: threshold, max: threshold });
+            let above = (self.max > threshold).then(|| Range { min: self.min.max(threshold + 1), max: self.max });
+            let join = |a: Option<Range>, b: Option<Range>| match (a, b) {
+                (Some(a), Some(b)) => Some(Range { min: a.min, max: b.max }),
+                (a, None) => a,
+                (None, b) => b,
+            };
+            match operation {
+                Operation::LessThan => (below, join(at, above)),
+                Operation::GreaterThan => (above, join(below, at)),
+            }
         }
+    }
 
+    /// A hyperrectangle of parts: every combination of ratings within the
+    /// range for each category.
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    pub struct PartCombination {
+        ranges: [Range; 4],
     }
 
-    #[cfg(test)]
-    mod tests {
-        use test_case::test_case;
-        use crate::utils::{test_utils, solution::Answer};
-        use super::*;
-        use super::super::DAY;
+    impl PartCombination {
+        pub fn range(&self, category: Category) -> Range {
+            self.ranges[category.idx()]
+        }
 
-        #[test_case(1, Answer::U64(19_114); "example_1")]
-        fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::default(),
-                example_key,
-                answer,
-                &DAY,
-            );
+        fn with_range(&self, category: Category, range: Range) -> Self {
+            let mut part_combination = *self;
+            part_combination.ranges[category.idx()] = range;
+            part_combination
         }
-    }    
-}
 
-pub mod part_two {
+        pub fn combinations(&self) -> u64 {
+            self.ranges.iter().map(Range::len).product()
+        }
 
-    use std::collections::{HashMap, VecDeque};
+        pub fn contains(&self, part: &Part) -> bool {
+            self.ranges.iter().zip(part.ratings.iter()).all(|(range, rating)| range.contains(*rating))
+        }
+    }
 
-    use regex::Regex;
+    /// The workflows visited while sorting a part: each workflow with the
+    /// index of the rule that sent the part onwards (`rules.len()` for the
+    /// final destination), and whether the part ended up accepted.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct Trace {
+        pub hops: Vec<(String, usize)>,
+        pub accepted: bool,
+    }
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
-    use super::utils::{Category, Operation, Destination, Rule, Workflow};
+    /// What the range-splitting analysis found: the accepted parts as
+    /// disjoint hyperrectangles, and which workflows and rules any part can
+    /// reach.
+    #[derive(Debug, Default)]
+    struct Analysis {
+        accepted: Vec<PartCombination>,
+        reached_workflows: HashSet<String>,
+        firing_rules: HashSet<(String, usize)>, // the final destination has index `rules.len()`
+    }
 
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    enum SplitDestWorkflow {
-        Current,
-        New,
+    pub const START: &str = "in";
+
+    /// Returns a mapping of workflow name to workflow.
+    pub fn parse_workflows(section: &Section) -> HashMap<String, Workflow> {
+        let workflow_re = Regex::new(r"(?<name>[a-z]+)(?<workflow>.+)").unwrap();
+        section.lines()
+            .map(|line| {
+                let captures = workflow_re.captures(line).unwrap();
+                let name = String::from(captures.name("name").unwrap().as_str());
+                let workflow = Workflow::from_str(captures.name("workflow").unwrap().as_str());
+                (name, workflow)
+            })
+            .collect()
     }
 
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    struct Range {
-        min: u64, // inclusive
-        max: u64, // inclusive
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct Workflows {
+        workflows: HashMap<String, Workflow>,
     }
 
-    impl Default for Range {
-        fn default() -> Self {
-            Self {
-                min: 1,
-                max: 4_000,
+    impl Workflows {
+        pub fn new(workflows: HashMap<String, Workflow>) -> Self {
+            Self { workflows }
+        }
+
+        fn workflow(&self, name: &str) -> &Workflow {
+            self.workflows.get(name).expect("Should have a workflow matching this name.")
+        }
+
+        /// Sorts a part, recording every workflow it passes through.
+        pub fn evaluate(&self, part: &Part) -> Trace {
+            let mut hops = Vec::new();
+            let mut destination = Destination::Workflow(String::from(START));
+            while let Destination::Workflow(name) = destination {
+                assert!(hops.len() <= self.workflows.len(), "Workflows should not loop.");
+                let (rule_idx, next) = self.workflow(&name).process(part);
+                destination = next.clone();
+                hops.push((name, rule_idx));
             }
+            Trace { hops, accepted: destination == Destination::A }
         }
-    }
 
-    impl Range {
-        fn range(&self) -> u64 {
-            self.max - self.min + 1
+        /// Sends every possible part through the workflows at once, splitting
+        /// the hyperrectangle of parts at each rule.
+        fn analyze(&self) -> Analysis {
+            let mut analysis = Analysis::default();
+            let mut to_process = vec![(PartCombination::default(), String::from(START), 0)];
+            while let Some((part_combination, name, depth)) = to_process.pop() {
+                assert!(depth <= self.workflows.len(), "Workflows should not loop.");
+                analysis.reached_workflows.insert(name.clone());
+                let workflow = self.workflow(&name);
+                let mut route = |part_combination: PartCombination, destination: &Destination, rule_idx: usize| {
+                    analysis.firing_rules.insert((name.clone(), rule_idx));
+                    match destination {
+                        Destination::A => analysis.accepted.push(part_combination),
+                        Destination::R => {},
+                        Destination::Workflow(next) => to_process.push((part_combination, next.clone(), depth + 1)),
+                    }
+                };
+                let mut remaining = Some(part_combination);
+                for (rule_idx, rule) in workflow.rules.iter().enumerate() {
+                    let Some(current) = remaining else { break; };
+                    let (matching, rest) = rule.split(&current);
+                    if let Some(matching) = matching {
+                        route(matching, &rule.destination, rule_idx);
+                    }
+                    remaining = rest;
+                }
+                if let Some(rest) = remaining {
+                    route(rest, &workflow.final_dest, workflow.rules.len());
+                }
+            }
+            analysis
+        }
+
+        /// Returns every accepted part as a set of disjoint hyperrectangles.
+        pub fn accepted_combinations(&self) -> Vec<PartCombination> {
+            self.analyze().accepted
+        }
+
+        /// Returns the workflows no part can reach, sorted by name.
+        pub fn unreachable_workflows(&self) -> Vec<String> {
+            let reached = self.analyze().reached_workflows;
+            let mut unreachable: Vec<String> = self.workflows.keys()
+                .filter(|name| !reached.contains(*name))
+                .cloned()
+                .collect();
+            unreachable.sort();
+            unreachable
+        }
+
+        /// Returns the rules (by workflow and index) in reachable workflows
+        /// that no part can ever match, because earlier rules or the path to
+        /// the workflow already exclude them. Sorted by workflow then index.
+        pub fn unreachable_rules(&self) -> Vec<(String, usize)> {
+            let analysis = self.analyze();
+            let mut unreachable: Vec<(String, usize)> = analysis.reached_workflows.iter()
+                .flat_map(|name| {
+                    (0..self.workflow(name).rules.len()).map(|rule_idx| (name.clone(), rule_idx))
+                })
+                .filter(|rule| !analysis.firing_rules.contains(rule))
+                .collect();
+            unreachable.sort();
+            unreachable
         }
 
-        /// Returns a vector of 1-2 `Range`s and whether they should continue on to the next rule in the
-        /// current workflow or go to a new workflow.
-        fn split(self, threshold: u64, operation: Operation) -> Vec<(Range, SplitDestWorkflow)> {
-            let mut result = Vec::new();
-            if operation == Operation::GreaterThan && threshold >= self.max || operation == Operation::LessThan && threshold <= self.min {
-                result.push((self, SplitDestWorkflow::Current));
-            } else if operation == Operation::GreaterThan && threshold < self.min || operation == Operation::LessThan && threshold > self.max {
-                result.push((self, SplitDestWorkflow::New));
-            } else {
-                let (current, new) = match operation {
-                    Operation::GreaterThan => {
-                        (Range { min: self.min, max: threshold }, Range { min: threshold + 1, max: self.max })
-                    },
-                    Operation::LessThan => {
-                        (Range { min: threshold, max: self.max }, Range { min: self.min, max: threshold - 1 })
-                    },
+        /// Returns equivalent workflows (accepting exactly the same parts)
+        /// with redundant rules removed:
+        ///  - rules no part can match are dropped,
+        ///  - if no part reaches the final destination, the last rule becomes
+        ///    the final destination,
+        ///  - trailing rules that go to the final destination anyway are
+        ///    dropped,
+        ///  - workflows that send every part to the same place are inlined,
+        ///  - workflows no part can reach are dropped.
+        pub fn simplify(&self) -> Self {
+            let mut simplified = self.clone();
+            loop {
+                let before = simplified.clone();
+                let analysis = simplified.analyze();
+                simplified.workflows.retain(|name, _workflow| analysis.reached_workflows.contains(name));
+                for (name, workflow) in simplified.workflows.iter_mut() {
+                    let final_fires = analysis.firing_rules.contains(&(name.clone(), workflow.rules.len()));
+                    let mut rule_idx = 0;
+                    workflow.rules.retain(|_rule| {
+                        rule_idx += 1;
+                        analysis.firing_rules.contains(&(name.clone(), rule_idx - 1))
+                    });
+                    // Nothing gets past the last rule that fires, so it can
+                    // become the fallback.
+                    if !final_fires {
+                        if let Some(last) = workflow.rules.pop() {
+                            workflow.final_dest = last.destination;
+                        }
+                    }
+                    while workflow.rules.last().is_some_and(|rule| rule.destination == workflow.final_dest) {
+                        workflow.rules.pop();
+                    }
+                }
+                let constant: HashMap<String, Destination> = simplified.workflows.iter()
+                    .filter(|(name, workflow)| workflow.rules.is_empty() && name.as_str() != START)
+                    .map(|(name, workflow)| (name.clone(), workflow.final_dest.clone()))
+                    .collect();
+                let inline = |destination: &mut Destination| {
+                    if let Destination::Workflow(name) = destination {
+                        if let Some(constant_dest) = constant.get(name) {
+                            *destination = constant_dest.clone();
+                        }
+                    }
                 };
-                result.push((current, SplitDestWorkflow::Current));
-                result.push((new, SplitDestWorkflow::New));
+                simplified.workflows.values_mut()
+                    .for_each(|workflow| {
+                        workflow.rules.iter_mut().for_each(|rule| inline(&mut rule.destination));
+                        inline(&mut workflow.final_dest);
+                    });
+                if simplified == before {
+                    simplified.workflows.retain(|name, _workflow| !constant.contains_key(name));
+                    return simplified;
+                }
             }
-            return result;
         }
     }
 
-    #[derive(Debug, Default, PartialEq, Eq, Clone)]
-    struct PartCombination {
-        x: Range,
-        m: Range,
-        a: Range,
-        s: Range,
+    impl fmt::Display for Workflows {
+        /// Writes the workflows in the input format, sorted by name.
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut names: Vec<&String> = self.workflows.keys().collect();
+            names.sort();
+            for name in names {
+                writeln!(f, "{name}{}", self.workflows[name])?;
+            }
+            Ok(())
+        }
     }
 
-    impl PartCombination {
-        fn category_clone(&self, category: &Category) -> Range {
-            match category {
-                Category::X => self.x.clone(),
-                Category::M => self.m.clone(),
-                Category::A => self.a.clone(),
-                Category::S => self.s.clone(),
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{InputFileType, Sections, input_filename};
+        use super::*;
+        use super::super::{report, DAY};
+
+        fn workflows(lines: &[&str]) -> Workflows {
+            Workflows::new(parse_workflows(Sections::from_lines(lines.iter(), 1).nth(0)))
+        }
+
+        fn example() -> (Workflows, Vec<Part>) {
+            let sections = Sections::from_file(&input_filename(&DAY, InputFileType::Example(1)), 2);
+            (
+                Workflows::new(parse_workflows(sections.nth(0))),
+                sections.nth(1).lines().map(Part::from_str).collect(),
+            )
+        }
+
+        #[test]
+        fn evaluate_is_traced() {
+            let (workflows, parts) = example();
+            let hops = |names: &[(&str, usize)]| -> Vec<(String, usize)> {
+                names.iter().map(|(name, idx)| (String::from(*name), *idx)).collect()
+            };
+            assert_eq!(
+                workflows.evaluate(&parts[0]),
+                Trace { hops: hops(&[("in", 1), ("qqz", 0), ("qs", 1), ("lnx", 0)]), accepted: true },
+            );
+            assert_eq!(
+                workflows.evaluate(&parts[1]),
+                Trace { hops: hops(&[("in", 0), ("px", 2), ("rfg", 0), ("gd", 1)]), accepted: false },
+            );
+        }
+
+        #[test]
+        fn accepted_combinations_agree_with_evaluate() {
+            let (workflows, parts) = example();
+            let accepted = workflows.accepted_combinations();
+            for part in parts.iter() {
+                let count = accepted.iter().filter(|part_combination| part_combination.contains(part)).count();
+                assert_eq!(count, workflows.evaluate(part).accepted as usize);
             }
         }
 
-        fn combinations(&self) -> u64 {
-            self.x.range() * self.m.range() * self.a.range() * self.s.range()
+        #[test]
+        fn unreachable_rules_and_workflows_are_detected() {
+            let workflows = workflows(&[
+                "in{x>10:a,x>5:b,R}",
+                "a{x<5:R,m<100:R,A}",
+                "b{x>20:R,A}",
+                "c{A}",
+            ]);
+            assert_eq!(workflows.unreachable_workflows(), vec!["c"]);
+            assert_eq!(workflows.unreachable_rules(), vec![(String::from("a"), 0), (String::from("b"), 0)]);
         }
 
-        fn clone_with_new_category_range(&self, category: &Category, range: Range) -> PartCombination {
-            PartCombination {
-                x: if *category == Category::X { range } else { self.x },
-                m: if *category == Category::M { range } else { self.m },
-                a: if *category == Category::A { range } else { self.a },
-                s: if *category == Category::S { range } else { self.s },
+        #[test]
+        fn simplify_is_equivalent() {
+            let (workflows, parts) = example();
+            let simplified = workflows.simplify();
+            assert_eq!(
+                simplified.to_string(),
+                "crn{x>2662:A,R}\n\
+                 hdj{m>838:A,pv}\n\
+                 in{s<1351:px,qqz}\n\
+                 pv{a>1716:R,A}\n\
+                 px{a<2006:qkq,m>2090:A,rfg}\n\
+                 qkq{x<1416:A,crn}\n\
+                 qqz{s>2770:A,m<1801:hdj,R}\n\
+                 rfg{s<537:R,x>2440:R,A}\n",
+            );
+            let total = |workflows: &Workflows| -> u64 {
+                workflows.accepted_combinations().iter().map(PartCombination::combinations).sum()
+            };
+            assert_eq!(total(&simplified), total(&workflows));
+            for part in parts.iter() {
+                assert_eq!(simplified.evaluate(part).accepted, workflows.evaluate(part).accepted);
             }
         }
-    }
-    
-    impl Rule {
-        /// Applies the rule to a `PartCombination`. Returns 1-2 new `PartCombination`s, possibly one that should continue
-        /// on the current workflow, and possibly one that should have a new workflow as a destination.
-        fn split(&self, part_combination: &PartCombination) -> Vec<(PartCombination, SplitDestWorkflow)> {
-            let split_ranges = part_combination.category_clone(&self.category).split(self.threshold, self.operation);
-            split_ranges.into_iter()
-                .map(|(range, split_dest_workflow)| {
-                    (part_combination.clone_with_new_category_range(&self.category, range), split_dest_workflow)
-                }).collect()
+
+        #[test]
+        fn simplify_removes_redundant_rules() {
+            let redundant = workflows(&[
+                "in{x>10:a,x>5:b,R}",
+                "a{x<5:R,m<100:R,A}",
+                "b{x>20:R,m>5:A,A}",
+                "c{A}",
+            ]);
+            assert_eq!(redundant.simplify().to_string(), "a{m<100:R,A}\nin{x>10:a,x>5:A,R}\n");
+            let dangling = workflows(&[
+                "in{x>10:w,R}",
+                "w{x>5:A,m<100:z,R}",
+                "z{a>5:A,R}",
+            ]);
+            assert_eq!(dangling.simplify().to_string(), "in{x>10:A,R}\n");
+        }
+
+        #[test]
+        fn report_is_correct() {
+            let report = report(&input_filename(&DAY, InputFileType::Example(1)));
+            let lines: Vec<&str> = report.lines().collect();
+            assert_eq!(lines[..3], ["Unreachable workflows: []", "Unreachable rules: []", "Simplified workflows:"]);
+            assert_eq!(
+                lines[11..],
+                [
+                    "{x=787,m=2655,a=1222,s=2876}: in -> qqz -> qs -> lnx -> A",
+                    "{x=1679,m=44,a=2067,s=496}: in -> px -> rfg -> gd -> R",
+                    "{x=2036,m=264,a=79,s=2244}: in -> qqz -> hdj -> pv -> A",
+                    "{x=2461,m=1339,a=466,s=291}: in -> px -> qkq -> crn -> R",
+                    "{x=2127,m=1623,a=2188,s=1013}: in -> px -> rfg -> A",
+                ],
+            );
         }
     }
+}
 
-    #[derive(Debug, Default, PartialEq, Eq)]
-    pub struct Soln {
-        to_process: VecDeque<(PartCombination, Destination)>,
-        sum_of_combinations: u64,
-        workflows: HashMap<String, Workflow>,
+/// Describes the input's workflows: which workflows and rules are
+/// unreachable, the simplified workflows, and the path each part takes.
+pub fn report(filename: &str) -> String {
+    let sections = crate::utils::io_utils::Sections::from_file(filename, 2);
+    let workflows = utils::Workflows::new(utils::parse_workflows(sections.nth(0)));
+    let mut report = format!("Unreachable workflows: {:?}\n", workflows.unreachable_workflows());
+    report.push_str(&format!("Unreachable rules: {:?}\n", workflows.unreachable_rules()));
+    report.push_str(&format!("Simplified workflows:\n{}", workflows.simplify()));
+    for line in sections.nth(1).lines() {
+        let trace = workflows.evaluate(&utils::Part::from_str(line));
+        let path: Vec<&str> = trace.hops.iter().map(|(name, _rule_idx)| name.as_str()).collect();
+        let result = if trace.accepted { "A" } else { "R" };
+        report.push_str(&format!("{line}: {} -> {result}\n", path.join(" -> ")));
     }
+    report
+}
+
+pub mod part_one {
+    use crate::utils::{io_utils::Sections, solution::{Answer, Solution}};
+    use super::utils::{self, Part, Workflows};
+
+    #[derive(Debug, Default, PartialEq, Eq)]
+    pub struct Soln {}
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.parse_input_file(filename);
-            self.find_all_combinations();
-            Answer::U64(self.sum_of_combinations())
-        }
-    }
-
-    impl Soln {
-        fn parse_input_file(&mut self, filename: &str) {
-            let workflow_re = Regex::new(r"(?<name>[a-z]+)(?<workflow>.+)").unwrap();
-            for line in io_utils::file_to_lines(filename) {
-                if line.is_empty() { 
-                    return;
-                } else {
-                    let captures = workflow_re.captures(&line).unwrap();
-                    let name = String::from(captures.name("name").unwrap().as_str());
-                    let workflow = Workflow::from_str(captures.name("workflow").unwrap().as_str());
-                    self.workflows.insert(name, workflow);
-                }
-            }
+            let sections = Sections::from_file(filename, 2);
+            let accepted = Workflows::new(utils::parse_workflows(sections.nth(0))).accepted_combinations();
+            let sum_of_accepted = sections.nth(1)
+                .lines()
+                .map(Part::from_str)
+                .filter(|part| accepted.iter().any(|part_combination| part_combination.contains(part)))
+                .map(|part| part.sum_of_ratings())
+                .sum();
+            Answer::U64(sum_of_accepted)
         }
+    }
 
-        fn find_all_combinations(&mut self) {
-            self.to_process.push_back((PartCombination::default(), Destination::Workflow(String::from("in"))));
-            while !self.to_process.is_empty() {
-                let (part_combination, dest) = self.to_process.pop_front().unwrap();
-                match dest {
-                    Destination::A => self.sum_of_combinations += part_combination.combinations(),
-                    Destination::R => {},
-                    Destination::Workflow(workflow_name) => self.process_workflow(&workflow_name, part_combination),
-                }
-            }
-        }
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
 
-        fn process_workflow(&mut self, workflow_name: &str, mut part_combination: PartCombination) {
-            let workflow = self.workflows.get(workflow_name).expect("Workflow with name should exist");
-            for rule in &workflow.rules {
-                for (pc, split_dest_workflow) in rule.split(&part_combination) {
-                    match split_dest_workflow {
-                        SplitDestWorkflow::Current => part_combination = pc,
-                        SplitDestWorkflow::New => self.to_process.push_back((pc, rule.destination.clone())),
-                    }
-                }
-            }
-            self.to_process.push_back((part_combination, workflow.final_dest.clone()));
+        #[test_case(1, Answer::U64(19_114); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
         }
+    }
+}
 
-        fn sum_of_combinations(&self) -> u64 {
-            self.sum_of_combinations
-        }
+pub mod part_two {
+    use crate::utils::{io_utils::Sections, solution::{Answer, Solution}};
+    use super::utils::{self, PartCombination, Workflows};
+
+    #[derive(Debug, Default, PartialEq, Eq)]
+    pub struct Soln {}
 
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let sections = Sections::from_file(filename, 2);
+            let sum_of_combinations = Workflows::new(utils::parse_workflows(sections.nth(0)))
+                .accepted_combinations()
+                .iter()
+                .map(PartCombination::combinations)
+                .sum();
+            Answer::U64(sum_of_combinations)
+        }
     }
 
     #[cfg(test)]
@@ -425,5 +691,5 @@ pub mod part_two {
                 &DAY,
             );
         }
-    }    
-}
\ No newline at end of file
+    }
+}
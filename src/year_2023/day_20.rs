@@ -3,9 +3,14 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2023, day: 20 };
 
+use itertools::Itertools;
+
+use crate::utils::dot::Graph;
+
 mod utils {
     use std::collections::{HashMap, HashSet, VecDeque};
 
+    use itertools::Itertools;
     use regex::Regex;
 
     use crate::utils::io_utils;
@@ -26,6 +31,11 @@ mod utils {
     pub trait Module {
         fn receive(&mut self, pulse: Pulse, sender: &str) -> VecDeque<PulseInProcess>;
         fn set_input_modules(&mut self, inputs: Vec<String>);
+        fn module_type(&self) -> &'static str;
+        fn destination_modules(&self) -> &[String];
+        /// Appends the module's internal state (what it remembers between
+        /// pulses) to `state`.
+        fn write_state(&self, _state: &mut Vec<bool>) {}
     }
 
     #[derive(Debug, PartialEq, Eq)]
@@ -57,6 +67,18 @@ mod utils {
         }
 
         fn set_input_modules(&mut self, _inputs: Vec<String>) {}
+
+        fn module_type(&self) -> &'static str {
+            "flip-flop"
+        }
+
+        fn destination_modules(&self) -> &[String] {
+            &self.destination_modules
+        }
+
+        fn write_state(&self, state: &mut Vec<bool>) {
+            state.push(self.on);
+        }
     }
 
     impl FlipFlopModule {
@@ -94,6 +116,20 @@ mod utils {
                 self.most_recent_pulses.insert(input, Pulse::Low);
             })            
         }
+
+        fn module_type(&self) -> &'static str {
+            "conjunction"
+        }
+
+        fn destination_modules(&self) -> &[String] {
+            &self.destination_modules
+        }
+
+        fn write_state(&self, state: &mut Vec<bool>) {
+            self.most_recent_pulses.iter()
+                .sorted_by_key(|(input, _pulse)| *input)
+                .for_each(|(_input, pulse)| state.push(*pulse == Pulse::High));
+        }
     }
 
     impl ConjunctionModule {
@@ -124,6 +160,14 @@ mod utils {
         }
 
         fn set_input_modules(&mut self, _inputs: Vec<String>) {}
+
+        fn module_type(&self) -> &'static str {
+            "broadcaster"
+        }
+
+        fn destination_modules(&self) -> &[String] {
+            &self.destination_modules
+        }
     }
 
     impl BroadcasterModule {
@@ -147,6 +191,14 @@ mod utils {
         }
 
         fn set_input_modules(&mut self, _inputs: Vec<String>) {}
+
+        fn module_type(&self) -> &'static str {
+            "end"
+        }
+
+        fn destination_modules(&self) -> &[String] {
+            &self.destination_modules
+        }
     }
 
     impl EndModule {
@@ -214,68 +266,187 @@ mod utils {
             });
         modules
     }
-}
-
-pub mod part_one {
 
-    use std::collections::{HashMap, VecDeque};
-
-    use crate::utils::solution::{Answer, Solution};
-    use super::utils::{self, Module, Pulse, PulseInProcess};
+    /// The number of low and high pulses a module has sent.
+    #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+    pub struct PulseCounts {
+        pub low: u64,
+        pub high: u64,
+    }
 
-    #[derive(Default)]
-    pub struct Soln {
-        low_pulses: u32,
-        high_pulses: u32,
-        pulses_in_process: VecDeque<PulseInProcess>,
+    /// A network of modules that can be driven by pushing the button.
+    pub struct Network {
         modules: HashMap<String, Box<dyn Module>>,
+        button_pushes: u64,
+        pulses_sent: HashMap<String, PulseCounts>,
     }
 
-    impl Solution for Soln {
-        fn solve(&mut self, filename: &str) -> Answer {
-            self.parse_input_file(filename);
-            for _ in 0..1000 { // TODO: could try to find where it's back in original state and short-circuit
-                self.process_button_push();
+    impl Network {
+        pub fn from_file(filename: &str) -> Self {
+            Self {
+                modules: parse_input_file(filename),
+                button_pushes: 0,
+                pulses_sent: HashMap::new(),
             }
-            Answer::U32(self.product_of_pulses())
         }
-    }
 
-    impl Soln {
-        fn parse_input_file(&mut self, filename: &str) {
-            self.modules = utils::parse_input_file(filename);
+        pub fn button_pushes(&self) -> u64 {
+            self.button_pushes
         }
 
-        fn process_button_push(&mut self) {
-            self.pulses_in_process.push_back(PulseInProcess { sender: String::from("button"), pulse: Pulse::Low, recipient: String::from("broadcaster")});
-            while !self.pulses_in_process.is_empty() {
-                let pip = self.pulses_in_process.pop_front().unwrap();
-                self.process_pip(pip);
+        /// Pushes the button and processes pulses until the network settles,
+        /// calling `observe` on every pulse as it is delivered.
+        pub fn push_button(&mut self, mut observe: impl FnMut(&PulseInProcess)) {
+            self.button_pushes += 1;
+            let mut pulses_in_process = VecDeque::from([
+                PulseInProcess { sender: String::from("button"), pulse: Pulse::Low, recipient: String::from("broadcaster")},
+            ]);
+            while let Some(pip) = pulses_in_process.pop_front() {
+                observe(&pip);
+                let counts = self.pulses_sent.entry(pip.sender.clone()).or_default();
+                match pip.pulse {
+                    Pulse::Low => counts.low += 1,
+                    Pulse::High => counts.high += 1,
+                }
+                let recipient = self.modules.get_mut(&pip.recipient)
+                    .expect("Recipient module should exist");
+                pulses_in_process.append(&mut recipient.receive(pip.pulse, &pip.sender));
             }
         }
 
-        fn process_pip(&mut self, pip: PulseInProcess) {
-            match pip.pulse {
-                Pulse::Low => self.low_pulses += 1,
-                Pulse::High => self.high_pulses += 1,
+        /// Returns a histogram of the pulses each module (including the
+        /// button) has sent so far.
+        pub fn pulses_sent(&self) -> &HashMap<String, PulseCounts> {
+            &self.pulses_sent
+        }
+
+        /// Returns the total pulses sent by every module so far.
+        pub fn total_pulses_sent(&self) -> PulseCounts {
+            self.pulses_sent().values()
+                .fold(PulseCounts::default(), |total, counts| PulseCounts {
+                    low: total.low + counts.low,
+                    high: total.high + counts.high,
+                })
+        }
+
+        /// Returns the histogram as one line per module, sorted by name.
+        pub fn pulse_histogram(&self) -> String {
+            self.pulses_sent.iter()
+                .sorted_by_key(|(name, _counts)| *name)
+                .map(|(name, counts)| format!("{name:>11}: {:>8} low {:>8} high", counts.low, counts.high))
+                .join("\n")
+        }
+
+        pub fn module_type(&self, name: &str) -> Option<&'static str> {
+            self.modules.get(name).map(|module| module.module_type())
+        }
+
+        /// Returns the names of the modules that send pulses to `name`,
+        /// sorted.
+        pub fn inputs(&self, name: &str) -> Vec<String> {
+            self.modules.iter()
+                .filter(|(_input, module)| module.destination_modules().iter().any(|dest| dest == name))
+                .map(|(input, _module)| input.clone())
+                .sorted()
+                .collect()
+        }
+
+        /// Returns every module reachable from `start` without passing
+        /// through `barrier`, sorted.
+        pub fn reachable(&self, start: &str, barrier: &str) -> Vec<String> {
+            let mut seen = HashSet::from([String::from(start)]);
+            let mut to_visit = vec![String::from(start)];
+            while let Some(name) = to_visit.pop() {
+                self.modules[&name].destination_modules()
+                    .iter()
+                    .filter(|dest| *dest != barrier)
+                    .for_each(|dest| {
+                        if seen.insert(dest.clone()) {
+                            to_visit.push(dest.clone());
+                        }
+                    });
             }
-            let recipient = self.modules.get_mut(&pip.recipient)
-                .expect("Recipient module should exist");
-            self.pulses_in_process.append(&mut recipient.receive(pip.pulse, &pip.sender));
+            seen.into_iter().sorted().collect()
+        }
+
+        pub fn broadcast_destinations(&self) -> Vec<String> {
+            self.modules.get("broadcaster")
+                .expect("Network should have a broadcaster.")
+                .destination_modules()
+                .to_vec()
         }
 
-        fn product_of_pulses(&self) -> u32 {
-            self.low_pulses * self.high_pulses
+        /// Returns the combined internal state of the given modules.
+        pub fn state(&self, names: &[String]) -> Vec<bool> {
+            let mut state = Vec::new();
+            names.iter().for_each(|name| self.modules[name].write_state(&mut state));
+            state
         }
+    }
+}
 
+/// Pushes the button `button_pushes` times and returns a histogram of the
+/// pulses each module sent, for debugging.
+pub fn pulse_histogram(filename: &str, button_pushes: u64) -> String {
+    let mut network = utils::Network::from_file(filename);
+    for _ in 0..button_pushes {
+        network.push_button(|_pip| {});
+    }
+    network.pulse_histogram()
+}
+
+/// Returns the module configuration as a graph, with each module's type as a
+/// node attribute. Conjunctions are drawn as boxes so the ones gating `rx`
+/// stand out.
+pub fn graph(filename: &str) -> Graph {
+    let modules = utils::parse_input_file(filename);
+    let mut graph = Graph::directed("modules");
+    modules.iter()
+        .sorted_by_key(|(name, _module)| *name)
+        .for_each(|(name, module)| {
+            let shape = match module.module_type() {
+                "conjunction" => "box",
+                "broadcaster" => "doublecircle",
+                "end" => "doubleoctagon",
+                _ => "ellipse",
+            };
+            graph.node(name)
+                .attr("module_type", module.module_type())
+                .attr("shape", shape);
+        });
+    modules.iter()
+        .sorted_by_key(|(name, _module)| *name)
+        .for_each(|(name, module)| {
+            module.destination_modules().iter().for_each(|dest| { graph.edge(name, dest); });
+        });
+    graph
+}
+
+pub mod part_one {
+
+    use crate::utils::solution::{Answer, Solution};
+    use super::utils::Network;
+
+    #[derive(Default)]
+    pub struct Soln {}
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            let mut network = Network::from_file(filename);
+            for _ in 0..1000 { // TODO: could try to find where it's back in original state and short-circuit
+                network.push_button(|_pip| {});
+            }
+            let total = network.total_pulses_sent();
+            Answer::U32((total.low * total.high).try_into().unwrap())
+        }
     }
 
     #[cfg(test)]
     mod tests {
         use test_case::test_case;
-        use crate::utils::{test_utils, solution::Answer};
+        use crate::utils::{io_utils::{self, InputFileType}, test_utils, solution::Answer};
         use super::*;
-        use super::super::DAY;
+        use super::super::{pulse_histogram, utils::PulseCounts, DAY};
 
         #[test_case(1, Answer::U32(32_000_000); "example_1")]
         #[test_case(2, Answer::U32(11_687_500); "example_2")]
@@ -287,25 +458,52 @@ pub mod part_one {
                 &DAY,
             );
         }
+
+        #[test]
+        fn pulses_sent_are_correct() {
+            let mut network = Network::from_file(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            network.push_button(|_pip| {});
+            let pulses_sent = network.pulses_sent();
+            assert_eq!(pulses_sent["button"], PulseCounts { low: 1, high: 0 });
+            assert_eq!(pulses_sent["broadcaster"], PulseCounts { low: 3, high: 0 });
+            assert_eq!(pulses_sent["inv"], PulseCounts { low: 1, high: 1 });
+            assert_eq!(network.total_pulses_sent(), PulseCounts { low: 8, high: 4 });
+        }
+
+        #[test]
+        fn pulse_histogram_is_correct() {
+            let histogram = pulse_histogram(&io_utils::input_filename(&DAY, InputFileType::Example(1)), 1_000);
+            assert_eq!(
+                histogram.lines().collect::<Vec<&str>>(),
+                vec![
+                    "          a:     1000 low     1000 high",
+                    "          b:     1000 low     1000 high",
+                    "broadcaster:     3000 low        0 high",
+                    "     button:     1000 low        0 high",
+                    "          c:     1000 low     1000 high",
+                    "        inv:     1000 low     1000 high",
+                ],
+            );
+        }
     }    
 }
 
 /// This requires at least a partial decompilation approach, 
 /// as a brute force approach sending button pushes has an 
-/// infeasible runtime. There are 4 loops that operate in
-/// parallel. Each loop feeds into a conjunction module
-/// which feeds directly and solely into another conjunction
-/// module. Those 4 conjunction modules are the sole inputs
-/// to a conjunction module, which has only one destination
-/// module: the one we are trying to track when it receives
-/// its first low pulse.
+/// infeasible runtime. The module we are tracking has a single
+/// conjunction module as its input, and each of the broadcaster's
+/// destinations starts an independent sub-network (a counter) that
+/// eventually feeds that conjunction. The final conjunction sends
+/// a low pulse once every sub-network has sent it a high pulse
+/// during the same button push.
 /// 
-/// If the 4 independent loops are periodic (there is a calculable
-/// number of button pushes, after which the conjunction module
-/// at the end of the loop sends a low pulse, on repeat), the 
-/// number of button pushes after which all 4 loops will emit a
-/// low pulse is the least common multiple of the periods of each
-/// loop.
+/// Each sub-network is found by walking from its broadcaster
+/// destination up to (but not through) the final conjunction. Its
+/// period is found by hashing the state of its modules after every
+/// button push until a state repeats. If each sub-network fires
+/// exactly on the multiples of its period, the number of button
+/// pushes after which all of them fire together is the least common
+/// multiple of the periods.
 /// 
 /// The diagram looks like this:
 /// 
@@ -424,95 +622,144 @@ pub mod part_one {
 /// ```
 pub mod part_two {
 
-    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::collections::HashMap;
 
     use crate::utils::{math_utils, solution::{Answer, Solution}};
-    use super::utils::{self, Module, Pulse, PulseInProcess};
+    use super::utils::{Network, Pulse};
 
+    /// A cycle in a sub-network's state: the state after `start` button
+    /// pushes is the same as the state after `start + len` pushes.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct Cycle {
+        start: u64,
+        len: u64,
+    }
+
+    /// An independent part of the network that feeds the final conjunction
+    /// through its `output` module.
     #[derive(Debug, PartialEq, Eq)]
-    struct Period {
-        module_name: String,
-        period: u64,
-        confirmed: bool,        
+    struct SubNetwork {
+        modules: Vec<String>,
+        output: String,
+        seen_states: HashMap<Vec<bool>, u64>,
+        firings: Vec<u64>, // button pushes during which `output` sent a high pulse to the final conjunction
+        cycle: Option<Cycle>,
+    }
+
+    impl SubNetwork {
+        /// Returns the period after which the sub-network fires, panicking if
+        /// it doesn't fire on exactly the multiples of its cycle length.
+        fn period(&self) -> u64 {
+            let cycle = self.cycle.expect("Cycle should have been found.");
+            let expected: Vec<u64> = (1..=(cycle.start + cycle.len) / cycle.len)
+                .map(|multiple| multiple * cycle.len)
+                .collect();
+            assert_eq!(
+                self.firings,
+                expected,
+                "Sub-network feeding `{}` should fire exactly on the multiples of its cycle length.",
+                self.output,
+            );
+            cycle.len
+        }
     }
 
     pub struct Soln {
-        button_pushes: u64,
-        pulses_in_process: VecDeque<PulseInProcess>,
-        modules: HashMap<String, Box<dyn Module>>,
-        critical_modules: HashSet<String>,
-        low_pulse_periods: HashMap<String, Period>, // tracks the period of the low pulses sent from the critical modules.
+        target: String,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_critical_modules(
-                HashSet::from([
-                    String::from("vt"),
-                    String::from("qt"),
-                    String::from("dq"),
-                    String::from("nl"),
-                ]))
+            Self {
+                target: String::from("rx"),
+            }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.parse_input_file(filename);
-            while self.low_pulse_periods.len() < self.critical_modules.len() || self.low_pulse_periods.values().any(|period| !period.confirmed) {
-                self.process_button_push();
-            }
-            Answer::U64(math_utils::least_common_multiple(self.low_pulse_periods.values().clone().map(|period| period.period)))
+            let mut network = Network::from_file(filename);
+            Answer::U64(self.fewest_button_pushes(&mut network))
         }
     }
 
     impl Soln {
-        fn with_critical_modules(critical_modules: HashSet<String>) -> Self {
-            Self {
-                button_pushes: 0,
-                pulses_in_process: VecDeque::new(),
-                modules: HashMap::new(),
-                critical_modules,
-                low_pulse_periods: HashMap::new(),
+        fn fewest_button_pushes(&self, network: &mut Network) -> u64 {
+            let final_conjunction = self.final_conjunction(network);
+            let mut sub_networks = Self::sub_networks(network, &final_conjunction);
+            sub_networks.iter_mut()
+                .for_each(|sub_network| {
+                    sub_network.seen_states.insert(network.state(&sub_network.modules), 0);
+                });
+            while sub_networks.iter().any(|sub_network| sub_network.cycle.is_none()) {
+                let mut fired: Vec<String> = Vec::new();
+                network.push_button(|pip| {
+                    if pip.recipient == final_conjunction && pip.pulse == Pulse::High {
+                        fired.push(pip.sender.clone());
+                    }
+                });
+                let button_pushes = network.button_pushes();
+                sub_networks.iter_mut()
+                    .filter(|sub_network| sub_network.cycle.is_none())
+                    .for_each(|sub_network| {
+                        if fired.contains(&sub_network.output) {
+                            sub_network.firings.push(button_pushes);
+                        }
+                        let state = network.state(&sub_network.modules);
+                        match sub_network.seen_states.get(&state) {
+                            Some(start) => sub_network.cycle = Some(Cycle { start: *start, len: button_pushes - start }),
+                            None => { sub_network.seen_states.insert(state, button_pushes); },
+                        }
+                    });
             }
+            math_utils::least_common_multiple(sub_networks.iter().map(|sub_network| sub_network.period()))
         }
-        
-        fn parse_input_file(&mut self, filename: &str) {
-            self.modules = utils::parse_input_file(filename);
+
+        /// Returns the conjunction module that is the sole input to the
+        /// target module.
+        fn final_conjunction(&self, network: &Network) -> String {
+            let inputs = network.inputs(&self.target);
+            assert_eq!(inputs.len(), 1, "`{}` should have exactly one input.", self.target);
+            let final_conjunction = inputs.into_iter().next().unwrap();
+            assert_eq!(
+                network.module_type(&final_conjunction),
+                Some("conjunction"),
+                "The input to `{}` should be a conjunction module.", self.target,
+            );
+            final_conjunction
         }
 
-        fn process_button_push(&mut self) {
-            self.button_pushes += 1;
-            self.pulses_in_process.push_back(PulseInProcess { sender: String::from("button"), pulse: Pulse::Low, recipient: String::from("broadcaster")});
-            while !self.pulses_in_process.is_empty() {
-                let pip = self.pulses_in_process.pop_front().unwrap();
-                if self.critical_modules.contains(&pip.sender) && pip.pulse == Pulse::Low {
-                    self.low_pulse_periods.entry(pip.sender.clone())
-                        .and_modify(|period| {
-                            if self.button_pushes > period.period {
-                                if self.button_pushes % period.period == 0 {
-                                    period.confirmed = true;
-                                } else {
-                                    panic!("non-periodic critical module found.");
-                                }
-                            }
-                        })
-                        .or_insert(Period { 
-                            module_name: pip.sender.clone(),
-                            period: self.button_pushes,
-                            confirmed: false,
-                        });
+        /// Splits the network into the sub-networks started by each of the
+        /// broadcaster's destinations, checking that they are independent.
+        fn sub_networks(network: &Network, final_conjunction: &str) -> Vec<SubNetwork> {
+            let final_inputs = network.inputs(final_conjunction);
+            let sub_networks: Vec<SubNetwork> = network.broadcast_destinations()
+                .iter()
+                .map(|start| {
+                    let modules = network.reachable(start, final_conjunction);
+                    let outputs: Vec<&String> = modules.iter()
+                        .filter(|name| final_inputs.contains(*name))
+                        .collect();
+                    assert_eq!(outputs.len(), 1, "Sub-network starting at `{start}` should feed the final conjunction exactly once.");
+                    SubNetwork {
+                        output: outputs[0].clone(),
+                        modules,
+                        seen_states: HashMap::new(),
+                        firings: Vec::new(),
+                        cycle: None,
+                    }
+                })
+                .collect();
+            for (idx, sub_network) in sub_networks.iter().enumerate() {
+                for other in sub_networks[idx + 1..].iter() {
+                    assert!(
+                        sub_network.modules.iter().all(|name| !other.modules.contains(name)),
+                        "Sub-networks feeding `{}` and `{}` should not share modules.", sub_network.output, other.output,
+                    );
                 }
-                self.process_pip(pip);
             }
+            sub_networks
         }
-
-        fn process_pip(&mut self, pip: PulseInProcess) {
-            let recipient = self.modules.get_mut(&pip.recipient)
-                .expect("Recipient module should exist");
-            self.pulses_in_process.append(&mut recipient.receive(pip.pulse, &pip.sender));
-        }
-
     }
 
     #[cfg(test)]
@@ -522,13 +769,13 @@ pub mod part_two {
         use super::*;
         use super::super::DAY;
 
-        #[test_case(3, "vt", Answer::U64(4_003); "vt_loop")]
-        #[test_case(4, "qt", Answer::U64(3_797); "qt_loop")]
-        #[test_case(5, "dq", Answer::U64(3_881); "dq_loop")]
-        #[test_case(6, "nl", Answer::U64(3_823); "nl_loop")]
-        fn examples_are_correct(example_key: u8, critical_module: &str, answer: Answer) {
+        #[test_case(3, Answer::U64(4_003); "vt_loop")]
+        #[test_case(4, Answer::U64(3_797); "qt_loop")]
+        #[test_case(5, Answer::U64(3_881); "dq_loop")]
+        #[test_case(6, Answer::U64(3_823); "nl_loop")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
             test_utils::check_example_case(
-                &mut Soln::with_critical_modules(HashSet::from([String::from(critical_module)])),
+                &mut Soln::default(),
                 example_key,
                 answer,
                 &DAY,
@@ -3,114 +3,203 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2023, day: 21 };
 
-pub mod part_one {
-
-    use std::collections::{HashMap, HashSet, VecDeque};
+mod utils {
+    use std::collections::HashSet;
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::{io_utils, math_utils};
 
     #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
     struct Point {
-        row: usize,
-        col: usize,
-    }
-
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    struct Path {
-        point: Point,
-        steps: usize,
+        row: isize,
+        col: isize,
     }
 
+    /// The garden from the puzzle input: a single tile of garden plots and
+    /// rocks, with the elf starting at `S`.
     #[derive(Debug, Default)]
-    pub struct Soln {
-        rows: usize,
-        cols: usize,
+    pub struct Garden {
+        rows: isize,
+        cols: isize,
         start: Point,
         rocks: HashSet<Point>,
     }
 
-    impl Solution for Soln {
-        fn solve(&mut self, filename: &str) -> Answer {
-            self.parse_input_file(filename);
-            let mut shortest_paths: HashMap<Point, usize> = HashMap::new();
-            let mut queue = VecDeque::from([
-                Path {
-                    point: self.start,
-                    steps: 0,
-                },
-            ]);
-            while !queue.is_empty() {
-                let path = queue.pop_front().unwrap();
-                if shortest_paths.contains_key(&path.point) { continue; }
-                shortest_paths.insert(path.point, path.steps);
-                queue.append(&mut self.next_paths(&path));
-            }
-            Answer::Usize(
-                shortest_paths.values()
-                    .filter(|steps| **steps % 2 == 0 && **steps <= 64)
-                    .count()
-            )
+    impl Garden {
+        pub fn from_file(filename: &str) -> Self {
+            let mut garden = Self::default();
+            io_utils::file_to_lines(filename)
+                .for_each(|line| {
+                    garden.cols = line.len() as isize;
+                    garden.rocks.extend(
+                        line.char_indices()
+                            .filter(|(_col, ch)| *ch == '#')
+                            .map(|(col, _ch)| Point { row: garden.rows, col: col as isize })
+                    );
+                    if let Some(col) = line.find('S') {
+                        garden.start = Point { row: garden.rows, col: col as isize };
+                    }
+                    garden.rows += 1;
+                });
+            garden
         }
-    }
 
-    impl Soln {
-        fn next_paths(&self, path: &Path) -> VecDeque<Path> {
-            let mut result = VecDeque::new();
-            if path.point.row != 0 {
-                let point = Point { 
-                    row: path.point.row - 1,
-                    col: path.point.col,
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
+        /// The number of plots the elf can end on after exactly `steps`
+        /// steps, in the single tile or, if `tiled`, in the infinitely tiled
+        /// garden. This walks every step, so it's only practical for small
+        /// numbers of steps.
+        pub fn reachable_plots_brute_force(&self, steps: usize, tiled: bool) -> usize {
+            let mut walk = Walk::new(self, tiled);
+            while walk.steps < steps && walk.step() {}
+            walk.reachable_plots(steps)
+        }
+
+        /// The number of plots the elf can end on after exactly `steps`
+        /// steps in the infinitely tiled garden.
+        ///
+        /// Far enough from the start, each step into another copy of the
+        /// tile adds the same pattern of plots as the copy before it, so the
+        /// number of plots reachable in `r + k * period` steps is quadratic
+        /// in `k` once the walk has warmed up. The period is twice the
+        /// tile's size since a tile of odd size flips the parity of the
+        /// plots in the next copy. This walks (by brute force) until the
+        /// second differences of these counts settle, then extrapolates.
+        pub fn reachable_plots(&self, steps: usize) -> usize {
+            const SETTLED_SECOND_DIFFERENCES: usize = 3;
+
+            let tile_size = math_utils::least_common_multiple([self.rows as u64, self.cols as u64].into_iter());
+            let period = 2 * tile_size as usize;
+            let remainder = steps % period;
+            let mut walk = Walk::new(self, true);
+            let mut samples: Vec<i64> = Vec::new();
+            loop {
+                let sample_steps = remainder + samples.len() * period;
+                if sample_steps >= steps {
+                    while walk.steps < steps && walk.step() {}
+                    return walk.reachable_plots(steps);
                 }
-            }
-            if path.point.col != 0 {
-                let point = Point { 
-                    row: path.point.row,
-                    col: path.point.col - 1, 
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
+                while walk.steps < sample_steps && walk.step() {}
+                samples.push(walk.reachable_plots(sample_steps) as i64);
+                let second_differences: Vec<i64> = samples.windows(3)
+                    .map(|window| window[2] - 2 * window[1] + window[0])
+                    .collect();
+                if second_differences.len() >= SETTLED_SECOND_DIFFERENCES
+                    && second_differences.iter()
+                        .rev()
+                        .take(SETTLED_SECOND_DIFFERENCES)
+                        .all(|difference| *difference == second_differences[second_differences.len() - 1])
+                {
+                    let base = samples.len() - 3;
+                    let value = samples[base];
+                    let first_difference = samples[base + 1] - samples[base];
+                    let second_difference = second_differences[second_differences.len() - 1];
+                    let k = ((steps - remainder) / period - base) as i64;
+                    return (value + k * first_difference + k * (k - 1) / 2 * second_difference)
+                        .try_into()
+                        .expect("Number of reachable plots should be positive.");
                 }
             }
-            if path.point.row != self.rows - 1 {
-                let point = Point { 
-                    row: path.point.row + 1,
-                    col: path.point.col, 
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
-                }
+        }
+
+        fn is_plot(&self, point: &Point, tiled: bool) -> bool {
+            if tiled {
+                !self.rocks.contains(&Point {
+                    row: point.row.rem_euclid(self.rows),
+                    col: point.col.rem_euclid(self.cols),
+                })
+            } else {
+                (0..self.rows).contains(&point.row)
+                    && (0..self.cols).contains(&point.col)
+                    && !self.rocks.contains(point)
             }
-            if path.point.col != self.cols - 1 {
-                let point = Point { 
-                    row: path.point.row,
-                    col: path.point.col + 1, 
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
-                }
+        }
+    }
+
+    /// A breadth-first walk out from the start, one step at a time.
+    ///
+    /// Only the last two frontiers are kept: a plot first reached in `n`
+    /// steps has neighbours first reached in `n - 1`, `n` or `n + 1` steps.
+    /// Since the elf can step back and forth, a plot first reached in `n`
+    /// steps can be ended on after any `m >= n` steps with the same parity,
+    /// so the walk only needs to count the plots reached in an even and an
+    /// odd number of steps.
+    struct Walk<'a> {
+        garden: &'a Garden,
+        tiled: bool,
+        steps: usize,
+        previous: HashSet<Point>,
+        current: HashSet<Point>,
+        reached_by_parity: [usize; 2],
+    }
+
+    impl<'a> Walk<'a> {
+        fn new(garden: &'a Garden, tiled: bool) -> Self {
+            Self {
+                garden,
+                tiled,
+                steps: 0,
+                previous: HashSet::new(),
+                current: HashSet::from([garden.start]),
+                reached_by_parity: [1, 0],
             }
-            result
         }
 
-        fn parse_input_file(&mut self, filename: &str) {
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    self.cols = line.len();
-                    self.rocks.extend(
-                        line.char_indices()
-                            .filter(|(_col, ch)| *ch == '#')
-                            .map(|(col, _ch)| Point { row: self.rows, col })
-                    );
-                    if let Some(col) = line.find('S') {
-                        self.start = Point { row: self.rows, col };
-                    }
-                    self.rows += 1;
-                });
+        /// Takes another step. Returns `false` (without changing anything)
+        /// once there are no new plots to reach.
+        fn step(&mut self) -> bool {
+            let next: HashSet<Point> = self.current.iter()
+                .flat_map(|point| [
+                    Point { row: point.row - 1, col: point.col },
+                    Point { row: point.row + 1, col: point.col },
+                    Point { row: point.row, col: point.col - 1 },
+                    Point { row: point.row, col: point.col + 1 },
+                ])
+                .filter(|point| {
+                    !self.current.contains(point)
+                    && !self.previous.contains(point)
+                    && self.garden.is_plot(point, self.tiled)
+                })
+                .collect();
+            if next.is_empty() { return false; }
+            self.steps += 1;
+            self.reached_by_parity[self.steps % 2] += next.len();
+            self.previous = std::mem::replace(&mut self.current, next);
+            true
+        }
+
+        /// The number of plots that can be ended on after exactly `steps`
+        /// steps, which must be the number of steps walked so far (or more,
+        /// if the walk has finished).
+        fn reachable_plots(&self, steps: usize) -> usize {
+            self.reached_by_parity[steps % 2]
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Garden;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        steps: usize,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { steps: 64 }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            Answer::Usize(Garden::from_file(filename).reachable_plots_brute_force(self.steps, false))
         }
 
+        solution_params! {
+            steps: "Number of steps the elf takes",
+        }
     }
 
     #[cfg(test)]
@@ -129,203 +218,57 @@ pub mod part_one {
                 &DAY,
             );
         }
-    }    
+
+        #[test_case(1, 6, Answer::Usize(16); "example_1")]
+        fn examples_with_steps_are_correct(example_key: u8, steps: usize, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("steps", steps.to_string())],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
 }
 
-/// This solution is based on [this explanation](https://github.com/villuna/aoc23/wiki/A-Geometric-solution-to-advent-of-code-2023,-day-21).
-/// Ispired by [this comment](https://www.reddit.com/r/adventofcode/comments/18nol3m/comment/ked9am2),
-/// it calculates the even parity corners differently than the original explanation's
-/// simplified (and incorrect, on my version of the full input) calculation. This calculates
-/// the even corners directly, by finding the shortest path to each point in
-/// the input from each of the corners.
+/// The garden repeats infinitely in every direction. Rather than relying on
+/// the special shape of the full input (an empty row and column through the
+/// start, which is in the middle of a square tile), this walks the tiled
+/// garden until the number of reachable plots grows quadratically from one
+/// copy of the tile to the next and extrapolates from there, so it works for
+/// the example too.
 pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
 
-    use std::collections::{HashMap, HashSet, VecDeque};
-
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use super::utils::Garden;
 
-    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
-    struct Point {
-        row: usize,
-        col: usize,
-    }
-
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    struct Path {
-        point: Point,
+    #[derive(Debug)]
+    pub struct Soln {
         steps: usize,
     }
 
-    #[derive(Debug, Default)]
-    pub struct Soln {
-        rows: usize,
-        cols: usize,
-        start: Point,
-        rocks: HashSet<Point>,
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { steps: 26_501_365 }
+        }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.parse_input_file(filename);
-            let shortest_paths_start = self.shortest_paths(self.start);
-            let shortest_paths_tl = self.shortest_paths(Point { row: 0, col: 0 });
-            let shortest_paths_tr = self.shortest_paths(Point { row: 0, col: self.cols - 1 });
-            let shortest_paths_bl = self.shortest_paths(Point { row: self.rows - 1, col: 0 });
-            let shortest_paths_br = self.shortest_paths(Point { row: self.rows - 1, col: self.cols - 1 });
-            /*  
-            This solution relies on the input having these special case
-            properties as outlined in the explanation linked at the
-            beginning of this mod.
-            */
-            // Input is a square
-            assert_eq!(self.rows, self.cols);
-            // Input dimensions are odd
-            assert_eq!(self.rows % 2, 1);
-            let steps_to_edge = (self.rows - 1) / 2;
-            // Start point is in the middle of the input
-            assert_eq!(self.start, Point { row: steps_to_edge, col: steps_to_edge });
-            // Start row and col are unobstructed
-            assert_eq!(0, self.rocks.iter().filter(|pt| pt.row == self.start.row).count());
-            assert_eq!(0, self.rocks.iter().filter(|pt| pt.row == self.start.row).count());
-            // With the required number of steps, we can reach the very edge of an input tile
-            // at the furthest point.
-            let steps_required: usize = 26_501_365;
-            assert_eq!(0, (steps_required - steps_to_edge) % self.rows);
-            let diamond_radius = (steps_required - steps_to_edge) / self.rows;
-            // Diamond radius is even
-            assert_eq!(0, diamond_radius % 2);
-
-            let even_parity_all_spaces = shortest_paths_start.values()
-                .filter(|steps| **steps % 2 == 0)
-                .count();
-            let odd_parity_all_spaces = shortest_paths_start.values()
-                .filter(|steps| **steps % 2 == 1)
-                .count();
-            let odd_parity_corner_spaces = shortest_paths_start.values()
-                .filter(|steps| {
-                    **steps % 2 == 1
-                    && **steps > steps_to_edge
-                })
-                .count();
-            let even_parity_tl_corner_spaces = shortest_paths_tl.values()
-                .filter(|steps| {
-                    **steps % 2 == 0 
-                    && **steps <= steps_to_edge
-                })
-                .count();
-            let even_parity_tr_corner_spaces = shortest_paths_tr.values()
-                .filter(|steps| {
-                    **steps % 2 == 0 
-                    && **steps <= steps_to_edge
-                })
-                .count();
-            let even_parity_bl_corner_spaces = shortest_paths_bl.values()
-                .filter(|steps| {
-                    **steps % 2 == 0 
-                    && **steps <= steps_to_edge
-                })
-                .count();
-            let even_parity_br_corner_spaces = shortest_paths_br.values()
-                .filter(|steps| {
-                    **steps % 2 == 0 
-                    && **steps <= steps_to_edge
-                })
-                .count();
-            let even_parity_corner_spaces = even_parity_tl_corner_spaces
-                + even_parity_tr_corner_spaces
-                + even_parity_bl_corner_spaces
-                + even_parity_br_corner_spaces;
-
-            Answer::Usize(
-                diamond_radius.pow(2) * even_parity_all_spaces
-                + (diamond_radius + 1).pow(2) * odd_parity_all_spaces
-                + diamond_radius * even_parity_corner_spaces
-                - (diamond_radius + 1) * odd_parity_corner_spaces
-            )
-        }
-    }
-
-    impl Soln {
-        fn shortest_paths(&self, start: Point) -> HashMap<Point, usize> {
-            let mut shortest_paths: HashMap<Point, usize> = HashMap::new();
-            let mut queue = VecDeque::from([
-                Path {
-                    point: start,
-                    steps: 0,
-                },
-            ]);
-            while !queue.is_empty() {
-                let path = queue.pop_front().unwrap();
-                if shortest_paths.contains_key(&path.point) { continue; }
-                shortest_paths.insert(path.point, path.steps);
-                queue.append(&mut self.next_paths(&path));
-            }
-            shortest_paths     
-        }
-
-        fn next_paths(&self, path: &Path) -> VecDeque<Path> {
-            let mut result = VecDeque::new();
-            if path.point.row != 0 {
-                let point = Point { 
-                    row: path.point.row - 1,
-                    col: path.point.col,
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
-                }
-            }
-            if path.point.col != 0 {
-                let point = Point { 
-                    row: path.point.row,
-                    col: path.point.col - 1, 
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
-                }
-            }
-            if path.point.row != self.rows - 1 {
-                let point = Point { 
-                    row: path.point.row + 1,
-                    col: path.point.col, 
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
-                }
-            }
-            if path.point.col != self.cols - 1 {
-                let point = Point { 
-                    row: path.point.row,
-                    col: path.point.col + 1, 
-                };
-                if !self.rocks.contains(&point) {
-                    result.push_back(Path { point, steps: path.steps + 1 });
-                }
-            }
-            result
+            Answer::Usize(Garden::from_file(filename).reachable_plots(self.steps))
         }
 
-        fn parse_input_file(&mut self, filename: &str) {
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    self.cols = line.len();
-                    self.rocks.extend(
-                        line.char_indices()
-                            .filter(|(_col, ch)| *ch == '#')
-                            .map(|(col, _ch)| Point { row: self.rows, col })
-                    );
-                    if let Some(col) = line.find('S') {
-                        self.start = Point { row: self.rows, col };
-                    }
-                    self.rows += 1;
-                });
+        solution_params! {
+            steps: "Number of steps the elf takes",
         }
-
     }
 
     #[cfg(test)]
     mod tests {
         use test_case::test_case;
-        use crate::utils::{test_utils, solution::Answer};
+        use crate::utils::{io_utils::{self, InputFileType}, test_utils, solution::Answer};
         use super::*;
         use super::super::DAY;
 
@@ -338,5 +281,35 @@ pub mod part_two {
                 &DAY,
             );
         }
-    }    
-}
\ No newline at end of file
+
+        #[test_case(1, 6, Answer::Usize(16); "example_1_6")]
+        #[test_case(1, 10, Answer::Usize(50); "example_1_10")]
+        #[test_case(1, 50, Answer::Usize(1_594); "example_1_50")]
+        #[test_case(1, 100, Answer::Usize(6_536); "example_1_100")]
+        #[test_case(1, 500, Answer::Usize(167_004); "example_1_500")]
+        #[test_case(1, 1000, Answer::Usize(668_697); "example_1_1000")]
+        #[test_case(1, 5000, Answer::Usize(16_733_044); "example_1_5000")]
+        fn examples_with_steps_are_correct(example_key: u8, steps: usize, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("steps", steps.to_string())],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+
+        #[test_case(1, 6, 16; "example_1_6")]
+        #[test_case(1, 10, 50; "example_1_10")]
+        #[test_case(1, 50, 1_594; "example_1_50")]
+        #[test_case(1, 100, 6_536; "example_1_100")]
+        #[test_case(1, 500, 167_004; "example_1_500")]
+        #[test_case(1, 1000, 668_697; "example_1_1000")]
+        fn brute_force_agrees(example_key: u8, steps: usize, plots: usize) {
+            let filename = io_utils::input_filename(&DAY, InputFileType::Example(example_key));
+            let garden = Garden::from_file(&filename);
+            assert_eq!(garden.reachable_plots_brute_force(steps, true), plots);
+            assert_eq!(garden.reachable_plots(steps), plots);
+        }
+    }
+}
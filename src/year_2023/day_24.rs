@@ -6,7 +6,7 @@ const DAY: Day = crate::utils::Day { year: 2023, day: 24 };
 pub mod part_one {
     use regex::Regex;
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::{io_utils, rational::{solve_linear_system, Rational}, solution::{Answer, Solution}};
 
     #[derive(Debug, PartialEq)]
     struct Position {
@@ -26,11 +26,7 @@ pub mod part_one {
     enum CrossStatus {
         Past,
         Parallel,
-        At(f64, f64),
-    }
-
-    fn approx_eq(l: f64, r: f64) -> bool {
-        (l - r).abs() < f64::EPSILON
+        At(Rational, Rational),
     }
 
     #[derive(Debug, PartialEq)]
@@ -40,52 +36,43 @@ pub mod part_one {
     }
 
     impl Hailstone {
-        fn slope(&self) -> f64 {
-            self.vel.y as f64 / self.vel.x as f64
-        }
-
-        /* we need to find self.pos.x + self.vel.x * time_self = other.pos.x + other.vel.x * time_other
-                    and    self.pos.y + self.vel.y * time_self = other.pos.y + other.vel.y * time_other
-
-                    time_self = (other.pos.y + other.vel.y * time_other - self.pos.y) / self.vel.y
-
-            substituting in yields
-
-                    self.pos.x + self.vel.x * ((other.pos.y + other.vel.y * time_other - self.pos.y) / self.vel.y) = other.pos.x + other.vel.x * time_other
-
-                    time_other = self.pos.x - other.pos.x + (self.vel.x / self.vel.y) * other.vel.y
-
-            Need to account for velocity of 0. And need to figure out the parallel situation.
-         */
+        /// Where the paths of the two hailstones cross, ignoring the z axis.
+        /// Solves `self.pos + self.vel * time_self = other.pos + other.vel * time_other`
+        /// exactly, since the positions are too large for floating point.
         fn crosses(&self, other: &Hailstone) -> CrossStatus {
-            // TODO: correctly deal with 0
-            if self.vel.x == 0 || self.vel.y == 0 || other.vel.x == 0 || other.vel.y == 0 { panic!("Zero velocity along one axis"); }
-            // TODO: if the ratio of vel.x / vel.y is the same for other and self, this calculation fails (parallel)
-            if approx_eq(self.slope(), other.slope()) {
+            let system = vec![
+                vec![self.vel.x, -other.vel.x, other.pos.x - self.pos.x],
+                vec![self.vel.y, -other.vel.y, other.pos.y - self.pos.y],
+            ];
+            let system = system.into_iter()
+                .map(|row| row.into_iter().map(Rational::from).collect())
+                .collect();
+            let Some(times) = solve_linear_system(system) else {
                 return CrossStatus::Parallel;
-            }
-            // TODO: make it a function
-            let time_other = (self.pos.x as f64 - other.pos.x as f64 + (other.pos.y as f64 - self.pos.y as f64) * (self.vel.x as f64 / self.vel.y as f64)) / (other.vel.x as f64 - other.vel.y as f64 * self.vel.x as f64 / self.vel.y as f64);
-            let time_self = (other.pos.y as f64 - self.pos.y as f64 + other.vel.y as f64 * time_other) / self.vel.y as f64;
-            if time_other < 0.0 || time_self < 0.0 {
+            };
+            let (time_self, time_other) = (times[0], times[1]);
+            if time_self < Rational::ZERO || time_other < Rational::ZERO {
                 return CrossStatus::Past;
             }
-            CrossStatus::At(self.pos.x as f64 + self.vel.x as f64 * time_self, self.pos.y as f64 + self.vel.y as f64 * time_self)
+            CrossStatus::At(
+                Rational::from(self.pos.x) + Rational::from(self.vel.x) * time_self,
+                Rational::from(self.pos.y) + Rational::from(self.vel.y) * time_self,
+            )
         }
     }
 
     #[derive(Debug)]
     pub struct Soln {
-        test_area_min: f64,
-        test_area_max: f64,
+        test_area_min: i64,
+        test_area_max: i64,
         hailstones: Vec<Hailstone>,
     }
 
     impl Default for Soln {
         fn default() -> Self {
             Self::with_test_area(
-                200000000000000.0, 
-                400000000000000.0,
+                200_000_000_000_000,
+                400_000_000_000_000,
             )
         }
     }
@@ -94,15 +81,13 @@ pub mod part_one {
         fn solve(&mut self, filename: &str) -> Answer {
             let mut future_crosses = 0;
             self.parse_input_file(filename);
+            let test_area = Rational::from(self.test_area_min)..=Rational::from(self.test_area_max);
             for i in 0..self.hailstones.len() {
                 for j in i + 1..self.hailstones.len() {
                     if let CrossStatus::At(x, y) = self.hailstones[i].crosses(&self.hailstones[j]) {
-                        if x >= self.test_area_min
-                            && x <= self.test_area_max
-                            && y >= self.test_area_min
-                            && y <= self.test_area_max {
-                                future_crosses += 1;
-                            }
+                        if test_area.contains(&x) && test_area.contains(&y) {
+                            future_crosses += 1;
+                        }
                     }
                 }
             }
@@ -111,7 +96,7 @@ pub mod part_one {
     }
 
     impl Soln {
-        fn with_test_area(test_area_min: f64, test_area_max: f64) -> Self {
+        fn with_test_area(test_area_min: i64, test_area_max: i64) -> Self {
             Self {
                 test_area_min,
                 test_area_max,
@@ -151,7 +136,7 @@ pub mod part_one {
         #[test_case(1, Answer::U32(2); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
             test_utils::check_example_case(
-                &mut Soln::with_test_area(7.0, 27.0),
+                &mut Soln::with_test_area(7, 27),
                 example_key,
                 answer,
                 &DAY,
@@ -160,195 +145,99 @@ pub mod part_one {
     }    
 }
 
-/// Assumes the intersections occur at integer time intervals.
-pub mod part_two {
-    use std::collections::{HashMap, HashSet};
 
+/// In the frame of reference of the first hailstone, that hailstone sits
+/// still at the origin, so the rock's path has to pass through the origin.
+/// The rock's path then lies in the plane through the origin containing any
+/// other hailstone's path, so two such planes meet along it. Everything is
+/// solved exactly, in integers and rationals, since the positions are too
+/// large for floating point.
+pub mod part_two {
     use itertools::Itertools;
-    use prime_factorization::Factorization;
     use regex::Regex;
-    use ndarray::prelude::*;
-    use ndarray_linalg::Solve;
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::{io_utils, rational::{solve_linear_system, Rational}, solution::{Answer, Solution}};
 
     const NUM_AXES: usize = 3;
 
-    // Treating as an enum rather than named fields so we can use to index into arrays
-    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-    #[repr(usize)]
-    enum Axis {
-        X = 0,
-        Y,
-        Z,
+    type Vector = [i128; NUM_AXES];
+
+    fn checked(value: Option<i128>) -> i128 {
+        value.expect("Vector arithmetic should fit into `i128` datatype.")
     }
 
-    #[derive(Debug, PartialEq, Eq)]
-    struct Projectile {
-        pos: [i64; NUM_AXES],
-        vel: [i64; NUM_AXES],
+    fn sub(l: Vector, r: Vector) -> Vector {
+        [0, 1, 2].map(|axis| checked(l[axis].checked_sub(r[axis])))
     }
 
-    #[derive(Debug, PartialEq, Eq)]
-    struct TimeDelta {
-        i: usize,
-        j: usize,
-        delta: i64,
+    fn cross(l: Vector, r: Vector) -> Vector {
+        [(1, 2), (2, 0), (0, 1)]
+            .map(|(a, b)| checked(checked(l[a].checked_mul(r[b])).checked_sub(checked(l[b].checked_mul(r[a])))))
     }
 
-    #[derive(Debug, Default, PartialEq, Eq)]
-    struct Possibilities {
-        possible: HashMap<i64, Vec<TimeDelta>>,
-        impossible: HashSet<i64>,
+    /// The shortest integer vector in the same direction.
+    fn primitive(vector: Vector) -> Vector {
+        let divisor = vector.iter()
+            .fold(0, |divisor, component| gcd::Gcd::gcd(divisor, component.unsigned_abs())) as i128;
+        vector.map(|component| component / divisor)
     }
 
-    impl Possibilities {
-        fn determined(&self) -> bool {
-            self.possible.len() == 1
-        }
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct Projectile {
+        pos: Vector,
+        vel: Vector,
+    }
 
-        fn determined_value(&self) -> Option<i64> {
-            if !self.determined() {
-                None
-            } else {
-                Some(*self.possible.iter().next().unwrap().0)
+    impl Projectile {
+        fn relative_to(&self, other: &Projectile) -> Projectile {
+            Projectile {
+                pos: sub(self.pos, other.pos),
+                vel: sub(self.vel, other.vel),
             }
         }
 
-        fn determined_time_deltas(&self) -> impl Iterator<Item = &TimeDelta> {
-            self.possible.get(&self.determined_value().unwrap()).unwrap().iter()
+        fn position_at(&self, time: Rational) -> [Rational; NUM_AXES] {
+            [0, 1, 2].map(|axis| Rational::from(self.pos[axis]) + Rational::from(self.vel[axis]) * time)
         }
-    }
 
-    #[derive(Debug, Default, PartialEq, Eq)]
-    struct PossibleProjectile {
-        pos: [Possibilities; NUM_AXES],
-        vel: [Possibilities; NUM_AXES],
-    }
+        /// The time when the projectile reaches the line through the origin
+        /// in `direction`, if it does so exactly once.
+        fn time_to_line(&self, direction: Vector) -> Option<Rational> {
+            // pos + vel * time = direction * distance
+            let system = (0..NUM_AXES)
+                .map(|axis| vec![self.vel[axis], -direction[axis], -self.pos[axis]])
+                .map(|row| row.into_iter().map(Rational::from).collect())
+                .collect();
+            solve_linear_system(system).map(|solution| solution[0])
+        }
 
-    impl PossibleProjectile {
-        fn sum_of_pos_coords(&self) -> Option<i64> {
-            if let (Some(x), Some(y), Some(z)) = (
-                self.pos[Axis::X as usize].determined_value(), 
-                self.pos[Axis::Y as usize].determined_value(), 
-                self.pos[Axis::Z as usize].determined_value()
-            ) {
-                Some(x + y + z)
-            } else {
-                None
-            }
+        /// The time when the two projectiles collide, if they do so exactly
+        /// once.
+        fn time_to_collision(&self, other: &Projectile) -> Option<Rational> {
+            let relative = other.relative_to(self);
+            // relative.pos + relative.vel * time = 0
+            let system = (0..NUM_AXES)
+                .map(|axis| vec![Rational::from(relative.vel[axis]), Rational::from(-relative.pos[axis])])
+                .collect();
+            solve_linear_system(system).map(|solution| solution[0])
         }
     }
 
     #[derive(Debug, Default)]
     pub struct Soln {
         hailstones: Vec<Projectile>,
-        rock: PossibleProjectile,
     }
 
-    // Todo: The smaller example in the problem is actually less constrained than the full input.
-    /// This solution tracks and then uses the known time differences 
-    /// corresponding to the same-velocity pairs. can learn the y and z position 
-    /// for any pair where the x velocity is the same, etc.
-    /// 
-    /// Ideal is if we track for each axis: velocity: i, j, delta triple. where delta is such that
-    /// t_i - t_j = delta and i < j.
-    /// 
-    /// Then once we know the velocity for the other axes, the matrix A is:
-    /// 
-    /// 1       rock_vel - hailstone_i_vel      0                                               hailstone_i_pos
-    /// 1       0                               rock_vel - hailstone_j_vel                      hailstone_j_pos
-    /// 0       1                               -1                                              delta
-    /// 
-    /// And the vector b is:
-    /// 
-    /// hailstone_i_pos
-    /// hailstone_j_pos
-    /// delta
-    /// 
-    /// Solving yields:
-    /// 
-    /// rock_pos
-    /// t_i
-    /// t_j
-    /// 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
             self.parse_input_file(filename);
-            // Find the possible velocities
-            for i in 0..self.hailstones.len() {
-                for j in i + 1..self.hailstones.len() {
-                    for axis_idx in [Axis::X, Axis::Y, Axis::Z].map(|a| a as usize) {
-                        if self.hailstones[i].vel[axis_idx] == self.hailstones[j].vel[axis_idx] {
-                            if self.hailstones[i].pos[axis_idx] != self.hailstones[j].pos[axis_idx] {
-                                self.mark_vel_impossible(axis_idx, self.hailstones[i].vel[axis_idx]);
-                                let max_offset = (self.hailstones[i].pos[axis_idx] - self.hailstones[j].pos[axis_idx]).abs() as u64;
-                                let prime_factors = Factorization::run(max_offset);
-                                let mut possible_offsets: HashSet<u64> = HashSet::new();
-                                for len in 0..=prime_factors.factors.len() {
-                                    for combo in prime_factors.factors.iter().combinations(len) {
-                                        let prod = combo.into_iter().fold(1u64, |prod, factor| prod * *factor);
-                                        possible_offsets.insert(prod);
-                                    }
-                                }
-                                let mut possible_vels: HashMap<i64, Vec<TimeDelta>> = HashMap::new();
-                                possible_offsets.into_iter()
-                                    .for_each(|offset| {
-                                        let offset: i64 = offset.try_into().unwrap();
-                                        possible_vels.insert(
-                                            self.hailstones[i].vel[axis_idx] - offset,
-                                            vec![TimeDelta {
-                                                i,
-                                                j,
-                                                delta: (self.hailstones[i].pos[axis_idx] - self.hailstones[j].pos[axis_idx]) / - offset,
-                                            }],
-                                        );
-                                        possible_vels.insert(
-                                            self.hailstones[i].vel[axis_idx] + offset,
-                                            vec![TimeDelta {
-                                                i,
-                                                j,
-                                                delta: (self.hailstones[i].pos[axis_idx] - self.hailstones[j].pos[axis_idx]) / offset,
-                                            }],
-                                        );
-                                    }); 
-                                self.mark_vels_possible(axis_idx, possible_vels);
-                            }
-                        }    
-                    }
-                }
-            }
-            // This solution assumes we have fully determined the velocities at this point.
-            // It works on the full input but will fail on the test case.
-            // There is a way to determine the velocities in the test case as well:
-            // Basically, we know the x-axis velocity in that case. For each known time_delta
-            // that has to be true for that x-axis velocity, we can find the set of all times
-            // involved in a known time_delta for each possible y-axis velocity and z-axis velocity.
-            // Then the unknown velocity for each axis is the one that yields the same
-            // exact times for each time delta.
-            // 
-            // TODO: implement determining velocities for the test case.
-            for axis_idx in [Axis::X as usize, Axis::Y as usize, Axis::Z as usize] {
-                assert!(self.rock.vel[axis_idx].determined())
-            }
-            // Determine the positions
-            for time_delta in self.rock.vel[Axis::X as usize].determined_time_deltas() {
-                if self.rock.pos[Axis::Y as usize].determined() && self.rock.pos[Axis::Z as usize].determined() {
-                    break;
-                }
-                for axis_idx in [Axis::Y as usize, Axis::Z as usize] {
-                    if let Some(pos) = self.determine_position(time_delta, axis_idx) {
-                        self.rock.pos[axis_idx].possible.insert(pos, vec![]);
-                    }
-                }
-            }
-            for time_delta in self.rock.vel[Axis::Y as usize].determined_time_deltas() {
-                if let Some(pos) = self.determine_position(time_delta, Axis::X as usize) {
-                    self.rock.pos[Axis::X as usize].possible.insert(pos, vec![]);
-                    break;
-                }
-            }
-            Answer::I64(self.rock.sum_of_pos_coords().expect("Should know the answer by now"))
+            let rock = self.rock();
+            assert!(
+                self.hailstones.iter()
+                    .all(|hailstone| rock.time_to_collision(hailstone).is_some_and(|time| time >= Rational::ZERO)),
+                "Rock should collide with every hailstone.",
+            );
+            Answer::I64(rock.pos.iter().sum::<i128>().try_into().unwrap())
         }
     }
 
@@ -374,48 +263,35 @@ pub mod part_two {
                 .collect();
         }
 
-        fn mark_vel_impossible(&mut self, axis_idx: usize, vel: i64) {
-            self.rock.vel[axis_idx].possible.remove(&vel);
-            self.rock.vel[axis_idx].impossible.insert(vel);
-        }
-
-        fn mark_vels_possible(&mut self, axis_idx: usize, vels: HashMap<i64, Vec<TimeDelta>>) {
-            let vels: HashMap<i64, Vec<TimeDelta>> = vels.into_iter().filter(|(vel, _tds)| !self.rock.vel[axis_idx].impossible.contains(&vel)).collect();
-            if self.rock.vel[axis_idx].possible.is_empty() {
-                self.rock.vel[axis_idx].possible = vels;
-            } else {
-                self.rock.vel[axis_idx].possible.retain(|vel, _tds| vels.contains_key(&vel));
-                if self.rock.vel[axis_idx].possible.is_empty() { panic!("Emptied possible set"); }
-                for (vel, mut new_tds) in vels.into_iter() {
-                    self.rock.vel[axis_idx].possible.entry(vel)
-                        .and_modify(|tds| tds.append(&mut new_tds));
-                }
-            }
-        }
-
-        fn determine_position(&self, time_delta: &TimeDelta, axis_idx: usize) -> Option<i64> {
-            if !self.rock.pos[axis_idx].determined() 
-                && self.hailstones[time_delta.i].vel[axis_idx] != self.hailstones[time_delta.j].vel[axis_idx] {
-                    let rock_vel = self.rock.vel[axis_idx].determined_value().unwrap() as f64;
-                    let a: Array2<f64> = array![
-                        [1.0, rock_vel - self.hailstones[time_delta.i].vel[axis_idx] as f64, 0.0], 
-                        [1.0, 0.0, rock_vel - self.hailstones[time_delta.j].vel[axis_idx] as f64],
-                        [0.0, 1.0, -1.0], // t_i - t_j = delta
-                    ];            
-                    let b: Array1<f64> = array![
-                        self.hailstones[time_delta.i].pos[axis_idx] as f64, 
-                        self.hailstones[time_delta.j].pos[axis_idx] as f64, 
-                        time_delta.delta as f64,
-                    ];
-                    let x = a.solve_into(b).unwrap();
-                    if x[0] == (x[0] as i64) as f64 {
-                        Some(x[0].round() as i64)
-                    } else {
-                        None
-                    }
-            } else {
-                None
+        /// Finds the rock's path from the first hailstone and any two others
+        /// whose paths aren't coplanar with it.
+        fn rock(&self) -> Projectile {
+            let origin = &self.hailstones[0];
+            let relative: Vec<Projectile> = self.hailstones.iter()
+                .map(|hailstone| hailstone.relative_to(origin))
+                .collect();
+            for (i, j) in (1..relative.len()).tuple_combinations() {
+                let planes = [i, j].map(|idx| cross(relative[idx].pos, relative[idx].vel));
+                let direction = cross(planes[0], planes[1]);
+                if direction == [0; NUM_AXES] { continue; }
+                let direction = primitive(direction);
+                let (Some(time_i), Some(time_j)) = (
+                    relative[i].time_to_line(direction),
+                    relative[j].time_to_line(direction),
+                ) else {
+                    continue;
+                };
+                if time_i == time_j { continue; }
+                let (pos_i, pos_j) = (self.hailstones[i].position_at(time_i), self.hailstones[j].position_at(time_j));
+                let vel = [0, 1, 2].map(|axis| (pos_j[axis] - pos_i[axis]) / (time_j - time_i));
+                let pos = [0, 1, 2].map(|axis| pos_i[axis] - vel[axis] * time_i);
+                let to_integer = |value: Rational| value.to_integer().expect("Rock should have integer coordinates.");
+                return Projectile {
+                    pos: pos.map(to_integer),
+                    vel: vel.map(to_integer),
+                };
             }
+            panic!("Should be able to find the rock's path.");
         }
     }
 
@@ -426,6 +302,7 @@ pub mod part_two {
         use super::*;
         use super::super::DAY;
 
+        #[test_case(1, Answer::I64(47); "example_1")]
         #[test_case(2, Answer::I64(646_810_057_104_753); "full_input")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
             test_utils::check_example_case(
@@ -436,4 +313,4 @@ pub mod part_two {
             );
         }
     }    
-}
\ No newline at end of file
+}
@@ -3,6 +3,28 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2023, day: 25 };
 
+use regex::Regex;
+
+use crate::utils::{dot::Graph, io_utils};
+
+/// Returns the wiring diagram as a graph, with each component's degree and
+/// connected component as node attributes. Rendered with `neato` or `sfdp`,
+/// the three wires to cut are the ones bridging the two clusters.
+pub fn graph(filename: &str) -> Graph {
+    let re = Regex::new(r"(?<component>[a-z]+): (?<connected>[ a-z]+)").unwrap();
+    let mut graph = Graph::undirected("wiring");
+    io_utils::file_to_lines(filename).for_each(|line| {
+        let captures = re.captures(&line).unwrap();
+        let component = captures.name("component").unwrap().as_str();
+        captures.name("connected").unwrap().as_str()
+            .split(' ')
+            .for_each(|cc| { graph.edge(component, cc); });
+    });
+    graph.annotate_degrees();
+    graph.annotate_components();
+    graph
+}
+
 /// Each vertex must have at least 4 edges coming out of it (otherwise the
 /// answer would be a trivial (this assumption can also be confirmed).
 /// At least in the example input, the components that will remain connected
@@ -6,7 +6,7 @@ const DAY: Day = crate::utils::Day { year: 2024, day: 5 };
 mod utils {
     use std::{cmp::Ordering, collections::{HashMap, HashSet}};
 
-    use crate::utils::io_utils;
+    use crate::utils::io_utils::Sections;
 
     #[derive(Debug, Default)]
     pub struct PrintingDepartment {
@@ -16,10 +16,9 @@ mod utils {
 
     impl PrintingDepartment {
         pub fn parse_input_file(&mut self, filename: &str) {
-            let mut lines = io_utils::file_to_lines(filename);
+            let sections = Sections::from_file(filename, 2);
             // parse page ordering rules
-            while let Some(line) = lines.next() {
-                if line.is_empty() { break; }
+            for line in sections.nth(0).lines() {
                 let l: Vec<usize> = line.split('|')
                     .map(|num| num.parse::<usize>().unwrap())
                     .collect();
@@ -28,7 +27,7 @@ mod utils {
                     .or_insert(HashSet::from([l[1]]));
             }
             // parse updates
-            self.updates = lines.map(|line| {
+            self.updates = sections.nth(1).lines().map(|line| {
                 line.split(',')
                     .map(|num| num.parse::<usize>().unwrap())
                     .collect()
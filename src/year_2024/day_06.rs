@@ -3,10 +3,12 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2024, day: 6 };
 
+use crate::utils::visualize::Visualize;
+
 mod utils {
     use std::{cmp::max, collections::HashSet};
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, visualize::{default_colour, Colour, Frame, Visualize}};
 
     #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
     pub struct Point {
@@ -33,6 +35,15 @@ mod utils {
             }
         }
 
+        fn to_char(self) -> char {
+            match self {
+                Self::Up => '^',
+                Self::Right => '>',
+                Self::Down => 'v',
+                Self::Left => '<',
+            }
+        }
+
         fn turn(&mut self) {
             *self = match self {
                 Self::Up => Self::Right,
@@ -67,6 +78,9 @@ mod utils {
         guard_start: GuardStatus,
         guard_status: GuardStatus,
         obstacles: HashSet<Point>,
+        // The guard's statuses so far when walking one step at a time
+        walk: HashSet<GuardStatus>,
+        left_map: bool,
     }
 
     impl LabMap {
@@ -192,8 +206,85 @@ mod utils {
         
         pub fn reset_guard_status(&mut self) {
             self.guard_status = self.guard_start.clone();
+            self.walk.clear();
+            self.left_map = false;
         }
     }
+
+    impl Visualize for LabMap {
+        /// Draws the map as in the puzzle's examples, marking the positions
+        /// visited so far with `X`.
+        fn frame(&self) -> Frame {
+            let visited: HashSet<Point> = self.walk.iter().map(|status| status.pos).collect();
+            let mut frame = Frame::new(&format!("{} distinct positions visited:", visited.len()));
+            for row in 0..self.rows {
+                frame.push_row((0..self.cols).map(|col| {
+                    let pt = Point { row, col };
+                    if !self.left_map && self.guard_status.pos == pt {
+                        self.guard_status.dir.to_char()
+                    } else if self.obstacles.contains(&pt) {
+                        '#'
+                    } else if visited.contains(&pt) {
+                        'X'
+                    } else {
+                        '.'
+                    }
+                }));
+            }
+            frame
+        }
+
+        /// Moves the guard one position (or turns it), until it leaves the
+        /// map or starts repeating itself.
+        fn step(&mut self) -> bool {
+            if self.left_map || !self.walk.insert(self.guard_status) { return false; }
+            let GuardStatus { pos, dir } = self.guard_status;
+            let next = match dir {
+                Direction::Up => pos.row.checked_sub(1).map(|row| Point { row, col: pos.col }),
+                Direction::Right => (pos.col + 1 < self.cols).then_some(Point { row: pos.row, col: pos.col + 1 }),
+                Direction::Down => (pos.row + 1 < self.rows).then_some(Point { row: pos.row + 1, col: pos.col }),
+                Direction::Left => pos.col.checked_sub(1).map(|col| Point { row: pos.row, col }),
+            };
+            match next {
+                None => self.left_map = true,
+                Some(next) if self.obstacles.contains(&next) => self.guard_status.dir.turn(),
+                Some(next) => self.guard_status.pos = next,
+            }
+            true
+        }
+
+        fn colour(&self, ch: char) -> Colour {
+            match ch {
+                '^' | '>' | 'v' | '<' => Colour::Green,
+                'X' => Colour::Cyan,
+                _ => default_colour(ch),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{self, InputFileType};
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn walk_visits_same_positions() {
+            let mut lab_map = LabMap::default();
+            lab_map.parse_input_file(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            while lab_map.step() {}
+            let visited = lab_map.frame().rows().iter().flatten().filter(|ch| **ch == 'X').count();
+            lab_map.reset_guard_status();
+            assert_eq!(visited, lab_map.num_positions_visited());
+        }
+    }
+}
+
+/// Returns the guard's walk, for animating.
+pub fn simulation(filename: &str) -> Box<dyn Visualize> {
+    let mut lab_map = utils::LabMap::default();
+    lab_map.parse_input_file(filename);
+    Box::new(lab_map)
 }
 
 pub mod part_one {
@@ -4,9 +4,9 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2024, day: 10 };
 
 mod utils {
-    use std::collections::{HashMap, HashSet};
+    use std::collections::HashMap;
 
-    use crate::utils::io_utils;
+    use crate::utils::{dag::Dag, io_utils};
 
     #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
     struct Point {
@@ -60,46 +60,46 @@ mod utils {
         }
 
         pub fn sum_of_trailhead_scores(&self) -> usize {
-            let mut cache: HashMap<Point, (HashSet<Point>, usize)> = HashMap::new();
+            let trails = self.trails();
             self.trailheads()
                 .map(|trailhead| {
-                    self.ends_reachable(&trailhead, &mut cache).0.len()
+                    self.ends_reachable(&trails, &trailhead)
+                        .len()
                 })
                 .sum()
         }
 
         pub fn sum_of_trailhead_ratings(&self) -> usize {
-            let mut cache: HashMap<Point, (HashSet<Point>, usize)> = HashMap::new();
-            self.trailheads()
+            let trails = self.trails();
+            let rating: u128 = self.trailheads()
                 .map(|trailhead| {
-                    self.ends_reachable(&trailhead, &mut cache).1
+                    self.ends_reachable(&trails, &trailhead)
+                        .values()
+                        .sum::<u128>()
                 })
-                .sum()
+                .sum();
+            rating.try_into().expect("Sum of ratings should fit in a usize.")
         }
 
-        fn ends_reachable(
-            &self, 
-            pt: &Point,
-            mut cache: &mut HashMap<Point, (HashSet<Point>, usize)>,
-        ) -> (HashSet<Point>, usize) {
-            if let Some(ends) = cache.get(pt) {
-                return ends.clone()
-            }
-            let ends = if self.height(pt) == 9 {
-                (HashSet::from([*pt]), 1)
-            } else {
-                if let Some(reachable_ends) = self.reachable_neighbors(pt)
-                    .into_iter()
-                    .map(|neighbor| self.ends_reachable(&neighbor, &mut cache))
-                    .reduce(|acc, e| {
-                        (acc.0.union(&e.0).cloned().collect(), acc.1 + e.1)
-                    }) {
-                        reachable_ends
-                    } else {
-                        (HashSet::new(), 0)
-                    }
-            };
-            cache.insert(*pt, ends.clone());
+        /// Every step a trail can take, from each point to its reachable
+        /// neighbors. Heights only go up along a trail, so this is acyclic.
+        fn trails(&self) -> Dag<Point> {
+            (0..self.rows())
+                .flat_map(|row| (0..self.cols()).map(move |col| Point { row, col }))
+                .flat_map(|pt| {
+                    self.reachable_neighbors(&pt)
+                        .into_iter()
+                        .map(move |neighbor| (pt, neighbor))
+                })
+                .collect()
+        }
+
+        /// The height 9 points reachable from `pt`, with the number of
+        /// trails to each.
+        fn ends_reachable<'a>(&self, trails: &'a Dag<Point>, pt: &Point) -> HashMap<&'a Point, u128> {
+            let mut ends = trails.num_paths_from(pt)
+                .expect("Trails should only go up.");
+            ends.retain(|end, _num_trails| self.height(end) == 9);
             ends
         }
 
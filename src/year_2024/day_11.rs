@@ -4,9 +4,7 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2024, day: 11 };
 
 mod utils {
-    use std::collections::HashMap;
-
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, memo::Memo};
 
     #[derive(Debug)]
     pub struct Stones {
@@ -30,63 +28,60 @@ mod utils {
         }
 
         pub fn num_stones(&self) -> usize {
-            let mut cache: HashMap<(usize, usize), usize> = HashMap::new();
-            self.stones.iter()
-                .map(|value| num_stones(*value, self.blinks, &mut cache))
-                .sum()
+            let mut memo = Memo::default().named("stones");
+            let num_stones = self.stones.iter()
+                .map(|value| num_stones(*value, self.blinks, &mut memo))
+                .sum();
+            memo.report();
+            num_stones
         }
     }
 
-    pub fn num_stones(value: usize, blinks: usize, mut cache: &mut HashMap<(usize, usize), usize>) -> usize {
+    pub fn num_stones(value: usize, blinks: usize, memo: &mut Memo<(usize, usize), usize>) -> usize {
         if blinks == 0 {
             return 1;
         }
-        if let Some(cached) = cache.get(&(value, blinks)) {
-            return *cached;
-        }
-        let res = if value == 0 {
-            num_stones(1, blinks - 1, &mut cache)
-        } else if value.to_string().len() % 2 == 0 {
-            let val_string = value.to_string();
-            let left = val_string[..val_string.len() / 2].parse().unwrap();
-            let right = val_string[val_string.len() / 2..].parse().unwrap();
-            num_stones(left, blinks - 1, &mut cache) + num_stones(right, blinks - 1, &mut cache)
-        } else {
-            num_stones(value * 2024, blinks - 1, &mut cache)
-        };
-        cache.insert((value, blinks), res);
-        res
+        memo.get_or_compute((value, blinks), |memo| {
+            if value == 0 {
+                num_stones(1, blinks - 1, memo)
+            } else if value.to_string().len().is_multiple_of(2) {
+                let val_string = value.to_string();
+                let left = val_string[..val_string.len() / 2].parse().unwrap();
+                let right = val_string[val_string.len() / 2..].parse().unwrap();
+                num_stones(left, blinks - 1, memo) + num_stones(right, blinks - 1, memo)
+            } else {
+                num_stones(value * 2024, blinks - 1, memo)
+            }
+        })
     }
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Stones;
 
     #[derive(Debug)]
     pub struct Soln {
-        stones: Stones,
+        blinks: usize,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_blinks(25)
+            Self { blinks: 25 }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.stones.parse_input_file(filename);
-            Answer::Usize(self.stones.num_stones())
+            let mut stones = Stones::with_blinks(self.blinks);
+            stones.parse_input_file(filename);
+            Answer::Usize(stones.num_stones())
         }
-    }
 
-    impl Soln {
-        fn with_blinks(blinks: usize) -> Self {
-            Self {
-                stones: Stones::with_blinks(blinks),
-            }
+        solution_params! {
+            blinks: "Number of times to blink",
         }
     }
 
@@ -101,8 +96,9 @@ pub mod part_one {
         #[test_case(2, 6, Answer::Usize(22); "example_2_6_blinks")]
         #[test_case(2, 25, Answer::Usize(55_312); "example_2_25_blinks")]
         fn examples_are_correct(example_key: u8, blinks: usize, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_blinks(blinks),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("blinks", blinks.to_string())],
                 example_key,
                 answer,
                 &DAY,
@@ -112,27 +108,31 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Stones;
 
     #[derive(Debug)]
     pub struct Soln {
-        stones: Stones,
+        blinks: usize,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self {
-                stones: Stones::with_blinks(75),
-            }
+            Self { blinks: 75 }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.stones.parse_input_file(filename);
-            Answer::Usize(self.stones.num_stones())
+            let mut stones = Stones::with_blinks(self.blinks);
+            stones.parse_input_file(filename);
+            Answer::Usize(stones.num_stones())
+        }
+
+        solution_params! {
+            blinks: "Number of times to blink",
         }
     }
 }
\ No newline at end of file
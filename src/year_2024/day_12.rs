@@ -6,7 +6,7 @@ const DAY: Day = crate::utils::Day { year: 2024, day: 12 };
 mod utils {
     use std::collections::{HashMap, HashSet};
 
-    use crate::utils::io_utils;
+    use crate::utils::{disjoint_set::DisjointSet, io_utils};
 
     #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
     struct Point {
@@ -28,10 +28,6 @@ mod utils {
     }
 
     impl Region {
-        fn add_point(&mut self, point: Point) {
-            self.points.insert(point);
-        }
-
         fn area(&self) -> usize {
             self.points.len()
         }
@@ -47,70 +43,73 @@ mod utils {
             neighbor_counts.values().sum()
         }
 
+        /// Counts the straight sections of fence, which is the same as
+        /// counting the region's corners. Looking diagonally out from each
+        /// plot, there is an outside corner if neither adjacent plot is in
+        /// the region, and an inside corner if both are but the diagonal
+        /// plot isn't. This counts the fences around holes too.
+        fn sides(&self) -> usize {
+            self.points.iter()
+                .map(|pt| {
+                    [(1, 1), (1, -1), (-1, 1), (-1, -1)].iter()
+                        .filter(|(delta_row, delta_col)| {
+                            let vertical = self.points.contains(&Point { row: pt.row + delta_row, col: pt.col });
+                            let horizontal = self.points.contains(&Point { row: pt.row, col: pt.col + delta_col });
+                            let diagonal = self.points.contains(&Point { row: pt.row + delta_row, col: pt.col + delta_col });
+                            (!vertical && !horizontal) || (vertical && horizontal && !diagonal)
+                        })
+                        .count()
+                })
+                .sum()
+        }
+
         fn price(&self) -> usize {
             self.area() * self.perimeter()
         }
+
+        fn bulk_discount_price(&self) -> usize {
+            self.area() * self.sides()
+        }
     }
 
     #[derive(Debug, Default)]
     pub struct Garden {
-        regions: HashMap<char, HashMap<Point, usize>>,
+        plots: HashMap<Point, char>,
+        regions: DisjointSet<Point>,
     }
 
     impl Garden {
         pub fn parse_input_file(&mut self, filename: &str) {
-            let mut next_id = 0;
             io_utils::file_to_lines(filename).enumerate().for_each(|(row, line)| {
                 line.char_indices().for_each(|(col, ch)| {
-                    self.add_plot(ch, Point { row: row as isize, col: col as isize }, &mut next_id);
+                    self.add_plot(ch, Point { row: row as isize, col: col as isize });
                 })
             })
         }
 
-        fn add_plot(&mut self, ch: char, point: Point, next_id: &mut usize) {
-            let neighbors: HashSet<Point> = point.neighbors().collect();
-            let neighboring_regions: HashSet<usize> = if let Some(regions) = self.regions.get(&ch) {
-                regions.iter().filter_map(|(pt, region_id)| {
-                    if neighbors.contains(pt) {
-                        Some(*region_id)
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-            } else {
-                HashSet::new()
-            };
-            if neighboring_regions.is_empty() {
-                self.regions.entry(ch)
-                    .and_modify(|point_to_region_id| {
-                        point_to_region_id.insert(point, *next_id);
-                    })
-                    .or_insert(HashMap::from([(point, *next_id)]));
-                *next_id += 1;
-            } else {
-                // Merge regions
-                let merged_id = *neighboring_regions.iter().min().unwrap();
-                self.regions.get_mut(&ch).unwrap().iter_mut()
-                    .for_each(|(_pt, region_id)| {
-                        if neighboring_regions.contains(region_id) {
-                            *region_id = merged_id;
-                        }
-                    });
-                self.regions.get_mut(&ch).unwrap().insert(point, merged_id);
+        fn add_plot(&mut self, ch: char, point: Point) {
+            self.regions.make_set(point);
+            for neighbor in point.neighbors() {
+                if self.plots.get(&neighbor) == Some(&ch) {
+                    self.regions.union(point, neighbor);
+                }
             }
+            self.plots.insert(point, ch);
         }
 
         pub fn sum_of_prices(&self) -> usize {
-            let mut regions: HashMap<usize, Region> = HashMap::new();
-            self.regions.iter().for_each(|(_ch, point_to_region_id)| {
-                point_to_region_id.iter().for_each(|(point, region_id)| {
-                    regions.entry(*region_id)
-                        .or_default()
-                        .add_point(*point);
-                })
-            });
-            regions.values().map(|region| region.price()).sum()
+            self.sum_over_regions(Region::price)
+        }
+
+        pub fn sum_of_bulk_discount_prices(&self) -> usize {
+            self.sum_over_regions(Region::bulk_discount_price)
+        }
+
+        fn sum_over_regions(&self, price: impl Fn(&Region) -> usize) -> usize {
+            self.regions.components()
+                .into_iter()
+                .map(|points| price(&Region { points: points.into_iter().collect() }))
+                .sum()
         }
     }
 }
@@ -151,4 +150,44 @@ pub mod part_one {
             );
         }
     }    
-}
\ No newline at end of file
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Garden;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        garden: Garden,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.garden.parse_input_file(filename);
+            Answer::Usize(self.garden.sum_of_bulk_discount_prices())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(80); "example_1")]
+        #[test_case(2, Answer::Usize(436); "example_2")]
+        #[test_case(3, Answer::Usize(1_206); "example_3")]
+        #[test_case(4, Answer::Usize(236); "example_4")]
+        #[test_case(5, Answer::Usize(368); "example_5")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
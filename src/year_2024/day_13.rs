@@ -0,0 +1,189 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 13 };
+
+mod utils {
+    use crate::utils::{io_utils, parse};
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct ClawMachine {
+        a: (i64, i64),
+        b: (i64, i64),
+        prize: (i64, i64),
+    }
+
+    impl ClawMachine {
+        fn from_block(block: &[&str]) -> Self {
+            let numbers: Vec<i64> = block.iter().flat_map(|line| parse::ints(line)).collect();
+            let [a_x, a_y, b_x, b_y, prize_x, prize_y] = numbers[..] else {
+                panic!("Claw machine should have two buttons and a prize.");
+            };
+            Self { a: (a_x, a_y), b: (b_x, b_y), prize: (prize_x, prize_y) }
+        }
+
+        /// The fewest tokens (3 for A, 1 for B) to win the prize, or `None`
+        /// if it can't be won within `max_presses` presses of each button.
+        ///
+        /// The presses solve a pair of linear equations, which have exactly
+        /// one solution unless the buttons move the claw in the same
+        /// direction, so Cramer's rule finds it and all that's left is to
+        /// check it's a whole number of presses. If the buttons are collinear,
+        /// the prize is either off their line or the problem is one
+        /// dimensional.
+        fn tokens(&self, offset: i64, max_presses: Option<i64>) -> Option<i64> {
+            let (prize_x, prize_y) = (self.prize.0 + offset, self.prize.1 + offset);
+            let determinant = self.a.0 * self.b.1 - self.a.1 * self.b.0;
+            if determinant == 0 {
+                let on_line = |button: (i64, i64)| button.0 * prize_y == button.1 * prize_x;
+                if !on_line(self.a) || !on_line(self.b) {
+                    return None;
+                }
+                return if self.a.0 != 0 || self.b.0 != 0 {
+                    tokens_along_line(self.a.0, self.b.0, prize_x, max_presses)
+                } else if prize_x == 0 {
+                    tokens_along_line(self.a.1, self.b.1, prize_y, max_presses)
+                } else {
+                    None
+                };
+            }
+            let a_numerator = prize_x * self.b.1 - prize_y * self.b.0;
+            let b_numerator = self.a.0 * prize_y - self.a.1 * prize_x;
+            if a_numerator % determinant != 0 || b_numerator % determinant != 0 {
+                return None;
+            }
+            let (a, b) = (a_numerator / determinant, b_numerator / determinant);
+            let in_range = |presses: i64| presses >= 0 && max_presses.is_none_or(|max| presses <= max);
+            (in_range(a) && in_range(b)).then_some(3 * a + b)
+        }
+    }
+
+    /// The fewest tokens to move the claw `prize` along a line when A moves
+    /// it `a` and B moves it `b` along the same line.
+    ///
+    /// Whichever button moves the claw further per token should be pressed
+    /// as often as possible. Counting down from there, the remainder left for
+    /// the other button repeats within `other` presses, so that's as far as
+    /// it needs to go. Buttons moving in opposite directions aren't handled,
+    /// since no machine has them.
+    fn tokens_along_line(a: i64, b: i64, prize: i64, max_presses: Option<i64>) -> Option<i64> {
+        let (a, b, prize) = if a <= 0 && b <= 0 { (-a, -b, -prize) } else { (a, b, prize) };
+        if a < 0 || b < 0 || prize < 0 {
+            return None;
+        }
+        let ((cheap, cheap_cost), (other, other_cost)) = if a > 3 * b { ((a, 3), (b, 1)) } else { ((b, 1), (a, 3)) };
+        if cheap == 0 {
+            return (prize == 0).then_some(0);
+        }
+        let most = max_presses.map_or(prize / cheap, |max| max.min(prize / cheap));
+        let (cheap_presses, other_presses) = (0..=most)
+            .rev()
+            .take(other.max(1) as usize)
+            .find_map(|cheap_presses| {
+                let rest = prize - cheap_presses * cheap;
+                match other {
+                    0 => (rest == 0).then_some((cheap_presses, 0)),
+                    _ => (rest % other == 0).then_some((cheap_presses, rest / other)),
+                }
+            })?;
+        // Fewer presses of the cheap button would only need more of the other.
+        if max_presses.is_some_and(|max| other_presses > max) {
+            return None;
+        }
+        Some(cheap_presses * cheap_cost + other_presses * other_cost)
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Arcade {
+        machines: Vec<ClawMachine>,
+    }
+
+    impl Arcade {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            self.machines = parse::blocks(&io_utils::file_to_string(filename))
+                .iter()
+                .map(|block| ClawMachine::from_block(block))
+                .collect();
+        }
+
+        pub fn fewest_tokens(&self, offset: i64, max_presses: Option<i64>) -> i64 {
+            self.machines.iter()
+                .filter_map(|machine| machine.tokens(offset, max_presses))
+                .sum()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Arcade;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        arcade: Arcade,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.arcade.parse_input_file(filename);
+            Answer::I64(self.arcade.fewest_tokens(0, Some(100)))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::I64(480); "example_1")]
+        #[test_case(2, Answer::I64(272); "collinear_buttons")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Arcade;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        arcade: Arcade,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.arcade.parse_input_file(filename);
+            Answer::I64(self.arcade.fewest_tokens(10_000_000_000_000, None))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::I64(875_318_608_908); "example_1")]
+        #[test_case(2, Answer::I64(24_285_714_285_879); "collinear_buttons")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
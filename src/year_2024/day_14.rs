@@ -3,13 +3,16 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2024, day: 14 };
 
+use crate::utils::{params::{self, ParamError}, visualize::Visualize};
+
 mod utils {
-    use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+    use std::{cmp::Ordering, collections::HashMap, fmt};
 
     use lazy_static::lazy_static;
     use regex::Regex;
+    use strum_macros::{Display, EnumString};
 
-    use crate::utils::io_utils;
+    use crate::utils::{io_utils, math_utils, visualize::{Colour, Frame, Visualize}};
 
     lazy_static! {
         static ref LINE_RE: Regex = Regex::new(r"p=(?<p_x>\d+),(?<p_y>\d+) v=(?<v_x>\-?\d+),(?<v_y>\-?\d+)").unwrap();
@@ -30,6 +33,60 @@ mod utils {
         }
     }
 
+    /// Ways of scoring how ordered the robots look along one axis, given how
+    /// many robots there are at each coordinate. Scoring one axis at a time
+    /// is what lets `Robots::find_tree` split the search by period.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, Display, EnumString)]
+    #[strum(serialize_all = "snake_case")]
+    pub enum Score {
+        /// Negative Shannon entropy of the positions.
+        Entropy,
+        /// The number of robots in the densest (wrapping) window a third of
+        /// the axis long.
+        ClusterDensity,
+        /// The longest run of consecutive coordinates with at least twice the
+        /// average number of robots, i.e. the longest band of dense columns
+        /// (or rows). This isn't the longest horizontal run of robots in a
+        /// frame, which depends on both axes at once and so can't be split by
+        /// period, but the tree's frame makes both long.
+        LongestRun,
+    }
+
+    impl Score {
+        /// Higher scores are more ordered.
+        fn score(&self, counts: &[usize]) -> f64 {
+            let total: usize = counts.iter().sum();
+            match self {
+                Self::Entropy => {
+                    counts.iter()
+                        .filter(|count| **count > 0)
+                        .map(|count| {
+                            let p = *count as f64 / total as f64;
+                            p * p.ln()
+                        })
+                        .sum()
+                },
+                Self::ClusterDensity => {
+                    let window = counts.len() / 3;
+                    (0..counts.len())
+                        .map(|start| {
+                            (start..start + window).map(|idx| counts[idx % counts.len()]).sum::<usize>()
+                        })
+                        .max()
+                        .unwrap_or(0) as f64
+                },
+                Self::LongestRun => {
+                    let (mut longest, mut current) = (0, 0);
+                    for count in counts.iter() {
+                        current = if count * counts.len() >= 2 * total { current + 1 } else { 0 };
+                        longest = longest.max(current);
+                    }
+                    longest as f64
+                },
+            }
+        }
+    }
+
     #[derive(Debug)]
     struct Robot {
         pos: Vector,
@@ -71,6 +128,9 @@ mod utils {
         width: isize,
         height: isize,
         robots: Vec<Robot>,
+        seconds: isize,
+        /// How to score each frame in its caption, if at all.
+        caption_score: Option<Score>,
     }
 
     impl Robots {
@@ -79,9 +139,15 @@ mod utils {
                 width,
                 height,
                 robots: Vec::new(),
+                seconds: 0,
+                caption_score: None,
             }
         } 
 
+        pub fn set_caption_score(&mut self, score: Score) {
+            self.caption_score = Some(score);
+        }
+
         pub fn parse_input_file(&mut self, filename: &str) {
             self.robots = io_utils::file_to_lines(filename)
                 .map(|line| Robot::from_str(&line))
@@ -91,6 +157,7 @@ mod utils {
         pub fn simulate(&mut self, seconds: isize) {
             self.robots.iter_mut()
                 .for_each(|robot| robot.simulate(seconds, self.width, self.height));
+            self.seconds += seconds;
         }
 
         pub fn safety_factor(&self) -> usize {
@@ -106,60 +173,201 @@ mod utils {
             });
             quad_counts.values().product()
         }
+
+        /// Returns how many robots are at each x coordinate and at each y
+        /// coordinate.
+        fn axis_counts(&self) -> (Vec<usize>, Vec<usize>) {
+            let mut x_counts = vec![0; self.width as usize];
+            let mut y_counts = vec![0; self.height as usize];
+            self.robots.iter().for_each(|robot| {
+                x_counts[robot.pos.x as usize] += 1;
+                y_counts[robot.pos.y as usize] += 1;
+            });
+            (x_counts, y_counts)
+        }
+
+        /// Finds the most ordered frame (the Christmas tree), returning the
+        /// seconds elapsed since parsing and the rendered frame. The robots'
+        /// x positions repeat every `width` seconds and their y positions every
+        /// `height` seconds, so the best x and y phases are found separately
+        /// and combined by the Chinese remainder theorem. This takes
+        /// `max(width, height)` simulated seconds instead of `width * height`.
+        pub fn find_tree(&mut self, score: Score) -> (isize, String) {
+            let start = self.seconds;
+            let mut best_x = (f64::MIN, 0);
+            let mut best_y = (f64::MIN, 0);
+            for offset in 0..self.width.max(self.height) {
+                let (x_counts, y_counts) = self.axis_counts();
+                let (x_score, y_score) = (score.score(&x_counts), score.score(&y_counts));
+                if offset < self.width && x_score > best_x.0 { best_x = (x_score, offset); }
+                if offset < self.height && y_score > best_y.0 { best_y = (y_score, offset); }
+                self.simulate(1);
+            }
+            let offset = math_utils::chinese_remainder([
+                (best_x.1 as i64, self.width as i64),
+                (best_y.1 as i64, self.height as i64),
+            ]).expect("Width and height should be coprime.") as isize;
+            self.simulate(start + offset - self.seconds);
+            (self.seconds - start, self.frame().to_string())
+        }
     }
 
-    impl Display for Robots {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    impl Visualize for Robots {
+        /// Draws the number of robots on each tile as in the puzzle's
+        /// examples.
+        fn frame(&self) -> Frame {
             let mut counts: HashMap<Vector, usize> = HashMap::new();
             self.robots.iter().for_each(|robot| {
-                counts.entry(Vector { x: robot.pos.x, y: robot.pos.y })
-                    .and_modify(|count| *count += 1)
-                    .or_insert(1);
+                *counts.entry(robot.pos).or_default() += 1;
             });
+            let caption = match self.caption_score {
+                None => format!("After {} seconds:", self.seconds),
+                Some(score) => {
+                    let (x_counts, y_counts) = self.axis_counts();
+                    format!(
+                        "After {} seconds ({score}: x {:.3}, y {:.3}):",
+                        self.seconds,
+                        score.score(&x_counts),
+                        score.score(&y_counts),
+                    )
+                },
+            };
+            let mut frame = Frame::new(&caption);
             for y in 0..self.height {
-                for x in 0..self.width {
-                    let ch = match counts.get(&Vector {x, y}) {
-                        None => ' ',
-                        _ => '#',
-                    };
-                    write!(f, "{}", ch)?;
-                }
-                write!(f, "\n")?;
+                frame.push_row((0..self.width).map(|x| {
+                    match counts.get(&Vector { x, y }) {
+                        None => '.',
+                        Some(count) => char::from_digit((*count).min(9) as u32, 10).unwrap(),
+                    }
+                }));
+            }
+            frame
+        }
+
+        /// Moves the robots one second, stopping once every position they
+        /// can be in has been shown (their positions repeat after that).
+        fn step(&mut self) -> bool {
+            if self.seconds >= self.width * self.height { return false; }
+            self.simulate(1);
+            true
+        }
+
+        fn colour(&self, ch: char) -> Colour {
+            match ch {
+                '.' => Colour::Background,
+                _ => Colour::Green,
+            }
+        }
+    }
+
+    impl fmt::Display for Robots {
+        /// Draws `frame` with a `#` for each tile with robots on it, and no
+        /// caption.
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for row in self.frame().rows() {
+                let row: String = row.iter().map(|ch| if *ch == '.' { ' ' } else { '#' }).collect();
+                writeln!(f, "{row}")?;
             }
             Ok(())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::utils::io_utils::{self, InputFileType};
+        use super::*;
+        use super::super::DAY;
+
+        #[test]
+        fn frames_match_example() {
+            let mut robots = Robots::new(11, 7);
+            robots.parse_input_file(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            let rows = |frame: Frame| -> Vec<String> {
+                frame.rows().iter().map(|row| row.iter().collect()).collect()
+            };
+            assert_eq!(
+                rows(robots.frame()),
+                vec!["1.12.......", "...........", "...........", "......11.11", "1.1........", ".........1.", ".......1..."],
+            );
+            robots.simulate(100);
+            assert_eq!(
+                rows(robots.frame()),
+                vec!["......2..1.", "...........", "1..........", ".11........", ".....1.....", "...12......", ".1....1...."],
+            );
+            assert_eq!(
+                robots.to_string(),
+                "      #  # \n           \n#          \n ##        \n     #     \n   ##      \n #    #    \n",
+            );
+        }
+
+        #[test]
+        fn every_score_finds_tree() {
+            for score in [Score::Entropy, Score::ClusterDensity, Score::LongestRun] {
+                let mut robots = Robots::new(101, 103);
+                robots.parse_input_file(&io_utils::input_filename(&DAY, InputFileType::Example(2)));
+                let (seconds, frame) = robots.find_tree(score);
+                assert_eq!(seconds, 6_532, "{score:?}");
+                assert!(frame.contains(&"1".repeat(31)), "{score:?}");
+            }
+        }
+    }
+}
+
+pub use utils::Score;
+
+/// Returns the number of seconds until the robots first arrange themselves
+/// into a Christmas tree and the rendered frame showing it.
+pub fn christmas_tree(filename: &str, score: Score) -> (isize, String) {
+    let mut robots = utils::Robots::new(101, 103);
+    robots.parse_input_file(filename);
+    robots.find_tree(score)
+}
+
+/// Returns the robots' simulation, for animating. Part two's parameters set
+/// the size of the space and the score shown in each frame's caption.
+pub fn simulation(filename: &str, assignments: &[(String, String)]) -> Result<Box<dyn Visualize>, ParamError> {
+    let mut soln = part_two::Soln::default();
+    params::apply(&mut soln, assignments)?;
+    let mut robots = soln.robots(filename);
+    robots.set_caption_score(soln.score());
+    Ok(Box::new(robots))
 }
 
 pub mod part_one {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
     use super::utils::Robots;
 
     #[derive(Debug)]
     pub struct Soln {
-        robots: Robots,
+        width: isize,
+        height: isize,
+        seconds: isize,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_dimensions(101, 103)
+            Self {
+                width: 101,
+                height: 103,
+                seconds: 100,
+            }
         }
     }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            self.robots.parse_input_file(filename);
-            self.robots.simulate(100);
-            Answer::Usize(self.robots.safety_factor())
+            let mut robots = Robots::new(self.width, self.height);
+            robots.parse_input_file(filename);
+            robots.simulate(self.seconds);
+            Answer::Usize(robots.safety_factor())
         }
-    }
 
-    impl Soln {
-        fn with_dimensions(width: isize, height: isize) -> Self {
-            Self {
-                robots: Robots::new(width, height),
-            }
+        solution_params! {
+            width: "Width of the space the robots move in",
+            height: "Height of the space the robots move in",
+            seconds: "Seconds to simulate",
         }
     }
 
@@ -172,8 +380,9 @@ pub mod part_one {
 
         #[test_case(1, Answer::Usize(12); "example_1")]
         fn examples_are_correct(example_key: u8, answer: Answer) {
-            test_utils::check_example_case(
-                &mut Soln::with_dimensions(11, 7),
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("width", "11"), ("height", "7")],
                 example_key,
                 answer,
                 &DAY,
@@ -183,42 +392,90 @@ pub mod part_one {
 }
 
 pub mod part_two {
+    use crate::solution_params;
     use crate::utils::solution::{Answer, Solution};
 
-    use super::utils::Robots;
+    use super::utils::{Robots, Score};
 
     #[derive(Debug)]
     pub struct Soln {
-        _robots: Robots,
+        width: isize,
+        height: isize,
+        score: Score,
     }
 
     impl Default for Soln {
         fn default() -> Self {
-            Self::with_dimensions(101, 103)
+            Self {
+                width: 101,
+                height: 103,
+                score: Score::Entropy,
+            }
+        }
+    }
+
+    impl Soln {
+        /// The robots in the file, in a space of the configured size.
+        pub(super) fn robots(&self, filename: &str) -> Robots {
+            let mut robots = Robots::new(self.width, self.height);
+            robots.parse_input_file(filename);
+            robots
+        }
+
+        pub(super) fn score(&self) -> Score {
+            self.score
         }
     }
 
     impl Solution for Soln {
-        fn solve(&mut self, _filename: &str) -> Answer {
-            /* 
-             * Code to print the display output to find the tree
-            self.robots.parse_input_file(filename);
-            for seconds in 1..=(101 * 103) {
-                self.robots.simulate(1);
-                println!("After {} seconds:", seconds);
-                println!("{}", &self.robots);
-            }
-            */
-            // Based on printing the display output
-            Answer::Usize(8_159)
+        fn solve(&mut self, filename: &str) -> Answer {
+            let (seconds, _) = self.robots(filename).find_tree(self.score);
+            Answer::Usize(seconds as usize)
+        }
+
+        solution_params! {
+            width: "Width of the space the robots move in",
+            height: "Height of the space the robots move in",
+            score: "How to score frames: entropy, cluster_density or longest_run",
         }
     }
 
-    impl Soln {
-        fn with_dimensions(width: isize, height: isize) -> Self {
-            Self {
-                _robots: Robots::new(width, height),
-            }
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{io_utils::{self, InputFileType}, test_utils, solution::Answer};
+        use super::*;
+        use super::super::{christmas_tree, simulation, DAY};
+
+        #[test_case(2, "entropy", Answer::Usize(6_532); "example_2_entropy")]
+        #[test_case(2, "cluster_density", Answer::Usize(6_532); "example_2_cluster_density")]
+        #[test_case(2, "longest_run", Answer::Usize(6_532); "example_2_longest_run")]
+        fn examples_are_correct(example_key: u8, score: &str, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("score", score)],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+
+        #[test]
+        fn christmas_tree_is_rendered() {
+            let (seconds, frame) = christmas_tree(&io_utils::input_filename(&DAY, InputFileType::Example(2)), Score::LongestRun);
+            assert_eq!(seconds, 6_532);
+            assert!(frame.starts_with("After 6532 seconds:\n"), "{frame}");
+        }
+
+        #[test]
+        fn simulation_takes_params() {
+            let filename = io_utils::input_filename(&DAY, InputFileType::Example(1));
+            let params = [(String::from("width"), String::from("11")), (String::from("height"), String::from("7"))];
+            let frame = simulation(&filename, &params).unwrap().frame();
+            assert_eq!((frame.width(), frame.height()), (11, 7));
+            assert!(frame.caption().starts_with("After 0 seconds (entropy: x "), "{}", frame.caption());
+            let params = [(String::from("seconds"), String::from("1"))];
+            assert!(simulation(&filename, &params).is_err());
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 15 };
+
+mod utils {
+    use std::collections::HashSet;
+
+    use crate::utils::io_utils::Sections;
+
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+    struct Point {
+        row: usize,
+        col: usize,
+    }
+
+    impl Point {
+        fn step(&self, (delta_row, delta_col): (isize, isize)) -> Self {
+            Self {
+                row: self.row.checked_add_signed(delta_row).unwrap(),
+                col: self.col.checked_add_signed(delta_col).unwrap(),
+            }
+        }
+    }
+
+    fn direction(ch: char) -> (isize, isize) {
+        match ch {
+            '^' => (-1, 0),
+            'v' => (1, 0),
+            '<' => (0, -1),
+            '>' => (0, 1),
+            _ => panic!("Unrecognized move {ch}."),
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Warehouse {
+        grid: Vec<Vec<char>>,
+        robot: Point,
+        moves: Vec<char>,
+    }
+
+    impl Warehouse {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let sections = Sections::from_file(filename, 2);
+            self.grid = sections.nth(0).grid();
+            self.moves = sections.nth(1).lines().flat_map(|line| line.chars()).collect();
+            self.find_robot();
+        }
+
+        fn find_robot(&mut self) {
+            self.robot = self.grid.iter()
+                .enumerate()
+                .find_map(|(row, line)| {
+                    line.iter().position(|ch| *ch == '@').map(|col| Point { row, col })
+                })
+                .expect("Warehouse should have a robot.");
+        }
+
+        /// Doubles the width of everything but the robot, so boxes become
+        /// `[]`.
+        pub fn widen(&mut self) {
+            self.grid = self.grid.iter()
+                .map(|line| {
+                    line.iter()
+                        .flat_map(|ch| match ch {
+                            '#' => ['#', '#'],
+                            'O' => ['[', ']'],
+                            '.' => ['.', '.'],
+                            '@' => ['@', '.'],
+                            _ => panic!("Unrecognized tile {ch}."),
+                        })
+                        .collect()
+                })
+                .collect();
+            self.find_robot();
+        }
+
+        fn tile(&self, point: &Point) -> char {
+            self.grid[point.row][point.col]
+        }
+
+        /// Moves the robot, pushing whatever boxes are in the way unless one
+        /// of them would hit a wall. Pushing half of a wide box up or down
+        /// pushes the other half too, so the tiles to move spread out from
+        /// the robot one row at a time.
+        fn try_move(&mut self, delta: (isize, isize)) {
+            let mut to_move: Vec<Point> = Vec::new();
+            let mut seen: HashSet<Point> = HashSet::new();
+            let mut front = vec![self.robot];
+            while !front.is_empty() {
+                let mut next_front = Vec::new();
+                for point in front.into_iter().filter(|point| seen.insert(*point)) {
+                    to_move.push(point);
+                    let next = point.step(delta);
+                    match self.tile(&next) {
+                        '#' => return,
+                        'O' => next_front.push(next),
+                        '[' if delta.0 != 0 => next_front.extend([next, next.step((0, 1))]),
+                        ']' if delta.0 != 0 => next_front.extend([next, next.step((0, -1))]),
+                        '[' | ']' => next_front.push(next),
+                        _ => (),
+                    }
+                }
+                front = next_front;
+            }
+            let tiles: Vec<char> = to_move.iter().map(|point| self.tile(point)).collect();
+            to_move.iter().for_each(|point| self.grid[point.row][point.col] = '.');
+            for (point, tile) in to_move.iter().zip(tiles) {
+                let next = point.step(delta);
+                self.grid[next.row][next.col] = tile;
+            }
+            self.robot = self.robot.step(delta);
+        }
+
+        pub fn run(&mut self) {
+            for ch in self.moves.clone() {
+                self.try_move(direction(ch));
+            }
+        }
+
+        /// The sum of the boxes' GPS coordinates, measured to their left
+        /// edges.
+        pub fn sum_of_gps_coordinates(&self) -> usize {
+            self.grid.iter()
+                .enumerate()
+                .flat_map(|(row, line)| {
+                    line.iter()
+                        .enumerate()
+                        .filter(|(_col, ch)| **ch == 'O' || **ch == '[')
+                        .map(move |(col, _ch)| 100 * row + col)
+                })
+                .sum()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Warehouse;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        warehouse: Warehouse,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.warehouse.parse_input_file(filename);
+            self.warehouse.run();
+            Answer::Usize(self.warehouse.sum_of_gps_coordinates())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(2_028); "example_1")]
+        #[test_case(2, Answer::Usize(10_092); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Warehouse;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        warehouse: Warehouse,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.warehouse.parse_input_file(filename);
+            self.warehouse.widen();
+            self.warehouse.run();
+            Answer::Usize(self.warehouse.sum_of_gps_coordinates())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(2, Answer::Usize(9_021); "example_2")]
+        #[test_case(3, Answer::Usize(618); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
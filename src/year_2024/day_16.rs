@@ -0,0 +1,239 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 16 };
+
+mod utils {
+    use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet}};
+
+    use crate::utils::{io_utils, parse};
+
+    #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+    struct Point {
+        row: usize,
+        col: usize,
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+    enum Direction {
+        North,
+        #[default] East,
+        South,
+        West,
+    }
+
+    impl Direction {
+        fn clockwise(&self) -> Self {
+            match self {
+                Self::North => Self::East,
+                Self::East => Self::South,
+                Self::South => Self::West,
+                Self::West => Self::North,
+            }
+        }
+
+        fn counterclockwise(&self) -> Self {
+            match self {
+                Self::North => Self::West,
+                Self::East => Self::North,
+                Self::South => Self::East,
+                Self::West => Self::South,
+            }
+        }
+    }
+
+    #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+    struct Reindeer {
+        pos: Point,
+        dir: Direction,
+    }
+
+    impl Reindeer {
+        fn forward(&self) -> Self {
+            let Point { row, col } = self.pos;
+            let pos = match self.dir {
+                Direction::North => Point { row: row - 1, col },
+                Direction::East => Point { row, col: col + 1 },
+                Direction::South => Point { row: row + 1, col },
+                Direction::West => Point { row, col: col - 1 },
+            };
+            Self { pos, ..*self }
+        }
+    }
+
+    /// The lowest scores to reach each state, and the states each was
+    /// reached from at that score (several when there are ties).
+    #[derive(Debug, Default)]
+    pub struct BestPaths {
+        score: usize,
+        ends: Vec<Reindeer>,
+        predecessors: HashMap<Reindeer, Vec<Reindeer>>,
+    }
+
+    impl BestPaths {
+        pub fn score(&self) -> usize {
+            self.score
+        }
+
+        /// The number of tiles on at least one best path, found by walking
+        /// back from the end through every tied predecessor.
+        pub fn num_tiles(&self) -> usize {
+            let mut visited: HashSet<Reindeer> = self.ends.iter().copied().collect();
+            let mut stack = self.ends.clone();
+            while let Some(reindeer) = stack.pop() {
+                for predecessor in self.predecessors.get(&reindeer).into_iter().flatten() {
+                    if visited.insert(*predecessor) {
+                        stack.push(*predecessor);
+                    }
+                }
+            }
+            visited.iter().map(|reindeer| reindeer.pos).collect::<HashSet<Point>>().len()
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Maze {
+        walls: HashSet<Point>,
+        start: Point,
+        end: Point,
+    }
+
+    impl Maze {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let grid = parse::char_grid(io_utils::file_to_lines(filename));
+            for (row, line) in grid.iter().enumerate() {
+                for (col, ch) in line.iter().enumerate() {
+                    let point = Point { row, col };
+                    match ch {
+                        '#' => { self.walls.insert(point); },
+                        'S' => self.start = point,
+                        'E' => self.end = point,
+                        '.' => (),
+                        _ => panic!("Unrecognized tile {ch}."),
+                    }
+                }
+            }
+        }
+
+        /// Dijkstra's algorithm over positions and facings, starting east.
+        /// Moving forward scores 1 and turning scores 1000.
+        pub fn best_paths(&self) -> BestPaths {
+            let start = Reindeer { pos: self.start, dir: Direction::East };
+            let mut scores: HashMap<Reindeer, usize> = HashMap::from([(start, 0)]);
+            let mut predecessors: HashMap<Reindeer, Vec<Reindeer>> = HashMap::new();
+            let mut queue: BinaryHeap<Reverse<(usize, Reindeer)>> = BinaryHeap::from([Reverse((0, start))]);
+            let mut best: Option<BestPaths> = None;
+            while let Some(Reverse((score, reindeer))) = queue.pop() {
+                if score > scores[&reindeer] {
+                    continue;
+                }
+                if reindeer.pos == self.end {
+                    match best.as_mut() {
+                        None => best = Some(BestPaths { score, ends: vec![reindeer], predecessors: HashMap::new() }),
+                        Some(best) if best.score == score => best.ends.push(reindeer),
+                        Some(_) => break,
+                    }
+                    continue;
+                }
+                let forward = reindeer.forward();
+                let moves = [
+                    (forward, 1),
+                    (Reindeer { dir: reindeer.dir.clockwise(), ..reindeer }, 1_000),
+                    (Reindeer { dir: reindeer.dir.counterclockwise(), ..reindeer }, 1_000),
+                ];
+                for (next, cost) in moves {
+                    if self.walls.contains(&next.pos) {
+                        continue;
+                    }
+                    let next_score = score + cost;
+                    match scores.get(&next) {
+                        Some(existing) if next_score > *existing => (),
+                        Some(existing) if next_score == *existing => predecessors.entry(next).or_default().push(reindeer),
+                        _ => {
+                            scores.insert(next, next_score);
+                            predecessors.insert(next, vec![reindeer]);
+                            queue.push(Reverse((next_score, next)));
+                        },
+                    }
+                }
+            }
+            let mut best = best.expect("The end should be reachable.");
+            best.predecessors = predecessors;
+            best
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Maze;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        maze: Maze,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.maze.parse_input_file(filename);
+            Answer::Usize(self.maze.best_paths().score())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(7_036); "example_1")]
+        #[test_case(2, Answer::Usize(11_048); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Maze;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        maze: Maze,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.maze.parse_input_file(filename);
+            Answer::Usize(self.maze.best_paths().num_tiles())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(45); "example_1")]
+        #[test_case(2, Answer::Usize(64); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 17 };
+
+mod utils {
+    use itertools::Itertools;
+
+    use crate::utils::{io_utils::Sections, parse};
+
+    /// A 3-bit computer: every instruction is an opcode and an operand, each
+    /// between 0 and 7.
+    #[derive(Debug, Default, Clone)]
+    pub struct Computer {
+        registers: [u64; 3],
+        program: Vec<u8>,
+    }
+
+    impl Computer {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let sections = Sections::from_file(filename, 2);
+            self.registers = sections.nth(0).lines()
+                .map(|line| parse::ints::<u64>(line)[0])
+                .collect::<Vec<u64>>()
+                .try_into()
+                .expect("Computer should have three registers.");
+            self.program = parse::ints(sections.nth(1).lines().next().unwrap());
+        }
+
+        /// Runs the program with register A set to `a` until it halts,
+        /// returning its output.
+        fn run(&self, a: u64) -> Vec<u8> {
+            let [mut a, mut b, mut c] = [a, self.registers[1], self.registers[2]];
+            let mut output = Vec::new();
+            let mut pointer = 0;
+            while let Some(&[opcode, operand, ..]) = self.program.get(pointer..) {
+                let combo = match operand {
+                    0..=3 => Some(operand as u64),
+                    4 => Some(a),
+                    5 => Some(b),
+                    6 => Some(c),
+                    _ => None,
+                };
+                // A divided by 2 to the power of the combo operand
+                let divided = combo.map(|combo| {
+                    u32::try_from(combo).ok().and_then(|shift| a.checked_shr(shift)).unwrap_or(0)
+                });
+                let combo = || combo.expect("Combo operand 7 is reserved.");
+                let divided = || divided.expect("Combo operand 7 is reserved.");
+                match opcode {
+                    0 => a = divided(),
+                    1 => b ^= operand as u64,
+                    2 => b = combo() % 8,
+                    3 if a != 0 => {
+                        pointer = operand as usize;
+                        continue;
+                    },
+                    3 => (),
+                    4 => b ^= c,
+                    5 => output.push((combo() % 8) as u8),
+                    6 => b = divided(),
+                    7 => c = divided(),
+                    _ => panic!("Unrecognized opcode {opcode}."),
+                }
+                pointer += 2;
+            }
+            output
+        }
+
+        pub fn output(&self) -> String {
+            self.run(self.registers[0]).iter().join(",")
+        }
+
+        /// The lowest value of register A that makes the program output a
+        /// copy of itself.
+        ///
+        /// Programs like this loop, shifting A right by three bits and
+        /// printing one number each time round, until A is zero. So the
+        /// last number printed depends only on A's top three bits, the one
+        /// before that on its top six bits and so on. Building A up three
+        /// bits at a time, keeping every prefix whose output matches the end
+        /// of the program, finds all the answers.
+        pub fn lowest_quine(&self) -> u64 {
+            let mut candidates: Vec<u64> = vec![0];
+            for idx in (0..self.program.len()).rev() {
+                candidates = candidates.iter()
+                    .flat_map(|a| (0..8).map(move |bits| a << 3 | bits))
+                    .filter(|a| self.run(*a) == self.program[idx..])
+                    .collect();
+            }
+            candidates.into_iter().min().expect("Some value of A should output the program.")
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Computer;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        computer: Computer,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.computer.parse_input_file(filename);
+            Answer::String(self.computer.output())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::String("4,6,3,5,6,3,5,2,1,0".to_string()); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Computer;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        computer: Computer,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.computer.parse_input_file(filename);
+            Answer::U64(self.computer.lowest_quine())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(2, Answer::U64(117_440); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 18 };
+
+mod utils {
+    use std::collections::{HashSet, VecDeque};
+
+    use crate::utils::{disjoint_set::DisjointSet, io_utils, parse};
+
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+    struct Point {
+        x: usize,
+        y: usize,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct MemorySpace {
+        size: usize,
+        bytes: Vec<Point>,
+    }
+
+    impl MemorySpace {
+        pub fn parse_input_file(&mut self, filename: &str, size: usize) {
+            self.size = size;
+            self.bytes = io_utils::file_to_lines(filename)
+                .map(|line| {
+                    let [x, y] = parse::ints(&line)[..] else { panic!("Byte should have two coordinates."); };
+                    Point { x, y }
+                })
+                .collect();
+        }
+
+        fn neighbors(&self, point: &Point) -> impl Iterator<Item = Point> {
+            let (size, Point { x, y }) = (self.size, *point);
+            [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)].into_iter()
+                .filter(move |(x, y)| *x <= size && *y <= size)
+                .map(|(x, y)| Point { x, y })
+        }
+
+        /// The fewest steps from the top left to the bottom right once the
+        /// first `num_bytes` bytes have fallen, if it can be reached.
+        pub fn fewest_steps(&self, num_bytes: usize) -> Option<usize> {
+            let corrupted: HashSet<Point> = self.bytes.iter().take(num_bytes).copied().collect();
+            let end = Point { x: self.size, y: self.size };
+            let mut visited: HashSet<Point> = HashSet::from([Point::default()]);
+            let mut queue: VecDeque<(Point, usize)> = VecDeque::from([(Point::default(), 0)]);
+            while let Some((point, steps)) = queue.pop_front() {
+                if point == end {
+                    return Some(steps);
+                }
+                for neighbor in self.neighbors(&point) {
+                    if !corrupted.contains(&neighbor) && visited.insert(neighbor) {
+                        queue.push_back((neighbor, steps + 1));
+                    }
+                }
+            }
+            None
+        }
+
+        /// The coordinates of the first byte that cuts the top left off from
+        /// the bottom right.
+        ///
+        /// Works backwards from every byte having fallen: joins up the
+        /// uncorrupted space, then clears the bytes in reverse order until
+        /// the corners are connected. The last byte cleared is the one that
+        /// cut them off.
+        pub fn first_blocking_byte(&self) -> String {
+            let corrupted: HashSet<Point> = self.bytes.iter().copied().collect();
+            let mut space: DisjointSet<Point> = DisjointSet::default();
+            let clear = |space: &mut DisjointSet<Point>, point: Point, corrupted: &HashSet<Point>| {
+                space.make_set(point);
+                for neighbor in self.neighbors(&point).filter(|neighbor| !corrupted.contains(neighbor)) {
+                    space.union(point, neighbor);
+                }
+            };
+            for x in 0..=self.size {
+                for y in 0..=self.size {
+                    let point = Point { x, y };
+                    if !corrupted.contains(&point) {
+                        clear(&mut space, point, &corrupted);
+                    }
+                }
+            }
+            let mut corrupted = corrupted;
+            let end = Point { x: self.size, y: self.size };
+            for byte in self.bytes.iter().rev() {
+                corrupted.remove(byte);
+                clear(&mut space, *byte, &corrupted);
+                if space.same_component(&Point::default(), &end) {
+                    return format!("{},{}", byte.x, byte.y);
+                }
+            }
+            panic!("The bytes should cut off the exit.")
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::MemorySpace;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        size: usize,
+        num_bytes: usize,
+        memory_space: MemorySpace,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                size: 70,
+                num_bytes: 1_024,
+                memory_space: MemorySpace::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.memory_space.parse_input_file(filename, self.size);
+            Answer::Usize(self.memory_space.fewest_steps(self.num_bytes).expect("The exit should be reachable."))
+        }
+
+        solution_params! {
+            size: "Largest coordinate of the memory space",
+            num_bytes: "Bytes fallen before walking",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(22); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("size", "6"), ("num_bytes", "12")],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::MemorySpace;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        size: usize,
+        memory_space: MemorySpace,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                size: 70,
+                memory_space: MemorySpace::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.memory_space.parse_input_file(filename, self.size);
+            Answer::String(self.memory_space.first_blocking_byte())
+        }
+
+        solution_params! {
+            size: "Largest coordinate of the memory space",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::String("6,1".to_string()); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("size", "6")],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
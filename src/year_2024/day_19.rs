@@ -0,0 +1,124 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 19 };
+
+mod utils {
+    use crate::utils::io_utils::Sections;
+
+    #[derive(Debug, Default)]
+    pub struct Onsen {
+        patterns: Vec<String>,
+        designs: Vec<String>,
+    }
+
+    impl Onsen {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let sections = Sections::from_file(filename, 2);
+            self.patterns = sections.nth(0).lines()
+                .flat_map(|line| line.split(", "))
+                .map(String::from)
+                .collect();
+            self.designs = sections.nth(1).lines().map(String::from).collect();
+        }
+
+        /// The number of ways to arrange towels into the design. The ways to
+        /// make each prefix are the ways to make a shorter prefix followed by
+        /// one towel.
+        fn arrangements(&self, design: &str) -> u64 {
+            let mut ways = vec![0; design.len() + 1];
+            ways[0] = 1;
+            for end in 1..=design.len() {
+                ways[end] = self.patterns.iter()
+                    .filter(|pattern| design[..end].ends_with(pattern.as_str()))
+                    .map(|pattern| ways[end - pattern.len()])
+                    .sum();
+            }
+            ways[design.len()]
+        }
+
+        pub fn num_possible_designs(&self) -> usize {
+            self.designs.iter()
+                .filter(|design| self.arrangements(design) > 0)
+                .count()
+        }
+
+        pub fn total_arrangements(&self) -> u64 {
+            self.designs.iter()
+                .map(|design| self.arrangements(design))
+                .sum()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Onsen;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        onsen: Onsen,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.onsen.parse_input_file(filename);
+            Answer::Usize(self.onsen.num_possible_designs())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(6); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Onsen;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        onsen: Onsen,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.onsen.parse_input_file(filename);
+            Answer::U64(self.onsen.total_arrangements())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(16); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,198 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 20 };
+
+mod utils {
+    use crate::utils::{io_utils, parse};
+
+    #[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+    struct Point {
+        row: usize,
+        col: usize,
+    }
+
+    impl Point {
+        fn offset(&self, delta_row: isize, delta_col: isize) -> Option<Self> {
+            Some(Self {
+                row: self.row.checked_add_signed(delta_row)?,
+                col: self.col.checked_add_signed(delta_col)?,
+            })
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Racetrack {
+        // The number of picoseconds from the start to each track position,
+        // or `None` for walls.
+        times: Vec<Vec<Option<usize>>>,
+        track: Vec<Point>,
+    }
+
+    impl Racetrack {
+        /// Reads the racetrack and follows it from the start, since there is
+        /// only one path to the end.
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let grid = parse::char_grid(io_utils::file_to_lines(filename));
+            let find = |target: char| {
+                grid.iter()
+                    .enumerate()
+                    .find_map(|(row, line)| line.iter().position(|ch| *ch == target).map(|col| Point { row, col }))
+                    .expect("Racetrack should have a start and an end.")
+            };
+            let (start, end) = (find('S'), find('E'));
+            self.times = vec![vec![None; grid[0].len()]; grid.len()];
+            self.track = vec![start];
+            let mut current = start;
+            self.times[start.row][start.col] = Some(0);
+            while current != end {
+                current = [(1, 0), (-1, 0), (0, 1), (0, -1)].iter()
+                    .filter_map(|(delta_row, delta_col)| current.offset(*delta_row, *delta_col))
+                    .find(|next| grid[next.row][next.col] != '#' && self.times[next.row][next.col].is_none())
+                    .expect("Racetrack should lead from the start to the end.");
+                self.times[current.row][current.col] = Some(self.track.len());
+                self.track.push(current);
+            }
+        }
+
+        fn time(&self, point: &Point) -> Option<usize> {
+            *self.times.get(point.row)?.get(point.col)?
+        }
+
+        /// The number of cheats lasting at most `max_cheat` picoseconds that
+        /// save at least `min_saving` picoseconds. A cheat goes straight from
+        /// one track position to another later one within `max_cheat` steps
+        /// (ignoring walls), so only the positions in that diamond around
+        /// each point need checking.
+        pub fn num_cheats(&self, max_cheat: usize, min_saving: usize) -> usize {
+            let max_cheat = max_cheat as isize;
+            self.track.iter()
+                .enumerate()
+                .map(|(time, point)| {
+                    (-max_cheat..=max_cheat)
+                        .flat_map(|delta_row| {
+                            let remaining = max_cheat - delta_row.abs();
+                            (-remaining..=remaining).map(move |delta_col| (delta_row, delta_col))
+                        })
+                        .filter(|(delta_row, delta_col)| {
+                            let cheat = (delta_row.abs() + delta_col.abs()) as usize;
+                            point.offset(*delta_row, *delta_col)
+                                .and_then(|end| self.time(&end))
+                                .is_some_and(|end_time| end_time >= time + cheat + min_saving)
+                        })
+                        .count()
+                })
+                .sum()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Racetrack;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        min_saving: usize,
+        racetrack: Racetrack,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                min_saving: 100,
+                racetrack: Racetrack::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.racetrack.parse_input_file(filename);
+            Answer::Usize(self.racetrack.num_cheats(2, self.min_saving))
+        }
+
+        solution_params! {
+            min_saving: "Fewest picoseconds a cheat must save",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, 64, Answer::Usize(1); "example_1_64")]
+        #[test_case(1, 40, Answer::Usize(2); "example_1_40")]
+        #[test_case(1, 20, Answer::Usize(5); "example_1_20")]
+        #[test_case(1, 1, Answer::Usize(44); "example_1_1")]
+        fn examples_are_correct(example_key: u8, min_saving: usize, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("min_saving", min_saving.to_string())],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Racetrack;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        min_saving: usize,
+        racetrack: Racetrack,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                min_saving: 100,
+                racetrack: Racetrack::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.racetrack.parse_input_file(filename);
+            Answer::Usize(self.racetrack.num_cheats(20, self.min_saving))
+        }
+
+        solution_params! {
+            min_saving: "Fewest picoseconds a cheat must save",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, 76, Answer::Usize(3); "example_1_76")]
+        #[test_case(1, 74, Answer::Usize(7); "example_1_74")]
+        #[test_case(1, 72, Answer::Usize(29); "example_1_72")]
+        #[test_case(1, 50, Answer::Usize(285); "example_1_50")]
+        fn examples_are_correct(example_key: u8, min_saving: usize, answer: Answer) {
+            test_utils::check_example_case_with_params(
+                &mut Soln::default(),
+                &[("min_saving", min_saving.to_string())],
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 21 };
+
+mod utils {
+    use std::collections::HashMap;
+
+    use crate::utils::io_utils;
+
+    /// A keypad's buttons by row, with a space for the gap the robot arms
+    /// must never point at.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    struct Keypad(&'static [&'static str]);
+
+    const NUMERIC: Keypad = Keypad(&["789", "456", "123", " 0A"]);
+    const DIRECTIONAL: Keypad = Keypad(&[" ^A", "<v>"]);
+
+    impl Keypad {
+        fn position(&self, button: char) -> (usize, usize) {
+            self.0.iter()
+                .enumerate()
+                .find_map(|(row, buttons)| buttons.find(button).map(|col| (row, col)))
+                .unwrap_or_else(|| panic!("Keypad should have button {button}."))
+        }
+
+        /// The button presses on the directional keypad that move the arm
+        /// from one button to another and press it. Only moving in one
+        /// direction then the other can be shortest once the keypads are
+        /// chained, and only if it doesn't cross the gap.
+        fn moves(&self, from: char, to: char) -> Vec<String> {
+            let ((from_row, from_col), (to_row, to_col)) = (self.position(from), self.position(to));
+            let gap = self.position(' ');
+            let vertical = if to_row > from_row { "v" } else { "^" }.repeat(from_row.abs_diff(to_row));
+            let horizontal = if to_col > from_col { ">" } else { "<" }.repeat(from_col.abs_diff(to_col));
+            let mut moves = Vec::new();
+            if (from_row, to_col) != gap {
+                moves.push(format!("{horizontal}{vertical}A"));
+            }
+            if (to_row, from_col) != gap && !vertical.is_empty() && !horizontal.is_empty() {
+                moves.push(format!("{vertical}{horizontal}A"));
+            }
+            moves
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Keypads {
+        codes: Vec<String>,
+        // The fewest presses on the outermost keypad to make a robot at the
+        // given depth press the second button, its arm starting on the first.
+        cache: HashMap<(char, char, usize), u64>,
+    }
+
+    impl Keypads {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            self.codes = io_utils::file_to_lines(filename).collect();
+        }
+
+        /// The fewest presses you need to make a robot `depth` directional
+        /// keypads away type `sequence` on its keypad, each robot's arm
+        /// starting (and ending) on `A`.
+        fn presses(&mut self, sequence: &str, depth: usize) -> u64 {
+            if depth == 0 {
+                return sequence.len() as u64;
+            }
+            let mut total = 0;
+            let mut from = 'A';
+            for to in sequence.chars() {
+                total += self.button_presses(from, to, depth);
+                from = to;
+            }
+            total
+        }
+
+        fn button_presses(&mut self, from: char, to: char, depth: usize) -> u64 {
+            if let Some(presses) = self.cache.get(&(from, to, depth)) {
+                return *presses;
+            }
+            let presses = DIRECTIONAL.moves(from, to).iter()
+                .map(|moves| self.presses(moves, depth - 1))
+                .min()
+                .unwrap();
+            self.cache.insert((from, to, depth), presses);
+            presses
+        }
+
+        /// The sum over the codes of the fewest presses to type the code
+        /// through `robots` robots on directional keypads and one on the
+        /// numeric keypad, times the code's numeric part.
+        pub fn sum_of_complexities(&mut self, robots: usize) -> u64 {
+            self.codes.clone().iter()
+                .map(|code| {
+                    let mut from = 'A';
+                    let mut presses = 0;
+                    for to in code.chars() {
+                        presses += NUMERIC.moves(from, to).iter()
+                            .map(|moves| self.presses(moves, robots))
+                            .min()
+                            .unwrap();
+                        from = to;
+                    }
+                    let numeric_part: u64 = code.trim_end_matches('A').parse().expect("Code should be numeric.");
+                    presses * numeric_part
+                })
+                .sum()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Keypads;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        robots: usize,
+        keypads: Keypads,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                robots: 2,
+                keypads: Keypads::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.keypads.parse_input_file(filename);
+            Answer::U64(self.keypads.sum_of_complexities(self.robots))
+        }
+
+        solution_params! {
+            robots: "Robots using directional keypads",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(126_384); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::solution_params;
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Keypads;
+
+    #[derive(Debug)]
+    pub struct Soln {
+        robots: usize,
+        keypads: Keypads,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self {
+                robots: 25,
+                keypads: Keypads::default(),
+            }
+        }
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.keypads.parse_input_file(filename);
+            Answer::U64(self.keypads.sum_of_complexities(self.robots))
+        }
+
+        solution_params! {
+            robots: "Robots using directional keypads",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(154_115_708_116_294); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 22 };
+
+mod utils {
+    use crate::utils::io_utils;
+
+    const PRUNE: u64 = 16_777_216;
+    const NUM_SECRETS: usize = 2_000;
+    // Price changes are between -9 and 9, so a run of four packs into base 19.
+    const NUM_CHANGE_RUNS: usize = 19 * 19 * 19 * 19;
+
+    fn next_secret(secret: u64) -> u64 {
+        let secret = (secret ^ (secret * 64)) % PRUNE;
+        let secret = (secret ^ (secret / 32)) % PRUNE;
+        (secret ^ (secret * 2_048)) % PRUNE
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Market {
+        buyers: Vec<u64>,
+    }
+
+    impl Market {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            self.buyers = io_utils::file_to_lines(filename)
+                .map(|line| line.parse().expect("Line should be a secret number."))
+                .collect();
+        }
+
+        pub fn sum_of_last_secrets(&self) -> u64 {
+            self.buyers.iter()
+                .map(|buyer| (0..NUM_SECRETS).fold(*buyer, |secret, _| next_secret(secret)))
+                .sum()
+        }
+
+        /// The most bananas one run of four price changes can buy. Each buyer
+        /// sells the first time the run appears in their prices, so the
+        /// bananas for every run can be totalled in one pass per buyer.
+        pub fn most_bananas(&self) -> u64 {
+            let mut bananas = vec![0; NUM_CHANGE_RUNS];
+            let mut last_seen: Vec<Option<usize>> = vec![None; NUM_CHANGE_RUNS];
+            for (idx, buyer) in self.buyers.iter().enumerate() {
+                let mut secret = *buyer;
+                let mut run = 0;
+                for step in 0..NUM_SECRETS {
+                    let next = next_secret(secret);
+                    let (price, next_price) = (secret % 10, next % 10);
+                    run = (run * 19 + (9 + next_price - price) as usize) % NUM_CHANGE_RUNS;
+                    if step >= 3 && last_seen[run] != Some(idx) {
+                        last_seen[run] = Some(idx);
+                        bananas[run] += next_price;
+                    }
+                    secret = next;
+                }
+            }
+            bananas.into_iter().max().unwrap()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Market;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        market: Market,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.market.parse_input_file(filename);
+            Answer::U64(self.market.sum_of_last_secrets())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(37_327_623); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Market;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        market: Market,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.market.parse_input_file(filename);
+            Answer::U64(self.market.most_bananas())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(2, Answer::U64(23); "example_2")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
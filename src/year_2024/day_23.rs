@@ -3,14 +3,16 @@ use crate::utils::Day;
 #[cfg(test)]
 const DAY: Day = crate::utils::Day { year: 2024, day: 23 };
 
+use crate::utils::dot::Graph;
+
 mod utils {
-    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::{collections::{BTreeSet, HashMap, HashSet}, ops::Bound::{Excluded, Unbounded}};
 
     use itertools::Itertools;
     use lazy_static::lazy_static;
     use regex::Regex;
 
-    use crate::utils::io_utils;
+    use crate::utils::{dot::Graph, io_utils};
 
     lazy_static! {
         static ref LINE_RE: Regex = Regex::new(r"(?<l>\w+)\-(?<r>\w+)").unwrap();
@@ -83,7 +85,7 @@ mod utils {
             }
         }
 
-        pub fn password(&self) -> String {
+        fn largest_clique(&self) -> BTreeSet<String> {
             let mut max_cliques = Vec::new();
             self.bron_kerbosch(
                 BTreeSet::new(), 
@@ -91,17 +93,54 @@ mod utils {
                 BTreeSet::new(), 
                 &mut max_cliques,
             );
-            let max_len_clique = max_cliques.iter().max_by_key(|c| {
+            max_cliques.into_iter().max_by_key(|c| {
                 c.len()
-            }).unwrap();
-            max_len_clique
+            }).unwrap()
+        }
+
+        pub fn password(&self) -> String {
+            self.largest_clique()
                 .iter()
                 .sorted()
                 .join(",")
         }
+
+        /// Returns the network as a graph. Computers whose names start with
+        /// `t` are boxes, and the LAN party (the largest clique) is filled.
+        pub fn graph(&self) -> Graph {
+            let lan_party = self.largest_clique();
+            let mut graph = Graph::undirected("lan");
+            self.connections.keys()
+                .sorted()
+                .for_each(|computer| {
+                    let node = graph.node(computer);
+                    if computer.starts_with('t') {
+                        node.attr("shape", "box");
+                    }
+                    if lan_party.contains(computer) {
+                        node.attr("lan_party", true).attr("style", "filled");
+                    }
+                });
+            self.connections.iter()
+                .sorted()
+                .for_each(|(computer, neighbors)| {
+                    neighbors.range::<String, _>((Excluded(computer), Unbounded))
+                        .for_each(|neighbor| { graph.edge(computer, neighbor); });
+                });
+            graph.annotate_degrees();
+            graph.annotate_components();
+            graph
+        }
     }
 }
 
+/// Returns the network as a graph (see `NetworkMap::graph`).
+pub fn graph(filename: &str) -> Graph {
+    let mut network_map = utils::NetworkMap::default();
+    network_map.parse_input_file(filename);
+    network_map.graph()
+}
+
 pub mod part_one {
     use crate::utils::solution::{Answer, Solution};
 
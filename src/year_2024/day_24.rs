@@ -0,0 +1,231 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2024, day: 24 };
+
+mod utils {
+    use std::collections::{BTreeSet, HashMap};
+
+    use crate::utils::io_utils::Sections;
+
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum Operation {
+        And,
+        Or,
+        Xor,
+    }
+
+    impl Operation {
+        fn from_str(input: &str) -> Self {
+            match input {
+                "AND" => Self::And,
+                "OR" => Self::Or,
+                "XOR" => Self::Xor,
+                _ => panic!("Unrecognized gate {input}."),
+            }
+        }
+
+        fn apply(&self, left: bool, right: bool) -> bool {
+            match self {
+                Self::And => left && right,
+                Self::Or => left || right,
+                Self::Xor => left ^ right,
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    struct Gate {
+        inputs: [String; 2],
+        operation: Operation,
+        output: String,
+    }
+
+    impl Gate {
+        fn from_str(input: &str) -> Self {
+            let [left, operation, right, "->", output] = input.split_whitespace().collect::<Vec<&str>>()[..] else {
+                panic!("Gate should look like `a OP b -> c`.");
+            };
+            Self {
+                inputs: [left.to_string(), right.to_string()],
+                operation: Operation::from_str(operation),
+                output: output.to_string(),
+            }
+        }
+
+        /// Whether both inputs come straight from `x` and `y`.
+        fn takes_input_bits(&self) -> bool {
+            self.inputs.iter().all(|input| input.starts_with('x') || input.starts_with('y'))
+        }
+
+        /// Whether the inputs are the lowest bits, `x00` and `y00`.
+        fn takes_lowest_bits(&self) -> bool {
+            self.inputs.iter().all(|input| input.ends_with("00"))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        initial: HashMap<String, bool>,
+        gates: Vec<Gate>,
+    }
+
+    impl Circuit {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let sections = Sections::from_file(filename, 2);
+            self.initial = sections.nth(0).lines()
+                .map(|line| {
+                    let (wire, value) = line.split_once(": ").expect("Line should give a wire's value.");
+                    (wire.to_string(), value == "1")
+                })
+                .collect();
+            self.gates = sections.nth(1).lines().map(Gate::from_str).collect();
+        }
+
+        /// The number output on the `z` wires once every gate has settled.
+        pub fn output(&self) -> u64 {
+            let mut values = self.initial.clone();
+            let mut pending: Vec<&Gate> = self.gates.iter().collect();
+            while !pending.is_empty() {
+                let num_pending = pending.len();
+                pending.retain(|gate| {
+                    let [left, right] = &gate.inputs;
+                    match (values.get(left), values.get(right)) {
+                        (Some(left), Some(right)) => {
+                            values.insert(gate.output.clone(), gate.operation.apply(*left, *right));
+                            false
+                        },
+                        _ => true,
+                    }
+                });
+                assert!(pending.len() < num_pending, "Gates should not form a loop.");
+            }
+            values.iter()
+                .filter(|(wire, value)| wire.starts_with('z') && **value)
+                .map(|(wire, _value)| 1 << wire[1..].parse::<u32>().unwrap())
+                .sum()
+        }
+
+        fn feeds(&self, wire: &str, operation: Operation) -> bool {
+            self.gates.iter().any(|gate| gate.operation == operation && gate.inputs.iter().any(|input| input == wire))
+        }
+
+        /// The wires whose outputs were swapped, sorted and joined with
+        /// commas.
+        ///
+        /// The circuit is meant to be a ripple-carry adder, where each bit
+        /// past the first has a full adder:
+        ///
+        /// ```text
+        /// x XOR y -> s        s XOR carry_in -> z
+        /// x AND y -> a        s AND carry_in -> c
+        ///                     a OR c -> carry_out
+        /// ```
+        ///
+        /// and the final carry out is the highest `z` bit. So a gate's output
+        /// is in the wrong place if it is:
+        /// - a `z` bit (except the highest) not made by XOR,
+        /// - made by an XOR of internal wires but not a `z` bit,
+        /// - made by an XOR of input bits but not feeding another XOR,
+        /// - made by an AND but not feeding an OR.
+        ///
+        /// The first bit only has a half adder, so its gates are exempt from
+        /// the last two rules.
+        pub fn swapped_wires(&self) -> String {
+            let highest_z = self.gates.iter()
+                .map(|gate| &gate.output)
+                .filter(|output| output.starts_with('z'))
+                .max()
+                .expect("Circuit should output to z wires.");
+            let swapped: BTreeSet<&str> = self.gates.iter()
+                .filter(|gate| {
+                    let is_z = gate.output.starts_with('z');
+                    match gate.operation {
+                        _ if is_z && gate.operation != Operation::Xor => &gate.output != highest_z,
+                        Operation::Xor if !gate.takes_input_bits() => !is_z,
+                        Operation::Xor => !gate.takes_lowest_bits() && !self.feeds(&gate.output, Operation::Xor),
+                        Operation::And => !gate.takes_lowest_bits() && !self.feeds(&gate.output, Operation::Or),
+                        Operation::Or => false,
+                    }
+                })
+                .map(|gate| gate.output.as_str())
+                .collect();
+            swapped.into_iter().collect::<Vec<&str>>().join(",")
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Circuit;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        circuit: Circuit,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.circuit.parse_input_file(filename);
+            Answer::U64(self.circuit.output())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(4); "example_1")]
+        #[test_case(2, Answer::U64(2_024); "example_2")]
+        #[test_case(3, Answer::U64(275); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Circuit;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        circuit: Circuit,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.circuit.parse_input_file(filename);
+            Answer::String(self.circuit.swapped_wires())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        // An 8-bit adder with four pairs of outputs swapped
+        #[test_case(3, Answer::String("byl,hho,qcx,rgn,tdk,z02,z05,z06".to_string()); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@ const DAY: Day = crate::utils::Day { year: 2024, day: 25 };
 mod utils {
     use itertools::iproduct;
 
-    use crate::utils::io_utils;
+    use crate::utils::io_utils::Sections;
 
     const MAX_HEIGHT: usize = 5;
 
@@ -19,37 +19,37 @@ mod utils {
     #[derive(Debug, PartialEq, Eq, Clone)]
     struct Heights {
         heights_type: HeightsType,
-        pin_heights: [Option<usize>; 5],
+        pin_heights: [usize; 5],
     }
 
     impl Heights {
-        fn new(heights_type: HeightsType) -> Self {
+        /// Reads a schematic: locks have their top row filled and keys their
+        /// bottom row, and each pin's height is the number of filled cells in
+        /// its column between those rows.
+        fn from_grid(grid: &[Vec<char>]) -> Self {
+            assert_eq!(grid.len(), MAX_HEIGHT + 2, "Schematic should have {} rows.", MAX_HEIGHT + 2);
+            let heights_type = match grid[0].iter().collect::<String>().as_str() {
+                "#####" => HeightsType::Lock,
+                "....." => HeightsType::Key,
+                _ => panic!("Unrecognized starting row"),
+            };
+            let mut pin_heights = [0; 5];
+            pin_heights.iter_mut()
+                .enumerate()
+                .for_each(|(col, height)| {
+                    *height = grid[1..=MAX_HEIGHT].iter()
+                        .filter(|row| row[col] == '#')
+                        .count();
+                });
             Self {
                 heights_type,
-                pin_heights: [None; 5],
+                pin_heights,
             }
         }
 
-        fn update_pin_height(&mut self, col: usize, height: usize) {
-            if self.pin_heights[col] == None {
-                self.pin_heights[col] = Some(height);
-            }
-        }
-
-        fn update_all_nones(&mut self) {
-            self.pin_heights.iter_mut()
-                .filter(|height| **height == None)
-                .for_each(|height| {
-                    *height = Some(match self.heights_type {
-                        HeightsType::Lock => MAX_HEIGHT,
-                        HeightsType::Key => 0,
-                    });
-                });
-        }
-
         fn fits(&self, other: &Self) -> bool {
             self.pin_heights.iter().zip(other.pin_heights.iter()).all(|(s, o)| {
-                s.unwrap() + o.unwrap() <= MAX_HEIGHT
+                s + o <= MAX_HEIGHT
             })
         }
     }
@@ -62,52 +62,15 @@ mod utils {
 
     impl Schematics {
         pub fn parse_input_file(&mut self, filename: &str) {
-            let mut cur: Option<Heights> = None;
-            let mut rows: usize = 0;
-            io_utils::file_to_lines(filename).for_each(|line| {
-                if line.is_empty() {
-                    cur = None;
-                    rows = 0;
-                } else if cur == None {
-                    if line == "#####" {
-                        cur = Some(Heights::new(HeightsType::Lock));
-                    } else if line == "....." {
-                        cur = Some(Heights::new(HeightsType::Key));
-                    } else {
-                        panic!("Unrecognized starting row");
-                    }
-                } else {
-                    if cur.as_ref().unwrap().heights_type == HeightsType::Lock {
-                        if rows == MAX_HEIGHT {
-                            cur.as_mut().unwrap().update_all_nones();
-                            self.locks.push(cur.as_ref().unwrap().clone());
-                        } else {
-                            line.char_indices()
-                                .filter(|(_idx, ch)| {
-                                    *ch == '.'
-                                })
-                                .for_each(|(col, _ch)| {
-                                    cur.as_mut().unwrap().update_pin_height(col, rows);
-                                });
-                        }
-                    } else {
-                        assert_eq!(cur.as_ref().unwrap().heights_type, HeightsType::Key);
-                        if rows == MAX_HEIGHT {
-                            cur.as_mut().unwrap().update_all_nones();
-                            self.keys.push(cur.as_ref().unwrap().clone());
-                        } else {
-                            line.char_indices()
-                                .filter(|(_idx, ch)| {
-                                    *ch == '#'
-                                })
-                                .for_each(|(col, _ch)| {
-                                    cur.as_mut().unwrap().update_pin_height(col, MAX_HEIGHT - rows);
-                                });
-                        }
+            Sections::from_file_any(filename)
+                .iter()
+                .map(|section| Heights::from_grid(&section.grid()))
+                .for_each(|heights| {
+                    match heights.heights_type {
+                        HeightsType::Lock => self.locks.push(heights),
+                        HeightsType::Key => self.keys.push(heights),
                     }
-                    rows += 1;
-                }
-            })
+                });
         }
 
         pub fn num_pairs_fitting(&self) -> usize {
@@ -8,5 +8,7 @@ pub mod day_05;
 pub mod day_06;
 pub mod day_07;
 pub mod day_08;
+pub mod day_09;
 pub mod day_10;
-pub mod day_11;
\ No newline at end of file
+pub mod day_11;
+pub mod day_12;
\ No newline at end of file
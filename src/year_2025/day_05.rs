@@ -160,7 +160,7 @@ mod utils {
 }
 
 pub mod part_one {
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::{io_utils::Sections, solution::{Answer, Solution}};
     use super::utils::FreshIngredientRanges;
 
     #[derive(Debug, Default)]
@@ -170,23 +170,14 @@ pub mod part_one {
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            let mut ingredient_range_mode = true;
-            let mut fresh_ingredients: usize = 0;
-            io_utils::file_to_lines(filename)
-                .for_each(|line| {
-                    if line.len() == 0 {
-                        ingredient_range_mode = false;
-                    } else if ingredient_range_mode {
-                        self.fresh_ingredient_ranges.add_range_str(&line);
-                    } else {
-                        // Check for freshness of ingredient
-                        if self.fresh_ingredient_ranges.is_fresh(
-                            line.parse().unwrap(),
-                        ) {
-                            fresh_ingredients += 1;
-                        }
-                    }
-                });
+            let sections = Sections::from_file(filename, 2);
+            sections.nth(0)
+                .lines()
+                .for_each(|line| self.fresh_ingredient_ranges.add_range_str(line));
+            let fresh_ingredients = sections.nth(1)
+                .lines()
+                .filter(|line| self.fresh_ingredient_ranges.is_fresh(line.parse().unwrap()))
+                .count();
             Answer::Usize(fresh_ingredients)
         }
     }
@@ -211,7 +202,7 @@ pub mod part_one {
 }
 
 pub mod part_two {
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::{io_utils::Sections, solution::{Answer, Solution}};
     use super::utils::FreshIngredientRanges;
 
     #[derive(Debug, Default)]
@@ -221,12 +212,10 @@ pub mod part_two {
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
-            for line in io_utils::file_to_lines(filename) {
-                if line.len() == 0 {
-                    break;
-                }
-                self.fresh_ingredient_ranges.add_range_str(&line);
-            }
+            Sections::from_file(filename, 2)
+                .nth(0)
+                .lines()
+                .for_each(|line| self.fresh_ingredient_ranges.add_range_str(line));
             Answer::Usize(self.fresh_ingredient_ranges.total_fresh_ingredients())
         }
     }
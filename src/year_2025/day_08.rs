@@ -4,103 +4,36 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2025, day: 8 };
 
 mod utils {
-    use std::{cmp::Reverse, collections::{BinaryHeap, HashMap, HashSet}};
+    use std::{cmp::Reverse, collections::BinaryHeap};
 
     use itertools::Itertools;
 
-    use crate::utils::io_utils;
+    use crate::utils::{disjoint_set::DisjointSet, io_utils};
 
     #[derive(Debug, Default)]
     struct Circuits {
-        point_to_circuit_id: HashMap<Point, usize>,
-        circuits: HashMap<usize, HashSet<Point>>,
-        next_circuit_id: usize,
-        num_points: usize,
+        circuits: DisjointSet<Point>,
     }
 
     impl Circuits {
-        pub fn new(num_points: usize) -> Self {
+        pub fn new(points: &[Point]) -> Self {
             Self {
-                point_to_circuit_id: HashMap::default(),
-                circuits: HashMap::default(),
-                next_circuit_id: usize::default(),
-                num_points,
+                circuits: points.iter().cloned().collect(),
             }
         }
 
         // Returns whether the connection created one fully connected circuit
         fn add_connection(&mut self, points: Vec<&Point>) -> bool {
-            match (
-                self.point_to_circuit_id.get(&points[0]),
-                self.point_to_circuit_id.get(&points[1]),
-            ) {
-                (None, None) => {
-                    // Create the new circuit
-                    self.circuits.insert(
-                        self.next_circuit_id,
-                        HashSet::from_iter(points.clone().into_iter().cloned())
-                    );
-                    points.into_iter().for_each(|pt| {
-                        self.point_to_circuit_id.insert(
-                            *pt,
-                            self.next_circuit_id,
-                        );
-                    });
-                    self.next_circuit_id += 1;
-                },
-                (Some(&id), None) => {
-                    self.point_to_circuit_id.insert(*points[1], id);
-                    self.circuits.entry(id)
-                        .and_modify(|circuit| circuit.extend(points.into_iter()));
-                }, 
-                (None, Some(&id)) => {
-                    self.point_to_circuit_id.insert(*points[0], id);
-                    self.circuits.entry(id)
-                        .and_modify(|circuit| circuit.extend(points.into_iter()));
-                },
-                (Some(&id_0), Some(&id_1)) => {
-                    // Merge the circuits
-                    if id_0 != id_1 {
-                        if self.circuits.get(&id_0).unwrap().len() <= self.circuits.get(&id_1).unwrap().len() {
-                            let circuit_0 = self.circuits.remove(&id_0).unwrap();
-                            self.circuits.entry(id_1)
-                                .and_modify(|circuit| {
-                                    circuit.extend(circuit_0.iter());
-                                });
-                            for pt in circuit_0.iter() {
-                                self.point_to_circuit_id.insert(
-                                    *pt,
-                                    id_1,
-                                );
-                            }
-                        } else {
-                            let circuit_1 = self.circuits.remove(&id_1).unwrap();
-                            self.circuits.entry(id_0)
-                                .and_modify(|circuit| {
-                                    circuit.extend(circuit_1.iter());
-                                });
-                            for pt in circuit_1.iter() {
-                                self.point_to_circuit_id.insert(
-                                    *pt,
-                                    id_0,
-                                );
-                            }
-
-                        }
-                    }
-                },
-            }
+            self.circuits.union(*points[0], *points[1]);
             self.is_completely_connected()
         }
 
         fn is_completely_connected(&self) -> bool {
-            self.circuits.len() == 1 && self.circuits.values().next().unwrap().len() == self.num_points
+            self.circuits.num_components() == 1
         }
 
         fn product_of_largest_circuits(&self, num_circuits: usize) -> usize {
-            let mut circuit_lengths = self.circuits.values()
-                .map(|v| v.len())
-                .collect::<Vec<usize>>();
+            let mut circuit_lengths = self.circuits.component_sizes();
             circuit_lengths.sort_by(|a, b| b.cmp(a));
             circuit_lengths.into_iter()
                 .take(num_circuits)
@@ -168,7 +101,7 @@ mod utils {
                     }
                 })
                 .collect();
-            self.circuits = Circuits::new(self.points.len());
+            self.circuits = Circuits::new(&self.points);
         }
 
         pub fn make_shortest_connections(&mut self) {
@@ -0,0 +1,207 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2025, day: 9 };
+
+mod utils {
+    use std::collections::VecDeque;
+
+    use itertools::Itertools;
+
+    use crate::utils::{io_utils, parse, polygon::Vertex};
+
+    fn area(a: &Vertex, b: &Vertex) -> u64 {
+        (a.x.abs_diff(b.x) + 1) * (a.y.abs_diff(b.y) + 1)
+    }
+
+    /// The tiles' coordinates along one axis, compressed so that each
+    /// distinct coordinate gets its own index, with a gap index between
+    /// neighbouring coordinates that have other coordinates between them.
+    /// Index 0 and the last index are gaps outside every tile.
+    #[derive(Debug)]
+    struct CompressedAxis {
+        coords: Vec<i64>,
+        indices: Vec<usize>,
+    }
+
+    impl CompressedAxis {
+        fn new(coords: impl Iterator<Item = i64>) -> Self {
+            let coords: Vec<i64> = coords.sorted().dedup().collect();
+            let mut indices = Vec::with_capacity(coords.len());
+            let mut index = 0;
+            for (i, coord) in coords.iter().enumerate() {
+                // Neighbouring coordinates only have an empty gap between
+                // them if they differ by more than one.
+                index += if i > 0 && coord - coords[i - 1] == 1 { 1 } else { 2 };
+                indices.push(index - 1);
+            }
+            Self { coords, indices }
+        }
+
+        fn index(&self, coord: i64) -> usize {
+            self.indices[self.coords.binary_search(&coord).expect("Coordinate should be a tile's.")]
+        }
+
+        fn len(&self) -> usize {
+            self.indices.last().map_or(0, |index| index + 1) + 1
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Theater {
+        red_tiles: Vec<Vertex>,
+    }
+
+    impl Theater {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            self.red_tiles = io_utils::file_to_lines(filename)
+                .map(|line| {
+                    let [x, y] = parse::ints(&line)[..] else { panic!("Tile should have two coordinates."); };
+                    Vertex::new(x, y)
+                })
+                .collect();
+        }
+
+        /// The largest rectangle with red tiles in opposite corners.
+        pub fn largest_rectangle(&self) -> u64 {
+            self.red_tiles.iter()
+                .tuple_combinations()
+                .map(|(a, b)| area(a, b))
+                .max()
+                .unwrap()
+        }
+
+        /// The largest rectangle with red tiles in opposite corners that only
+        /// covers red or green tiles, i.e. lies inside the loop through the
+        /// red tiles.
+        ///
+        /// The coordinates are far too big for a grid, but only the tiles'
+        /// own coordinates matter: everything between two neighbouring ones
+        /// is either all inside or all outside the loop. So the loop is drawn
+        /// on a compressed grid, the outside flood filled, and a prefix sum
+        /// of outside cells checks each rectangle in constant time.
+        pub fn largest_rectangle_inside(&self) -> u64 {
+            let xs = CompressedAxis::new(self.red_tiles.iter().map(|tile| tile.x));
+            let ys = CompressedAxis::new(self.red_tiles.iter().map(|tile| tile.y));
+            let mut on_loop = vec![vec![false; ys.len()]; xs.len()];
+            let next_tiles = self.red_tiles.iter().cycle().skip(1);
+            for (a, b) in self.red_tiles.iter().zip(next_tiles) {
+                let (x_1, x_2) = (xs.index(a.x), xs.index(b.x));
+                let (y_1, y_2) = (ys.index(a.y), ys.index(b.y));
+                for column in on_loop.iter_mut().take(x_1.max(x_2) + 1).skip(x_1.min(x_2)) {
+                    column[y_1.min(y_2)..=y_1.max(y_2)].fill(true);
+                }
+            }
+
+            let mut outside = vec![vec![false; ys.len()]; xs.len()];
+            outside[0][0] = true;
+            let mut queue: VecDeque<(usize, usize)> = VecDeque::from([(0, 0)]);
+            while let Some((x, y)) = queue.pop_front() {
+                let neighbors = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+                for (x, y) in neighbors {
+                    if x < xs.len() && y < ys.len() && !on_loop[x][y] && !outside[x][y] {
+                        outside[x][y] = true;
+                        queue.push_back((x, y));
+                    }
+                }
+            }
+
+            // num_outside[x][y] counts the outside cells below x and y.
+            let mut num_outside = vec![vec![0; ys.len() + 1]; xs.len() + 1];
+            for x in 0..xs.len() {
+                for y in 0..ys.len() {
+                    num_outside[x + 1][y + 1] = num_outside[x][y + 1] + num_outside[x + 1][y] - num_outside[x][y]
+                        + usize::from(outside[x][y]);
+                }
+            }
+            let is_inside = |a: &Vertex, b: &Vertex| {
+                let (x_1, x_2) = (xs.index(a.x.min(b.x)), xs.index(a.x.max(b.x)) + 1);
+                let (y_1, y_2) = (ys.index(a.y.min(b.y)), ys.index(a.y.max(b.y)) + 1);
+                num_outside[x_2][y_2] + num_outside[x_1][y_1] == num_outside[x_1][y_2] + num_outside[x_2][y_1]
+            };
+
+            self.red_tiles.iter()
+                .tuple_combinations()
+                .filter(|(a, b)| is_inside(a, b))
+                .map(|(a, b)| area(a, b))
+                .max()
+                .unwrap()
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Theater;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        theater: Theater,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.theater.parse_input_file(filename);
+            Answer::U64(self.theater.largest_rectangle())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(50); "example_1")]
+        #[test_case(2, Answer::U64(81); "example_2")]
+        #[test_case(3, Answer::U64(121); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
+
+pub mod part_two {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Theater;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        theater: Theater,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.theater.parse_input_file(filename);
+            Answer::U64(self.theater.largest_rectangle_inside())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::U64(24); "example_1")]
+        #[test_case(2, Answer::U64(36); "example_2")]
+        #[test_case(3, Answer::U64(121); "example_3")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}
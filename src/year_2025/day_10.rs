@@ -184,10 +184,8 @@ pub mod part_one {
 pub mod part_two {
     use std::collections::HashSet;
 
-    use crate::utils::{io_utils, solution::{Answer, Solution}};
+    use crate::utils::{ilp::{Backend, LinearExpr, Model, Relation, Variable}, io_utils, solution::{Answer, Solution}};
     use super::utils::{MACHINE_RE, BUTTONS_RE};
-
-    use z3::{Optimize, SatResult, ast::Int};
     
     // TODO: this may be overkill
     #[derive(Debug, Default, PartialEq, Eq)]
@@ -197,7 +195,7 @@ pub mod part_two {
 
     #[derive(Debug, Default)]
     struct Machine {
-        button_presses: u64,
+        model: Model,
     }
 
     impl Machine {
@@ -214,65 +212,46 @@ pub mod part_two {
                     Button { idxs }
                 })
                 .collect();
-            let joltages: Vec<u64> = caps.name("joltages")
+            let joltages: Vec<i64> = caps.name("joltages")
                 .unwrap()
                 .as_str()
                 .split(',')
                 .map(|num| num.parse().unwrap())
                 .collect();
 
-            let optimize = Optimize::new();
-
-            let button_vars: Vec<Int> = buttons.iter()
-                .enumerate()
-                .map(|(idx, _btn)| {
-                    Int::fresh_const(format!("button_{}", idx).as_str())
-                })
+            let mut model = Model::default();
+            let max_joltage = *joltages.iter().max().unwrap();
+            let button_vars: Vec<Variable> = (0..buttons.len())
+                .map(|idx| model.int_var(format!("button_{}", idx).as_str(), 0, max_joltage))
                 .collect();
-            button_vars.iter().for_each(|button_var| optimize.assert(&button_var.ge(0)));
-            button_vars.iter().for_each(|button_var| optimize.assert(&button_var.le(*joltages.iter().max().unwrap())));
             joltages.iter()
                 .enumerate()
                 .for_each(|(joltage_idx, joltage)| {
-                    optimize.assert(
-                        &button_vars
-                            .iter()
-                            .enumerate()
-                            .filter_map(|(idx, button_var)| {
-                                if buttons[idx].idxs.contains(&joltage_idx) {
-                                    Some(button_var)
-                                } else {
-                                    None
-                                }
-                            })
-                            .fold(Int::from_u64(0), |acc, x| {
-                                acc + x
-                            })
-                            .eq(*joltage)
-                    );
-                });
-            let button_presses = button_vars.iter()
-                .fold(Int::from_u64(0), |acc, x| {
-                    acc + x
+                    let pressed = button_vars.iter()
+                        .enumerate()
+                        .filter(|(idx, _button_var)| buttons[*idx].idxs.contains(&joltage_idx))
+                        .map(|(_idx, button_var)| *button_var);
+                    model.constrain(LinearExpr::sum(pressed), Relation::Equal, *joltage);
                 });
-            optimize.minimize(&button_presses);
-            match optimize.check(&[]) {
-                SatResult::Sat => {
-                    let model = optimize.get_model().unwrap();
-                    let button_presses_val = model.eval(&button_presses, true).unwrap();
-                    Self { button_presses: button_presses_val.as_u64().unwrap() }                    
-                },
-                _ => panic!("Solver failed to optimize."),
-            }
+            model.minimize(LinearExpr::sum(button_vars));
+            Self { model }
         }
 
-        pub fn button_presses(&self) -> u64 {
-            self.button_presses
+        pub fn button_presses(&self, backend: Backend) -> u64 {
+            self.model.solve(backend).expect("Joltages should be reachable.").objective()
         }
     }
 
-    #[derive(Debug, Default)]
-    pub struct Soln {}
+    #[derive(Debug)]
+    pub struct Soln {
+        backend: Backend,
+    }
+
+    impl Default for Soln {
+        fn default() -> Self {
+            Self { backend: Backend::Z3 }
+        }
+    }
 
     impl Solution for Soln {
         fn solve(&mut self, filename: &str) -> Answer {
@@ -280,7 +259,7 @@ pub mod part_two {
                 io_utils::file_to_lines(filename)
                     .map(|line| {
                         let machine = Machine::from_str(&line);
-                        machine.button_presses()
+                        machine.button_presses(self.backend)
                     })
                     .sum()
             )
@@ -303,5 +282,15 @@ pub mod part_two {
                 &DAY,
             );
         }
+
+        #[test_case(1, Answer::U64(33); "example_1")]
+        fn examples_are_correct_without_z3(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln { backend: Backend::BranchAndBound },
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
     }    
 }
\ No newline at end of file
@@ -4,18 +4,18 @@ use crate::utils::Day;
 const DAY: Day = crate::utils::Day { year: 2025, day: 11 };
 
 mod utils {
-    use std::collections::{BTreeMap, HashMap};
-
     use lazy_static::lazy_static;
     use regex::Regex;
 
+    use crate::utils::dag::Dag;
+
     lazy_static! {
         pub static ref LINE_RE: Regex = Regex::new(r"(?<device>\w+): (?<output_devices>[\w ]+)").unwrap();
     }
 
     #[derive(Debug, Default, PartialEq, Eq)]
     pub struct ServerRack {
-        connections: HashMap<String, Vec<String>>,
+        connections: Dag<String>,
         required: Vec<String>,
     }
 
@@ -24,71 +24,26 @@ mod utils {
             required: Vec<String>,
         ) -> Self {
             Self {
-                connections: HashMap::default(),
+                connections: Dag::default(),
                 required,
             }
         }
 
         pub fn add_line(&mut self, line: &str) {
             let captures = LINE_RE.captures(line).unwrap();
-            self.connections.insert(
-                captures.name("device").unwrap().as_str().to_string(),
-                captures.name("output_devices").unwrap().as_str().split(' ').map(String::from).collect(),
-            );
+            let device = captures.name("device").unwrap().as_str();
+            captures.name("output_devices").unwrap().as_str()
+                .split(' ')
+                .for_each(|output_device| {
+                    self.connections.add_edge(device.to_string(), output_device.to_string());
+                });
         }
 
         pub fn num_paths(&self, start_device: &str) -> usize {
-            let mut cache: HashMap<(String, BTreeMap<String, bool>), usize> = HashMap::new();
-            let visited_required: BTreeMap<String, bool> = BTreeMap::from_iter(
-                self.required.iter().map(|d| {
-                    (d.to_string(), false)
-                })
-            );
-            self.num_paths_from_device(
-                &mut cache,
-                start_device,
-                visited_required,
-            )
-        }
-
-        fn num_paths_from_device(
-            &self,
-            cache: &mut HashMap<(String, BTreeMap<String, bool>), usize>,
-            device: &str,
-            visited_required: BTreeMap<String, bool>,
-        ) -> usize {
-            if device == "out" {
-                if visited_required.values().all(|visited| *visited) {
-                    1
-                } else {
-                    0
-                }
-            } else if let Some(paths) = cache.get(&(device.to_string(), visited_required.clone())) {
-                *paths
-            } else {
-                let mut vr = visited_required.clone();
-                vr.entry(device.to_string())
-                    .and_modify(|v| *v = true);
-                let paths = self.connections.get(device)
-                    .unwrap()
-                    .iter()
-                    .map(|d| {
-                        self.num_paths_from_device(
-                            cache,
-                            d,
-                            vr.clone(),
-                        )
-                    })
-                    .sum();
-                cache.insert(
-                    (
-                        device.to_string(),
-                        vr,
-                    ),
-                    paths,
-                );
-                paths
-            }
+            self.connections.num_paths_via(&start_device.to_string(), &"out".to_string(), &self.required)
+                .unwrap_or_else(|err| panic!("{err}"))
+                .try_into()
+                .expect("Number of paths should fit in a usize.")
         }
     }
 }
@@ -0,0 +1,124 @@
+#[cfg(test)]
+use crate::utils::Day;
+#[cfg(test)]
+const DAY: Day = crate::utils::Day { year: 2025, day: 12 };
+
+mod utils {
+    use crate::utils::{exact_cover::{Polyomino, PolyominoPacking}, io_utils, parse};
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    struct Region {
+        width: usize,
+        height: usize,
+        counts: Vec<usize>,
+    }
+
+    impl Region {
+        fn from_line(line: &str) -> Self {
+            let (size, counts) = line.split_once(": ").expect("Region should look like `WxH: counts`.");
+            let (width, height) = size.split_once('x').expect("Region size should look like `WxH`.");
+            Self {
+                width: width.parse().expect("Width should be a number."),
+                height: height.parse().expect("Height should be a number."),
+                counts: parse::ints(counts),
+            }
+        }
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Farm {
+        shapes: Vec<Polyomino>,
+        regions: Vec<Region>,
+    }
+
+    impl Farm {
+        pub fn parse_input_file(&mut self, filename: &str) {
+            let text = io_utils::file_to_string(filename);
+            let blocks = parse::blocks(&text);
+            let (regions, shapes) = blocks.split_last().expect("Input should list shapes then regions.");
+            self.shapes = shapes.iter().map(|lines| Polyomino::from_lines(&lines[1..])).collect();
+            self.regions = regions.iter().map(|line| Region::from_line(line)).collect();
+        }
+
+        /// Whether all the region's presents fit under its tree. Most
+        /// regions are settled without searching: either the presents have
+        /// more cells than the region, or each present's 3x3 bounding box
+        /// gets a block of its own. The rest are packed as an exact cover
+        /// problem, where presents of the same shape aren't told apart.
+        fn fits(&self, region: &Region) -> bool {
+            let area = region.width * region.height;
+            let num_cells: usize = region.counts.iter()
+                .zip(&self.shapes)
+                .map(|(count, shape)| count * shape.len())
+                .sum();
+            if num_cells > area {
+                return false;
+            }
+            if region.counts.iter().sum::<usize>() <= (region.width / 3) * (region.height / 3) {
+                return true;
+            }
+            let presents = self.shapes.iter().cloned().zip(region.counts.iter().copied()).collect();
+            PolyominoPacking::new(region.height, region.width, presents, true)
+                .exact_cover()
+                .first_solution()
+                .is_some()
+        }
+
+        pub fn num_fitting_regions(&self) -> usize {
+            self.regions.iter().filter(|region| self.fits(region)).count()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::io_utils::{self, InputFileType};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(0, true; "region_0")]
+        #[test_case(1, true; "region_1")]
+        #[test_case(2, false; "region_2")]
+        fn regions_fit(idx: usize, fits: bool) {
+            let mut farm = Farm::default();
+            farm.parse_input_file(&io_utils::input_filename(&DAY, InputFileType::Example(1)));
+            assert_eq!(farm.fits(&farm.regions[idx]), fits);
+        }
+    }
+}
+
+pub mod part_one {
+    use crate::utils::solution::{Answer, Solution};
+
+    use super::utils::Farm;
+
+    #[derive(Debug, Default)]
+    pub struct Soln {
+        farm: Farm,
+    }
+
+    impl Solution for Soln {
+        fn solve(&mut self, filename: &str) -> Answer {
+            self.farm.parse_input_file(filename);
+            Answer::Usize(self.farm.num_fitting_regions())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use test_case::test_case;
+        use crate::utils::{test_utils, solution::Answer};
+        use super::*;
+        use super::super::DAY;
+
+        #[test_case(1, Answer::Usize(2); "example_1")]
+        fn examples_are_correct(example_key: u8, answer: Answer) {
+            test_utils::check_example_case(
+                &mut Soln::default(),
+                example_key,
+                answer,
+                &DAY,
+            );
+        }
+    }
+}